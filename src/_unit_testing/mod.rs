@@ -0,0 +1,7 @@
+// auto-publib no-use
+
+mod test_inversed_kinematics;
+mod test_robot_config_migration;
+mod test_robot_template;
+mod test_urdf_import;
+mod test_xml_element;
\ No newline at end of file
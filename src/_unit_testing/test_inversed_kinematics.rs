@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod test {
+	use crate::kinematics::{ calculate_leg_rotations, RobotSkeletonLeg, RobotSkeletonSegment };
+
+	static ROTATION_ROUNDING_MAX_OFFSET:f32 = 0.01;
+
+	/// Create a leg with a motor per given axis, each followed by a segment of the given endpoint.
+	fn create_leg(segments:&[(u8, [f32; 3])]) -> RobotSkeletonLeg {
+		let mut leg:RobotSkeletonLeg = RobotSkeletonLeg::new([0.0; 3]);
+		for (index, (axis, endpoint)) in segments.iter().enumerate() {
+			leg.add_segment(RobotSkeletonSegment::new(*axis, [index, index + 1], *endpoint));
+		}
+		leg
+	}
+
+	/// Assert the rotations match the expected joints and rotations in degrees.
+	fn assert_rotations(rotations:&[(usize, f32)], expected:&[(usize, f32)]) {
+		assert_eq!(rotations.len(), expected.len(), "{rotations:?} != {expected:?}");
+		for ((joint, rotation), (expected_joint, expected_rotation)) in rotations.iter().zip(expected) {
+			assert_eq!(joint, expected_joint);
+			assert!((rotation - expected_rotation).abs() < ROTATION_ROUNDING_MAX_OFFSET, "{rotations:?} != {expected:?}");
+		}
+	}
+
+
+
+	/* PLANAR LEGS */
+
+	#[test]
+	fn inversed_kinematics_planar_leg_reaches_target_in_plane() {
+		let leg:RobotSkeletonLeg = create_leg(&[(0, [0.0, 1.0, 0.0]), (0, [0.0, 1.0, 0.0])]);
+
+		// Without a motor over the rigid axis, only the flexible motors are rotated.
+		let rotations:Vec<(usize, f32)> = calculate_leg_rotations(&leg, [0.0, 2.0_f32.sqrt(), 0.0]).unwrap();
+		assert_rotations(&rotations, &[(0, -45.0), (1, 90.0)]);
+
+		// Targets barely off the plane are still considered in the plane.
+		assert!(calculate_leg_rotations(&leg, [0.0005, 2.0_f32.sqrt(), 0.0]).is_some());
+	}
+
+	#[test]
+	fn inversed_kinematics_planar_leg_rejects_target_out_of_plane() {
+		let leg:RobotSkeletonLeg = create_leg(&[(0, [0.0, 1.0, 0.0]), (0, [0.0, 1.0, 0.0])]);
+		assert!(calculate_leg_rotations(&leg, [0.5, 1.0, 0.0]).is_none());
+		assert!(calculate_leg_rotations(&leg, [-0.5, 1.0, 0.0]).is_none());
+	}
+
+	#[test]
+	fn inversed_kinematics_planar_leg_rejects_unreachable_target() {
+		let leg:RobotSkeletonLeg = create_leg(&[(0, [0.0, 1.0, 0.0]), (0, [0.0, 1.0, 0.0])]);
+		assert!(calculate_leg_rotations(&leg, [0.0, 2.5, 0.0]).is_none());
+	}
+
+
+
+	/* RIGID ROTATION */
+
+	#[test]
+	fn inversed_kinematics_rigid_rotation_points_at_target() {
+		let leg:RobotSkeletonLeg = create_leg(&[(2, [0.0, 1.0, 0.0]), (0, [0.0, 1.0, 0.0]), (0, [0.0, 1.0, 0.0])]);
+		let rotations:Vec<(usize, f32)> = calculate_leg_rotations(&leg, [1.0, 1.0, 0.0]).unwrap();
+		assert_rotations(&rotations[..1], &[(0, 45.0)]);
+		let rotations:Vec<(usize, f32)> = calculate_leg_rotations(&leg, [0.0, 2.0, 0.0]).unwrap();
+		assert_rotations(&rotations[..1], &[(0, 0.0)]);
+	}
+
+	#[test]
+	fn inversed_kinematics_rigid_rotation_wraps_within_half_turn() {
+		let leg:RobotSkeletonLeg = create_leg(&[(2, [0.0, 1.0, 0.0]), (0, [0.0, 1.0, 0.0]), (0, [0.0, 1.0, 0.0])]);
+
+		// Behind the motor on the positive side, the rotation stays below 180 degrees.
+		let rotations:Vec<(usize, f32)> = calculate_leg_rotations(&leg, [0.5, -2.0, 0.0]).unwrap();
+		assert_rotations(&rotations[..1], &[(0, 180.0 - 0.25_f32.atan().to_degrees())]);
+
+		// Behind the motor on the negative side, the rotation wraps around to the negative side instead of passing 180 degrees.
+		let rotations:Vec<(usize, f32)> = calculate_leg_rotations(&leg, [-0.5, -2.0, 0.0]).unwrap();
+		assert_rotations(&rotations[..1], &[(0, -180.0 + 0.25_f32.atan().to_degrees())]);
+
+		// Every rotation stays within a half turn either way.
+		for step in 0..36 {
+			let angle:f32 = (step as f32 * 10.0 + 5.0).to_radians();
+			let rotations:Vec<(usize, f32)> = calculate_leg_rotations(&leg, [angle.sin() * 2.0, angle.cos() * 2.0, 0.0]).unwrap();
+			assert!(rotations[0].1.abs() <= 180.0, "{rotations:?}");
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod test {
+	use crate::robot_configuration::{ KinematicsConfig, MotorConfig, PartConfig, RobotConfig, OBJ_PATH_MAX_LEN };
+	use dynamic_data_storage::{ GeneralDataType, MigrationRegistry };
+
+	/// Create the bytes of an obj path in the layout used before schema versions existed, a null-terminated path padded to a fixed length.
+	fn legacy_obj_bytes(obj:&str) -> Vec<u8> {
+		[obj.as_bytes().to_vec(), vec![0u8; OBJ_PATH_MAX_LEN + 1 - obj.len()]].concat()
+	}
+
+	/// Create the bytes of a joint in the layout used before schema versions existed.
+	fn legacy_joint_bytes(obj:&str, motor:Option<&MotorConfig>, kinematics:Option<[f32; 8]>) -> Vec<u8> {
+		let mut bytes:Vec<u8> = legacy_obj_bytes(obj);
+		bytes.extend_from_slice(&[1.0f32, 2.0, 3.0].to_bytes());
+		bytes.extend_from_slice(&[0.0f32, 90.0, 0.0].to_bytes());
+		bytes.extend_from_slice(&match motor { Some(motor) => [vec![1], motor.to_bytes()].concat(), None => vec![0] });
+		bytes.extend_from_slice(&match kinematics { Some(values) => [vec![1], values.to_bytes()].concat(), None => vec![0] });
+		bytes
+	}
+
+	/// Migrate the bytes of a robot config stored before schema versions existed.
+	fn migrate_legacy_config(bytes:&[u8]) -> RobotConfig {
+		RobotConfig::register_migrations();
+		let stored_versions:Vec<u16> = [vec![0], RobotConfig::item_schema_versions()].concat();
+		RobotConfig::from_bytes(&MigrationRegistry::get().migrate::<RobotConfig>(bytes, &stored_versions).unwrap()).unwrap()
+	}
+
+
+
+	#[test]
+	fn robot_config_migrates_legacy_body_and_legs() {
+		let motor:MotorConfig = MotorConfig::new([0.5, 0.0, 0.0], 2, 10.0, [-90.0, 90.0]);
+
+		// A body with two legs, the first of two joints.
+		let mut bytes:Vec<u8> = [vec![1], legacy_obj_bytes("body.obj")].concat();
+		bytes.extend_from_slice(&2u16.to_bytes());
+		bytes.extend_from_slice(&2u16.to_bytes());
+		bytes.extend_from_slice(&legacy_joint_bytes("hip.obj", Some(&motor), None));
+		bytes.extend_from_slice(&legacy_joint_bytes("foot.obj", None, Some([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0])));
+		bytes.extend_from_slice(&1u16.to_bytes());
+		bytes.extend_from_slice(&legacy_joint_bytes("arm.obj", None, None));
+		let config:RobotConfig = migrate_legacy_config(&bytes);
+
+		// The legs are chains of parts attached to the body.
+		let parts:&Vec<PartConfig> = config.parts();
+		assert_eq!(parts.iter().map(|part| (part.name().as_str(), *part.parent(), part.obj().as_str())).collect::<Vec<(&str, Option<usize>, &str)>>(), vec![
+			("Body", None, "body.obj"),
+			("Leg0 Joint0", Some(0), "hip.obj"),
+			("Leg0 Joint1", Some(1), "foot.obj"),
+			("Leg1 Joint0", Some(0), "arm.obj")
+		]);
+		assert_eq!(parts[1].position(), &[1.0, 2.0, 3.0]);
+		assert_eq!(parts[1].rotation(), &[0.0, 90.0, 0.0]);
+		assert_eq!(parts[1].motor().as_ref().unwrap().to_bytes(), motor.to_bytes());
+		assert!(parts[1].kinematics_config().is_none());
+		assert!(parts[2].motor().is_none());
+		let kinematics:&KinematicsConfig = parts[2].kinematics_config().as_ref().unwrap();
+		assert_eq!((kinematics.leg_endpoint(), kinematics.step_position(), kinematics.step_distance(), kinematics.step_height()), (&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], &7.0, &8.0));
+		assert!(kinematics.manipulator().is_none());
+		assert!(parts.iter().all(|part| part.primitive().is_none()));
+	}
+
+	#[test]
+	fn robot_config_migrates_legacy_without_body() {
+		let mut bytes:Vec<u8> = vec![0];
+		bytes.extend_from_slice(&1u16.to_bytes());
+		bytes.extend_from_slice(&1u16.to_bytes());
+		bytes.extend_from_slice(&legacy_joint_bytes("leg.obj", None, None));
+		let config:RobotConfig = migrate_legacy_config(&bytes);
+		assert_eq!(config.parts().len(), 1);
+		assert_eq!(config.parts()[0].parent(), &None);
+		assert_eq!(config.parts()[0].obj(), "leg.obj");
+	}
+
+	#[test]
+	fn robot_config_rejects_truncated_legacy_layout() {
+		let mut bytes:Vec<u8> = vec![0];
+		bytes.extend_from_slice(&1u16.to_bytes());
+		bytes.extend_from_slice(&1u16.to_bytes());
+		bytes.extend_from_slice(&legacy_joint_bytes("leg.obj", None, None)[..100]);
+		RobotConfig::register_migrations();
+		let stored_versions:Vec<u16> = [vec![0], RobotConfig::item_schema_versions()].concat();
+		assert!(MigrationRegistry::get().migrate::<RobotConfig>(&bytes, &stored_versions).is_err());
+	}
+}
\ No newline at end of file
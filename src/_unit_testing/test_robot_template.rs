@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod test {
+	use crate::{ kinematics::{ calculate_leg_rotations, RobotSkeleton, RobotSkeletonLeg }, robot_configuration::{ ConfigDiagnostic, DiagnosticSeverity, RobotConfig, RobotTemplate, TemplateParameters } };
+
+	/// Get the parameters to test a template with. Contains the default parameters and the boundaries of what a template can be generated with.
+	fn test_parameters(template:&RobotTemplate) -> Vec<TemplateParameters> {
+		let default_parameters:TemplateParameters = template.default_parameters();
+		let motor_count:usize = template.leg_motor_count();
+		let mut parameters:Vec<TemplateParameters> = vec![
+			default_parameters.clone(),
+			TemplateParameters::new(*default_parameters.leg_count(), vec![1.0; motor_count], 1.0),
+			TemplateParameters::new(*default_parameters.leg_count(), vec![1000.0; motor_count], 1000.0),
+			TemplateParameters::new(*default_parameters.leg_count(), (0..motor_count).map(|index| if index == motor_count - 1 { 100.0 } else { 50.0 }).collect(), 10.0),
+			TemplateParameters::new(*default_parameters.leg_count(), (0..motor_count).map(|index| if index == motor_count - 1 { 50.0 } else { 100.0 }).collect(), 200.0)
+		];
+		if *template == RobotTemplate::Hexapod || *template == RobotTemplate::Quadruped {
+			parameters.push(TemplateParameters::new(1, default_parameters.segment_lengths().clone(), *default_parameters.hip_radius()));
+			parameters.push(TemplateParameters::new(2, default_parameters.segment_lengths().clone(), *default_parameters.hip_radius()));
+			parameters.push(TemplateParameters::new(12, default_parameters.segment_lengths().clone(), *default_parameters.hip_radius()));
+		}
+		parameters
+	}
+
+
+
+	/* GENERATION TESTS */
+
+	#[test]
+	fn robot_template_generates_valid_config() {
+		for template in RobotTemplate::all() {
+			for parameters in test_parameters(&template) {
+				let robot_config:RobotConfig = template.generate(&parameters).unwrap();
+				let errors:Vec<ConfigDiagnostic> = robot_config.validate().into_iter().filter(|diagnostic| *diagnostic.severity() == DiagnosticSeverity::Error).collect();
+				assert!(errors.is_empty(), "{template:?} with {parameters:?}: {errors:?}");
+				assert_eq!(robot_config.legs().len(), *parameters.leg_count(), "{template:?} with {parameters:?}");
+			}
+		}
+	}
+
+	#[test]
+	fn robot_template_legs_reach_step_position() {
+		for template in RobotTemplate::all() {
+			for parameters in test_parameters(&template) {
+				let robot_config:RobotConfig = template.generate(&parameters).unwrap();
+				let skeleton:RobotSkeleton = RobotSkeleton::from_config(&robot_config);
+				assert_eq!(skeleton.legs().len(), *parameters.leg_count(), "{template:?} with {parameters:?}");
+				for leg in skeleton.legs() {
+					let leg:&RobotSkeletonLeg = leg.as_ref().unwrap();
+					assert_eq!(leg.segments().len(), template.leg_motor_count(), "{template:?} with {parameters:?}");
+					let rotations:Option<Vec<(usize, f32)>> = calculate_leg_rotations(leg, *leg.step_position());
+					assert!(rotations.is_some_and(|rotations| rotations.len() == template.leg_motor_count() && rotations.iter().all(|(_, rotation)| rotation.is_finite())), "{template:?} with {parameters:?} cannot reach {:?}", leg.step_position());
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn robot_template_rejects_invalid_parameters() {
+		for template in RobotTemplate::all() {
+			let default_parameters:TemplateParameters = template.default_parameters();
+			let motor_count:usize = template.leg_motor_count();
+			let leg_count:usize = *default_parameters.leg_count();
+			let hip_radius:f32 = *default_parameters.hip_radius();
+			let invalid_parameters:Vec<TemplateParameters> = vec![
+				TemplateParameters::new(0, default_parameters.segment_lengths().clone(), hip_radius),
+				TemplateParameters::new(leg_count, vec![50.0; motor_count - 1], hip_radius),
+				TemplateParameters::new(leg_count, vec![50.0; motor_count + 1], hip_radius),
+				TemplateParameters::new(leg_count, Vec::new(), hip_radius),
+				TemplateParameters::new(leg_count, vec![0.0; motor_count], hip_radius),
+				TemplateParameters::new(leg_count, vec![-50.0; motor_count], hip_radius),
+				TemplateParameters::new(leg_count, vec![f32::NAN; motor_count], hip_radius),
+				TemplateParameters::new(leg_count, vec![f32::INFINITY; motor_count], hip_radius),
+				TemplateParameters::new(leg_count, default_parameters.segment_lengths().clone(), 0.0),
+				TemplateParameters::new(leg_count, default_parameters.segment_lengths().clone(), -30.0),
+				TemplateParameters::new(leg_count, default_parameters.segment_lengths().clone(), f32::NAN)
+			];
+			for parameters in invalid_parameters {
+				assert!(template.generate(&parameters).is_err(), "{template:?} accepted {parameters:?}");
+			}
+		}
+	}
+}
\ No newline at end of file
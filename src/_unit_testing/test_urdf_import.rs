@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod test {
+	use crate::{ importing::{ aligned_primitive_shape, import_urdf, motor_axis }, robot_configuration::{ PrimitiveShape, RobotConfig } };
+	use std::{ f32::consts::{ FRAC_PI_2, FRAC_PI_4 }, fs, path::PathBuf };
+
+	static SIZE_ROUNDING_MAX_OFFSET:f32 = 0.0001;
+
+	/// Write a robot description to a temporary file and import it.
+	fn import_text(file_name:&str, text:&str, scale:f32) -> (RobotConfig, Vec<String>) {
+		let path:PathBuf = std::env::temp_dir().join(file_name);
+		fs::write(&path, text).unwrap();
+		let result:(RobotConfig, Vec<String>) = import_urdf(&path.display().to_string(), scale).unwrap();
+		fs::remove_file(&path).unwrap();
+		result
+	}
+
+	/// Assert the box sizes match.
+	fn assert_box_size(shape:&PrimitiveShape, expected:[f32; 3]) {
+		match shape {
+			PrimitiveShape::Box { size } => assert!(size.iter().zip(expected).all(|(value, expected)| (value - expected).abs() < SIZE_ROUNDING_MAX_OFFSET), "{size:?} != {expected:?}"),
+			_ => panic!("Expected a box.")
+		}
+	}
+
+
+
+	/* MOTOR AXIS */
+
+	#[test]
+	fn motor_axis_matches_urdf_axes() {
+		assert_eq!(motor_axis(&[1.0, 0.0, 0.0]), (0, -1.0));
+		assert_eq!(motor_axis(&[-1.0, 0.0, 0.0]), (0, 1.0));
+		assert_eq!(motor_axis(&[0.0, 1.0, 0.0]), (1, 1.0));
+		assert_eq!(motor_axis(&[0.0, -1.0, 0.0]), (1, -1.0));
+		assert_eq!(motor_axis(&[0.0, 0.0, 1.0]), (2, -1.0));
+		assert_eq!(motor_axis(&[0.0, 0.0, -1.0]), (2, 1.0));
+	}
+
+	#[test]
+	fn motor_axis_uses_closest_axis() {
+		assert_eq!(motor_axis(&[0.2, -0.9, 0.1]), (1, -1.0));
+		assert_eq!(motor_axis(&[0.6, 0.0, 0.8]), (2, -1.0));
+	}
+
+
+
+	/* ALIGNED PRIMITIVE SHAPE */
+
+	#[test]
+	fn aligned_primitive_shape_keeps_unrotated_shapes() {
+		let (shape, aligned) = aligned_primitive_shape(&PrimitiveShape::Box { size: [1.0, 2.0, 3.0] }, &[0.0; 3]);
+		assert!(aligned);
+		assert_box_size(&shape, [1.0, 2.0, 3.0]);
+	}
+
+	#[test]
+	fn aligned_primitive_shape_turns_box_dimensions() {
+		let (shape, aligned) = aligned_primitive_shape(&PrimitiveShape::Box { size: [1.0, 2.0, 3.0] }, &[0.0, 0.0, FRAC_PI_2]);
+		assert!(aligned);
+		assert_box_size(&shape, [2.0, 1.0, 3.0]);
+		let (shape, aligned) = aligned_primitive_shape(&PrimitiveShape::Box { size: [1.0, 2.0, 3.0] }, &[FRAC_PI_2, 0.0, 0.0]);
+		assert!(aligned);
+		assert_box_size(&shape, [1.0, 3.0, 2.0]);
+	}
+
+	#[test]
+	fn aligned_primitive_shape_turns_cylinder_axis() {
+		let (shape, aligned) = aligned_primitive_shape(&PrimitiveShape::Cylinder { radius: 1.0, length: 2.0, axis: 2 }, &[FRAC_PI_2, 0.0, 0.0]);
+		assert!(aligned);
+		assert!(matches!(shape, PrimitiveShape::Cylinder { axis: 1, .. }));
+		let (shape, aligned) = aligned_primitive_shape(&PrimitiveShape::Capsule { radius: 1.0, length: 2.0, axis: 2 }, &[0.0, FRAC_PI_2, 0.0]);
+		assert!(aligned);
+		assert!(matches!(shape, PrimitiveShape::Capsule { axis: 0, .. }));
+	}
+
+	#[test]
+	fn aligned_primitive_shape_rejects_diagonal_rotations() {
+		assert!(!aligned_primitive_shape(&PrimitiveShape::Box { size: [1.0, 2.0, 3.0] }, &[0.0, 0.0, FRAC_PI_4]).1);
+		assert!(!aligned_primitive_shape(&PrimitiveShape::Cylinder { radius: 1.0, length: 2.0, axis: 2 }, &[FRAC_PI_4, 0.0, 0.0]).1);
+		assert!(aligned_primitive_shape(&PrimitiveShape::Sphere { radius: 1.0 }, &[FRAC_PI_4, 0.0, 0.0]).1);
+	}
+
+
+
+	/* MESH SCALE WARNINGS */
+
+	#[test]
+	fn import_urdf_warns_about_mesh_units_once() {
+		let text:&str = "<robot name=\"bot\"><link name=\"a\"><visual><geometry><mesh filename=\"a.obj\"/></geometry></visual></link><link name=\"b\"><visual><geometry><mesh filename=\"b.obj\"/></geometry></visual></link><joint name=\"j\" type=\"fixed\"><parent link=\"a\"/><child link=\"b\"/></joint></robot>";
+		let (_, warnings) = import_text("test_urdf_import_mesh_units.urdf", text, 1000.0);
+		assert_eq!(warnings.iter().filter(|warning| warning.contains("times too small")).count(), 1, "{warnings:?}");
+		assert!(!warnings.iter().any(|warning| warning.contains("is scaled by")), "{warnings:?}");
+		let (_, warnings) = import_text("test_urdf_import_mesh_units_unscaled.urdf", text, 1.0);
+		assert!(!warnings.iter().any(|warning| warning.contains("times too small")), "{warnings:?}");
+	}
+
+	#[test]
+	fn import_urdf_warns_about_scaled_meshes() {
+		let text:&str = "<robot name=\"bot\"><link name=\"a\"><visual><geometry><mesh filename=\"a.obj\" scale=\"0.001 0.001 0.001\"/></geometry></visual></link></robot>";
+		let (_, warnings) = import_text("test_urdf_import_mesh_scale.urdf", text, 1.0);
+		assert_eq!(warnings.iter().filter(|warning| warning.contains("is scaled by")).count(), 1, "{warnings:?}");
+	}
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod test {
+	use crate::importing::XmlElement;
+
+
+
+	/* SKIPPED CONTENT */
+
+	#[test]
+	fn xml_element_skips_declaration_comments_and_cdata() {
+		let root:XmlElement = XmlElement::parse("<?xml version=\"1.0\"?>\n<!-- robot <link name=\"hidden\"/> -->\n<robot><![CDATA[<link name=\"hidden\"/>]]>text<link name=\"shown\"/><!-- end --></robot>\n<!-- trailing -->").unwrap();
+		assert_eq!(root.name(), "robot");
+		assert_eq!(root.children().len(), 1);
+		assert_eq!(root.children()[0].attribute("name"), Some("shown"));
+	}
+
+	#[test]
+	fn xml_element_reads_nested_children_and_attributes() {
+		let root:XmlElement = XmlElement::parse("<robot name='bot'>\n\t<link name=\"a\"><visual><origin xyz=\"1 2 3\" /></visual></link>\n\t<link name=\"b\"/>\n</robot>").unwrap();
+		assert_eq!(root.attribute("name"), Some("bot"));
+		assert_eq!(root.children_named("link").count(), 2);
+		assert_eq!(root.child("link").and_then(|link| link.child("visual")).and_then(|visual| visual.child("origin")).and_then(|origin| origin.attribute("xyz")), Some("1 2 3"));
+		assert_eq!(root.attribute("missing"), None);
+	}
+
+
+
+	/* ENTITIES */
+
+	#[test]
+	fn xml_element_unescapes_entities_in_attributes() {
+		let root:XmlElement = XmlElement::parse("<robot name=\"&lt;a&gt; &amp; &quot;b&quot; &apos;c&apos; &#65;&#x42;\"/>").unwrap();
+		assert_eq!(root.attribute("name"), Some("<a> & \"b\" 'c' AB"));
+	}
+
+	#[test]
+	fn xml_element_keeps_unknown_entities() {
+		let root:XmlElement = XmlElement::parse("<robot name=\"&unknown; & &#xZZ;\"/>").unwrap();
+		assert_eq!(root.attribute("name"), Some("&unknown; & &#xZZ;"));
+	}
+
+
+
+	/* ERRORS */
+
+	#[test]
+	fn xml_element_rejects_mismatched_closing_tag() {
+		let error:String = XmlElement::parse("<robot>\n<link>\n</joint>\n</robot>").err().unwrap().to_string();
+		assert!(error.contains("line 3") && error.contains("expected closing tag of 'link' but found 'joint'"), "{error}");
+	}
+
+	#[test]
+	fn xml_element_rejects_unterminated_attribute() {
+		let error:String = XmlElement::parse("<robot name=\"bot>\n</robot>").err().unwrap().to_string();
+		assert!(error.contains("unterminated attribute value"), "{error}");
+	}
+
+	#[test]
+	fn xml_element_rejects_missing_closing_tag() {
+		let error:String = XmlElement::parse("<robot><link/>").err().unwrap().to_string();
+		assert!(error.contains("missing closing tag of 'robot'"), "{error}");
+	}
+
+	#[test]
+	fn xml_element_rejects_unterminated_comment() {
+		let error:String = XmlElement::parse("<robot><!-- comment</robot>").err().unwrap().to_string();
+		assert!(error.contains("missing '-->'"), "{error}");
+	}
+
+	#[test]
+	fn xml_element_rejects_content_after_root() {
+		assert!(XmlElement::parse("<robot/><robot/>").is_err());
+		assert!(XmlElement::parse("just text").is_err());
+	}
+}
\ No newline at end of file
@@ -0,0 +1,147 @@
+use crate::{ robot_configuration::RobotConfig, storage };
+use dynamic_data_storage::{ GeneralDataType, StorageManager, TextFormat, TextValue };
+use std::{ error::Error, fs, path::Path, sync::atomic::{ AtomicBool, Ordering } };
+
+static SESSION_LOCK_FILE_NAME:&str = "session.lock";
+static AUTOSAVE_SLOT_FORMAT:TextFormat = TextFormat::Json;
+static UNCLEAN_SHUTDOWN_DETECTED:AtomicBool = AtomicBool::new(false);
+
+
+
+/* SESSION METHODS */
+
+/// Mark the start of an editor session. When the lock of the previous session still exists, that session did not end cleanly and its autosaves are offered to be restored.
+pub fn begin_session() -> Result<(), Box<dyn Error>> {
+	let lock_file:String = session_lock_file();
+	UNCLEAN_SHUTDOWN_DETECTED.store(Path::new(&lock_file).exists(), Ordering::Relaxed);
+	fs::create_dir_all(storage::user_storage_dir())?;
+	fs::write(&lock_file, std::process::id().to_string())?;
+	Ok(())
+}
+
+/// Mark the clean end of an editor session.
+pub fn end_session() -> Result<(), Box<dyn Error>> {
+	let lock_file:String = session_lock_file();
+	if Path::new(&lock_file).exists() {
+		fs::remove_file(lock_file)?;
+	}
+	Ok(())
+}
+
+/// Get the newest autosave if the previous session did not end cleanly and it was not restored yet.
+pub fn recoverable_autosave() -> Option<String> {
+	if UNCLEAN_SHUTDOWN_DETECTED.load(Ordering::Relaxed) { autosave_slots().into_iter().next() } else { None }
+}
+
+/// The file that exists while the editor is running.
+fn session_lock_file() -> String {
+	format!("{}/{SESSION_LOCK_FILE_NAME}", storage::user_storage_dir())
+}
+
+
+
+/* AUTOSAVE METHODS */
+
+/// List all autosave slots, newest first.
+pub fn autosave_slots() -> Vec<String> {
+	slots_in_dir(&autosaves_dir())
+}
+
+/// List all autosave slots in a specific dir, newest first.
+fn slots_in_dir(dir:&str) -> Vec<String> {
+	let extension:String = format!(".{}", AUTOSAVE_SLOT_FORMAT.extension());
+	let mut slots:Vec<String> = fs::read_dir(dir).map(|entries|
+		entries.flatten().map(|entry| entry.path().display().to_string().replace('\\', "/")).filter(|path| path.ends_with(&extension)).collect::<Vec<String>>()
+	).unwrap_or_default();
+	slots.sort_by(|a, b| b.cmp(a));
+	slots
+}
+
+/// Restore an autosave slot as a new project and open it. Returns the name of the new project.
+pub fn restore_autosave(slot:&str) -> Result<String, Box<dyn Error>> {
+	let original_name:String = slot_project_name(slot);
+	let project_name:String = storage::unique_project_name(&if original_name == storage::AUTO_SAVE_PROJECT_NAME { String::from("Recovered session") } else { format!("{original_name} (recovered)") });
+	fs::create_dir_all(format!("{}/{project_name}", storage::projects_dir()))?;
+	storage::select_project(&project_name)?;
+	storage::import_project_text(slot)?;
+	RobotConfig::save();
+	UNCLEAN_SHUTDOWN_DETECTED.store(false, Ordering::Relaxed);
+	Ok(project_name)
+}
+
+/// The dir to store the autosaves of all projects in.
+pub fn autosaves_dir() -> String {
+	format!("{}/autosaves", storage::user_storage_dir())
+}
+
+/// Get the name of the project an autosave slot was created from.
+fn slot_project_name(slot:&str) -> String {
+	let file_stem:String = Path::new(slot).file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+	file_stem.split_once('_').map(|(_, project_name)| project_name.to_string()).unwrap_or(file_stem)
+}
+
+
+
+// The state of the project at a specific moment, ready to be written to an autosave slot. Created on the UI thread, while writing it can happen on any thread.
+pub struct AutosaveSnapshot {
+	project_name:String,
+	dir:String,
+	timestamp:u128,
+	text:String
+}
+impl AutosaveSnapshot {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Take a snapshot of the current project, including changes to the robot config that were not saved yet.
+	pub fn from_current_project() -> Result<AutosaveSnapshot, Box<dyn Error>> {
+		use std::time::{ SystemTime, UNIX_EPOCH };
+
+		// Meshes in the assets dir of the project are stored relative to it, refer to them by their full path instead so the autosave can be restored as another project.
+		let mut robot_config:RobotConfig = RobotConfig::from_bytes(&RobotConfig::get().to_bytes())?;
+		for part in robot_config.parts_mut() {
+			if part.obj().starts_with(&format!("{}/", storage::PROJECT_ASSETS_DIR)) {
+				let absolute_path:String = std::path::absolute(storage::asset_path(part.obj()))?.display().to_string().replace('\\', "/");
+				*part.obj_mut() = absolute_path;
+			}
+		}
+
+		// Create the text version of the project.
+		let settings_text:String = StorageManager::get().categories_to_text(&["project_settings"], AUTOSAVE_SLOT_FORMAT)?;
+		let mut categories:Vec<(String, TextValue)> = AUTOSAVE_SLOT_FORMAT.parse(&settings_text)?.map()?.to_vec();
+		categories.push((String::from("project_robot_config"), TextValue::Map(vec![(String::from("robot_config"), TextValue::from_variable(&robot_config))])));
+
+		Ok(AutosaveSnapshot {
+			project_name: storage::project_name().to_string(),
+			dir: autosaves_dir(),
+			timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis(),
+			text: AUTOSAVE_SLOT_FORMAT.write(&TextValue::Map(categories))?
+		})
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Write the snapshot to a new autosave slot. Nothing is stored when the project did not change since its last autosave. Only the newest slots are kept. Returns the new slot, if any.
+	pub fn write(&self, slot_count:usize) -> Result<Option<String>, Box<dyn Error>> {
+
+		// Skip storing when the project is identical to its last autosave.
+		if let Some(last_slot) = slots_in_dir(&self.dir).into_iter().find(|slot| slot_project_name(slot) == self.project_name) {
+			if fs::read_to_string(last_slot).is_ok_and(|last_text| last_text == self.text) {
+				return Ok(None);
+			}
+		}
+
+		// Store the new slot.
+		fs::create_dir_all(&self.dir)?;
+		let slot:String = format!("{}/{:016}_{}.{}", self.dir, self.timestamp, self.project_name, AUTOSAVE_SLOT_FORMAT.extension());
+		fs::write(&slot, &self.text)?;
+
+		// Remove the oldest slots.
+		for old_slot in slots_in_dir(&self.dir).iter().skip(slot_count.max(1)) {
+			fs::remove_file(old_slot)?;
+		}
+		Ok(Some(slot))
+	}
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+use std::error::Error;
+
+
+
+pub struct CommandArguments {
+	command:String,
+	positional:Vec<String>,
+	options:Vec<(String, Option<String>)>
+}
+impl CommandArguments {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Parse a list of command-line arguments, excluding the executable itself. Options start with '--' and take the next argument as value unless it is another option.
+	pub fn parse(arguments:&[String]) -> Result<CommandArguments, Box<dyn Error>> {
+		if arguments.is_empty() {
+			return Err("No command given.".into());
+		}
+
+		// Split arguments into positional arguments and options.
+		let mut positional:Vec<String> = Vec::new();
+		let mut options:Vec<(String, Option<String>)> = Vec::new();
+		let mut cursor:usize = 1;
+		while cursor < arguments.len() {
+			let argument:&str = &arguments[cursor];
+			match argument.strip_prefix("--") {
+				Some(option_name) => {
+					let value:Option<String> = arguments.get(cursor + 1).filter(|value| !value.starts_with("--")).cloned();
+					if value.is_some() {
+						cursor += 1;
+					}
+					options.push((option_name.to_string(), value));
+				},
+				None => positional.push(argument.to_string())
+			}
+			cursor += 1;
+		}
+
+		Ok(CommandArguments {
+			command: arguments[0].to_string(),
+			positional,
+			options
+		})
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Return a reference to the command.
+	pub fn command(&self) -> &str {
+		&self.command
+	}
+
+	/// Get a positional argument by its index.
+	pub fn positional(&self, index:usize) -> Option<&str> {
+		self.positional.get(index).map(|argument| argument.as_str())
+	}
+
+	/// Get a positional argument by its index or return an error naming the missing argument.
+	pub fn required_positional(&self, index:usize, name:&str) -> Result<&str, Box<dyn Error>> {
+		self.positional(index).ok_or_else(|| format!("Missing argument <{name}> for command '{}'.", self.command).into())
+	}
+
+	/// Check if an option was given, with or without value.
+	pub fn flag(&self, name:&str) -> bool {
+		self.options.iter().any(|(option_name, _)| option_name == name)
+	}
+
+	/// Get the value of an option.
+	pub fn option(&self, name:&str) -> Option<&str> {
+		self.options.iter().find(|(option_name, _)| option_name == name).and_then(|(_, value)| value.as_deref())
+	}
+
+	/// Get the value of an option parsed to a specific type, or a default value if the option was not given.
+	pub fn option_or<T:std::str::FromStr>(&self, name:&str, default:T) -> Result<T, Box<dyn Error>> {
+		match self.option(name) {
+			Some(value) => value.parse::<T>().map_err(|_| format!("Invalid value '{value}' for option '--{name}'.").into()),
+			None => Ok(default)
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,396 @@
+use crate::{ exporting::{ arduino_sketch, trajectory_csv, export_urdf, URDF_DEFAULT_SCALE }, importing::import_urdf, kinematics::GaitTrajectory, project_archive::{ export_project_archive, import_project_archive, PROJECT_ARCHIVE_EXTENSION }, robot_configuration::{ RobotConfig, PartConfig, ConfigDiagnostic, DiagnosticSeverity, RobotTemplate, TemplateParameters }, storage, ui::Window };
+use super::CommandArguments;
+use dynamic_data_storage::TextFormat;
+use std::{ error::Error, fs, path::Path };
+
+
+
+pub static EXIT_CODE_SUCCESS:i32 = 0;
+pub static EXIT_CODE_FAILURE:i32 = 1;
+pub static EXIT_CODE_USAGE:i32 = 2;
+
+const DEFAULT_TRAJECTORY_SAMPLES:usize = 32;
+const DEFAULT_CYCLE_DURATION_MS:u32 = 1000;
+const DEFAULT_THUMBNAIL_SIZE:usize = 256;
+
+
+
+/* MAIN EXECUTION METHODS */
+
+/// Apply the options that go before the command and affect both the commands and the editor. Returns the remaining arguments.
+pub fn apply_global_options(arguments:&[String]) -> Result<Vec<String>, Box<dyn Error>> {
+	let mut cursor:usize = 0;
+	while let Some(argument) = arguments.get(cursor) {
+		match argument.as_str() {
+			"--data-dir" => {
+				let data_dir:&String = arguments.get(cursor + 1).ok_or("Missing value for option '--data-dir'.")?;
+				storage::set_user_storage_dir(data_dir);
+				cursor += 2;
+			},
+			_ => break
+		}
+	}
+	Ok(arguments[cursor..].to_vec())
+}
+
+
+/// Execute a command-line command without opening the UI. Returns the exit code of the process.
+pub fn execute(arguments:&[String]) -> i32 {
+	let arguments:CommandArguments = match CommandArguments::parse(arguments) {
+		Ok(arguments) => arguments,
+		Err(error) => {
+			eprintln!("{error}\n\n{}", usage());
+			return EXIT_CODE_USAGE;
+		}
+	};
+
+	// Find the requested command.
+	let command:fn(&CommandArguments) -> Result<bool, Box<dyn Error>> = match arguments.command() {
+		"validate" => validate,
+		"export-code" => export_code,
+		"export-trajectory" => export_trajectory,
+		"export-urdf" => export_urdf_description,
+		"import-urdf" => import_urdf_description,
+		"render-thumbnail" => render_thumbnail,
+		"export-text" => export_text,
+		"import-text" => import_text,
+		"pack" => pack,
+		"export-archive" => export_archive,
+		"import-archive" => import_archive,
+		"new" => new_project,
+		"list" => list,
+		"roots" => roots,
+		"info" => info,
+		"help" | "--help" | "-h" => {
+			println!("{}", usage());
+			return EXIT_CODE_SUCCESS;
+		},
+		unknown => {
+			eprintln!("Unknown command '{unknown}'.\n\n{}", usage());
+			return EXIT_CODE_USAGE;
+		}
+	};
+
+	// Execute the command.
+	match command(&arguments) {
+		Ok(true) => EXIT_CODE_SUCCESS,
+		Ok(false) => EXIT_CODE_FAILURE,
+		Err(error) => {
+			eprintln!("Error: {error}");
+			EXIT_CODE_FAILURE
+		}
+	}
+}
+
+/// Get the usage description of all commands.
+pub fn usage() -> String {
+	[
+		"Usage: kinematic_flow [global options] [<command> [arguments] [options]]",
+		"Without a command, the editor is opened.",
+		"",
+		"Global options:",
+		"  --data-dir <dir>                          Store user settings and projects in this dir. Can also be set using the KINEMATIC_FLOW_DATA_DIR environment variable.",
+		"",
+		"Commands:",
+		"  validate <project>                        Check the robot config and if all legs can reach their targets during a full step cycle.",
+		"  export-code <project> --target arduino    Generate a program that replays the step cycle.",
+		"      [--output <file>] [--first-pin <pin>] [--samples <count>] [--duration <ms>]",
+		"  export-trajectory <project>               Export the motor rotations of a full step cycle as CSV.",
+		"      [--output <file>] [--samples <count>]",
+		"  export-urdf <project> --output <dir>      Export the robot as URDF description with its meshes, for use in simulators.",
+		"      [--scale <factor>]",
+		"  import-urdf <project> <file>              Replace the robot of the project by a URDF robot description.",
+		"      [--scale <factor>]",
+		"  render-thumbnail <project>                Render an image of the robot.",
+		"      [--output <file>] [--size <pixels>]",
+		"  export-text <project>                     Export the project as readable text, for example for version control.",
+		"      [--output <file.json|file.toml>] [--format json|toml]",
+		"  import-text <project> <file>              Replace the project with a .json or .toml file created by export-text.",
+		"  pack <project>                            Copy all meshes into the assets dir of the project, so it can be moved to another machine.",
+		"  export-archive <project>                  Export the project with its meshes and thumbnail as a single file to share it.",
+		"      [--output <file>]",
+		"  import-archive <file>                     Create a new project from a file created by export-archive.",
+		"      [--name <name>]",
+		"  new <template>                            Create a new project with a generated robot. Templates: hexapod, quadruped, leg-2dof, leg-3dof.",
+		"      [--name <name>] [--legs <count>] [--segments <length,...>] [--hip-radius <length>]",
+		"  list                                      List all projects in all project roots, most recently modified first.",
+		"  roots [add|remove <dir>]                  List the dirs searched for projects, or add or remove one.",
+		"  info <project>                            Show a summary of a project.",
+		"  help                                      Show this message."
+	].join("\n")
+}
+
+
+
+/* COMMAND METHODS */
+
+/// Validate the config of a project and check that it can complete a full step cycle.
+fn validate(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	open_project(arguments.required_positional(0, "project")?)?;
+
+	// Report config diagnostics.
+	let robot_config:&RobotConfig = RobotConfig::get();
+	let diagnostics:Vec<ConfigDiagnostic> = robot_config.validate();
+	for diagnostic in &diagnostics {
+		match diagnostic.part_index().and_then(|part_index| robot_config.part(part_index)) {
+			Some(part) => eprintln!("{} in '{}': {}", diagnostic.severity(), part.name(), diagnostic.message()),
+			None => eprintln!("{diagnostic}")
+		}
+	}
+	let mut valid:bool = !diagnostics.iter().any(|diagnostic| diagnostic.severity() == &DiagnosticSeverity::Error);
+
+	// Without motors there is nothing to walk with.
+	let trajectory:GaitTrajectory = GaitTrajectory::create(arguments.option_or("samples", DEFAULT_TRAJECTORY_SAMPLES)?);
+	if trajectory.motors().is_empty() {
+		eprintln!("Project '{}' has no motors.", storage::project_name());
+		return Ok(false);
+	}
+
+	// Report unreachable steps.
+	for (progress, leg_index) in trajectory.unreachable_positions() {
+		eprintln!("{} cannot reach its target at {:.0}% of the step cycle.", leg_name(leg_index), progress * 100.0);
+		valid = false;
+	}
+	if valid {
+		println!("Project '{}' is valid.", storage::project_name());
+	}
+	Ok(valid)
+}
+
+/// Export code for a micro-controller that replays the step cycle.
+fn export_code(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	let project:&str = arguments.required_positional(0, "project")?;
+	let target:&str = arguments.option("target").ok_or("Missing option '--target'.")?;
+	if target != "arduino" {
+		return Err(format!("Unsupported target '{target}', supported targets are: arduino.").into());
+	}
+	open_project(project)?;
+
+	// Create the trajectory, refuse to export a robot that cannot walk.
+	let trajectory:GaitTrajectory = GaitTrajectory::create(arguments.option_or("samples", DEFAULT_TRAJECTORY_SAMPLES)?);
+	if !trajectory.is_reachable() {
+		return Err("Not all legs can reach their targets, run 'validate' for details.".into());
+	}
+
+	// Write the code.
+	let code:String = arduino_sketch(&trajectory, arguments.option_or("first-pin", 2)?, arguments.option_or("duration", DEFAULT_CYCLE_DURATION_MS)?);
+	write_output(arguments.option("output"), &code)?;
+	Ok(true)
+}
+
+/// Export the motor rotations of a step cycle.
+fn export_trajectory(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	open_project(arguments.required_positional(0, "project")?)?;
+	let trajectory:GaitTrajectory = GaitTrajectory::create(arguments.option_or("samples", DEFAULT_TRAJECTORY_SAMPLES)?);
+	write_output(arguments.option("output"), &trajectory_csv(&trajectory))?;
+	for (progress, leg_index) in trajectory.unreachable_positions() {
+		eprintln!("Warning: {} cannot reach its target at {:.0}% of the step cycle.", leg_name(leg_index), progress * 100.0);
+	}
+	Ok(true)
+}
+
+/// Export the robot as URDF description.
+fn export_urdf_description(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	let project:&str = arguments.required_positional(0, "project")?;
+	let output:&str = arguments.option("output").ok_or("Missing option '--output'.")?;
+	open_project(project)?;
+	let (urdf_file, missing_meshes) = export_urdf(output, arguments.option_or("scale", URDF_DEFAULT_SCALE)?)?;
+	for mesh in &missing_meshes {
+		eprintln!("Warning: Could not find mesh '{mesh}', its part is exported without geometry.");
+	}
+	println!("Robot description written to '{urdf_file}'.");
+	Ok(true)
+}
+
+/// Replace the robot by a URDF description.
+fn import_urdf_description(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	open_project(arguments.required_positional(0, "project")?)?;
+	let file:&str = arguments.required_positional(1, "file")?;
+	let (robot_config, warnings) = import_urdf(file, arguments.option_or("scale", 1.0 / URDF_DEFAULT_SCALE)?)?;
+	for warning in &warnings {
+		eprintln!("Warning: {warning}");
+	}
+	RobotConfig::replace(robot_config);
+	RobotConfig::save();
+	println!("Imported '{file}' into project '{}'.", storage::project_name());
+	Ok(true)
+}
+
+/// Render a thumbnail of the robot.
+fn render_thumbnail(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	open_project(arguments.required_positional(0, "project")?)?;
+	let output:String = arguments.option("output").map(|output| output.to_string()).unwrap_or(storage::project_thumbnail_file());
+	let size:usize = arguments.option_or("size", DEFAULT_THUMBNAIL_SIZE)?;
+	Window::render_robot_png(&output, [size, size])?;
+	println!("Thumbnail written to '{output}'.");
+	Ok(true)
+}
+
+/// Export a project as readable text.
+fn export_text(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	open_project(arguments.required_positional(0, "project")?)?;
+	let format:TextFormat = match (arguments.option("format"), arguments.option("output")) {
+		(Some("json"), _) => TextFormat::Json,
+		(Some("toml"), _) => TextFormat::Toml,
+		(Some(format), _) => return Err(format!("Unsupported format '{format}', supported formats are: json, toml.").into()),
+		(None, Some(output)) => TextFormat::from_path(output)?,
+		(None, None) => TextFormat::Toml
+	};
+	write_output(arguments.option("output"), &storage::project_to_text(format)?)?;
+	Ok(true)
+}
+
+/// Import a project from readable text.
+fn import_text(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	open_project(arguments.required_positional(0, "project")?)?;
+	let file:&str = arguments.required_positional(1, "file")?;
+	storage::import_project_text(file)?;
+	println!("Imported '{file}' into project '{}'.", storage::project_name());
+	Ok(true)
+}
+
+/// Copy all meshes of a project into the project itself.
+fn pack(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	open_project(arguments.required_positional(0, "project")?)?;
+	let missing_meshes:Vec<String> = storage::pack_project()?;
+	for mesh in &missing_meshes {
+		eprintln!("Could not find mesh '{mesh}', it was not copied into the project.");
+	}
+	println!("Packed meshes of project '{}' into '{}'.", storage::project_name(), storage::project_assets_dir());
+	Ok(missing_meshes.is_empty())
+}
+
+/// Export a project as a single archive file.
+fn export_archive(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	open_project(arguments.required_positional(0, "project")?)?;
+	let output:String = arguments.option("output").map(|output| output.to_string()).unwrap_or(format!("{}.{PROJECT_ARCHIVE_EXTENSION}", storage::project_name()));
+	export_project_archive(&output)?;
+	println!("Project '{}' exported to '{output}'.", storage::project_name());
+	Ok(true)
+}
+
+/// Create a new project from an archive file.
+fn import_archive(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	let file:&str = arguments.required_positional(0, "file")?;
+	storage::init_storage()?;
+	let project_name:String = import_project_archive(file, arguments.option("name"))?;
+	println!("Imported '{file}' as project '{project_name}'.");
+	Ok(true)
+}
+
+/// Create a new project from a robot template.
+fn new_project(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	let template_id:&str = arguments.required_positional(0, "template")?;
+	let template:RobotTemplate = RobotTemplate::from_id(template_id).ok_or_else(|| format!("Unknown template '{template_id}', available templates are: {}.", RobotTemplate::all().map(|template| template.id()).join(", ")))?;
+
+	// Override the default parameters by the given options.
+	let mut parameters:TemplateParameters = template.default_parameters();
+	*parameters.leg_count_mut() = arguments.option_or("legs", *parameters.leg_count())?;
+	*parameters.hip_radius_mut() = arguments.option_or("hip-radius", *parameters.hip_radius())?;
+	if let Some(segments) = arguments.option("segments") {
+		*parameters.segment_lengths_mut() = segments.split(',').map(|length| length.trim().parse::<f32>().map_err(|_| format!("Invalid segment length '{length}'."))).collect::<Result<Vec<f32>, String>>()?;
+	}
+
+	storage::init_storage()?;
+	let project_name:String = template.create_project(arguments.option("name"), &parameters)?;
+	println!("Created project '{project_name}' from template '{}'.", template.id());
+	Ok(true)
+}
+
+/// List all projects.
+fn list(_arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	storage::init_storage()?;
+	for project_name in storage::latest_modified_projects() {
+		println!("{project_name}");
+	}
+	Ok(true)
+}
+
+/// List, add or remove the dirs containing projects.
+fn roots(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	storage::init_storage()?;
+	match arguments.positional(0) {
+		None => {
+			for root in storage::project_roots() {
+				println!("{root}");
+			}
+		},
+		Some("add") => {
+			let dir:&str = arguments.required_positional(1, "dir")?;
+			storage::add_project_root(dir)?;
+			println!("Added project root '{dir}'.");
+		},
+		Some("remove") => {
+			let dir:&str = arguments.required_positional(1, "dir")?;
+			if !storage::remove_project_root(dir)? {
+				eprintln!("'{dir}' is not a project root.");
+				return Ok(false);
+			}
+			println!("Removed project root '{dir}'.");
+		},
+		Some(unknown) => return Err(format!("Unknown roots action '{unknown}', expected 'add' or 'remove'.").into())
+	}
+	Ok(true)
+}
+
+/// Show a summary of a project.
+fn info(arguments:&CommandArguments) -> Result<bool, Box<dyn Error>> {
+	open_project(arguments.required_positional(0, "project")?)?;
+	let robot_config:&RobotConfig = RobotConfig::get();
+	println!("Project: {}", storage::project_name());
+	println!("Location: {}", storage::project_dir());
+
+	// Show part tree.
+	println!("Parts: {}", robot_config.parts().len());
+	for (part_index, depth) in robot_config.tree_order() {
+		let part:&PartConfig = &robot_config.parts()[part_index];
+		let is_manipulator:bool = part.kinematics_config().as_ref().is_some_and(|kinematics| kinematics.manipulator().is_some());
+		let tags:Vec<&str> = [("motor", part.motor().is_some()), ("leg endpoint", part.kinematics_config().is_some() && !is_manipulator), ("manipulator endpoint", is_manipulator)].iter().filter(|(_, active)| *active).map(|(tag, _)| *tag).collect::<Vec<&str>>();
+		println!("  {}{}{}", "  ".repeat(depth), part.name(), if tags.is_empty() { String::new() } else { format!(" ({})", tags.join(", ")) });
+	}
+
+	// Show legs and manipulators.
+	for (title, chains) in [("Legs", robot_config.legs()), ("Manipulators", robot_config.manipulators())] {
+		println!("{title}: {}", chains.len());
+		for chain in &chains {
+			let motor_count:usize = chain.iter().filter(|part_index| robot_config.parts()[**part_index].motor().is_some()).count();
+			println!("  {}: {} parts, {motor_count} motors", robot_config.parts()[chain[chain.len() - 1]].name(), chain.len());
+		}
+	}
+	Ok(true)
+}
+
+
+
+/* HELPER METHODS */
+
+/// Select an existing project by its name or path.
+fn open_project(project_source:&str) -> Result<(), Box<dyn Error>> {
+	storage::init_storage()?;
+	let project_path:Option<String> = if project_source.contains('/') { Some(project_source.to_string()) } else { storage::find_project_dir(project_source) };
+	if !project_path.is_some_and(|project_path| Path::new(&project_path).is_dir()) {
+		return Err(format!("Project '{project_source}' does not exist.").into());
+	}
+	storage::select_project(project_source)
+}
+
+/// Get a readable name for a leg based on the part at its end.
+fn leg_name(leg_index:usize) -> String {
+	let robot_config:&RobotConfig = RobotConfig::get();
+	match robot_config.legs().get(leg_index) {
+		Some(leg) => format!("Leg '{}'", robot_config.parts()[leg[leg.len() - 1]].name()),
+		None => format!("Leg {leg_index}")
+	}
+}
+
+/// Write output to a file, or to the standard output if no file is given.
+fn write_output(output:Option<&str>, contents:&str) -> Result<(), Box<dyn Error>> {
+	match output {
+		Some(path) => {
+			fs::write(path, contents).map_err(|error| format!("Could not write '{path}': {error}"))?;
+			println!("Written to '{path}'.");
+		},
+		None => print!("{contents}")
+	}
+	Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+// auto-publib 
+
+mod arguments;
+mod commands;
+
+pub use arguments::*;
+pub use commands::*;
\ No newline at end of file
@@ -36,9 +36,9 @@ fn auto_implement_data_types() {
 
 	// Create datasets per type.
 	let data_sets = vec![
-		("UNSIGNED INT", "u$bit_size", vec![8, 16, 32, 64, 128]),
-		("SIGNED INT", "i$bit_size", vec![8, 16, 32, 64, 128]),
-		("FLOATING POINT NUMBER", "f$bit_size", vec![32, 64])
+		("UNSIGNED INT", "u$bit_size", "integer", vec![8, 16, 32, 64, 128]),
+		("SIGNED INT", "i$bit_size", "integer", vec![8, 16, 32, 64, 128]),
+		("FLOATING POINT NUMBER", "f$bit_size", "float", vec![32, 64])
 	];
 
 	// Loop through datasets.
@@ -49,8 +49,8 @@ fn auto_implement_data_types() {
 		code_lines.push(String::new());
 
 		// Implementations.
-		for bit_size in &data_set.2 {
-			code_lines.push(generate_datatype_implementation(data_set.1, bit_size / 8));
+		for bit_size in &data_set.3 {
+			code_lines.push(generate_datatype_implementation(data_set.1, data_set.2, bit_size / 8));
 		}
 
 		// Spacing for next banner.
@@ -67,14 +67,16 @@ fn auto_implement_data_types() {
 }
 
 /// Generate the code for one datatype implementation.
-fn generate_datatype_implementation(name:&str, byte_size:usize) -> String {
+fn generate_datatype_implementation(name:&str, text_kind:&str, byte_size:usize) -> String {
 r#"impl GeneralDataType for $name {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok($name::from_le_bytes([$from_implementation])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok($name::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { $byte_size }
+	fn to_text_value(&self) -> TextValue { TextValue::from_$text_kind(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.$text_kind() }
 }"#
 	.replace("$name", name)
+	.replace("$text_kind", text_kind)
 	.replace("$byte_size", &byte_size.to_string())
 	.replace("$bit_size", &(byte_size * 8).to_string())
-	.replace("$from_implementation", &(0..byte_size).map(|index| format!("bytes[{index}]")).collect::<Vec<String>>().join(", "))
 }
\ No newline at end of file
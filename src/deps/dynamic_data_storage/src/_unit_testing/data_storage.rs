@@ -17,7 +17,7 @@ mod test {
 		// Create storage manager.
 		StorageManager::hard_reset();
 		let test_storage_path:TestStoragePath = TestStoragePath::get();
-		let storage_manager:&mut StorageManager = StorageManager::get_mut();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
 		storage_manager.set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
 
 		// Store value.
@@ -43,14 +43,14 @@ mod test {
 
 		// Store var.
 		{
-			let storage_manager:&mut StorageManager = StorageManager::get_mut();
+			let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
 			storage_manager.set_categories(vec![(CAT_NAME, &path)]).unwrap();
 			storage_manager.set_value::<u8>(CAT_NAME, VAR_NAME, &storage_value).unwrap();
 		}
 
 		// Load var.
 		{
-			let storage_manager:&mut StorageManager = StorageManager::get_mut();
+			let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
 			storage_manager.add_category_from_file(&path).unwrap();
 			let validation_value:u8 = storage_manager.get_value::<u8>(CAT_NAME, VAR_NAME).unwrap();
 
@@ -66,7 +66,7 @@ mod test {
 		// Create storage manager.
 		StorageManager::hard_reset();
 		let test_storage_path:TestStoragePath = TestStoragePath::get();
-		let storage_manager:&mut StorageManager = StorageManager::get_mut();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
 		storage_manager.set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
 
 		// Store value.
@@ -80,7 +80,7 @@ mod test {
 	pub fn test_storage_nonexistent_file() {
 		StorageManager::hard_reset();
 		let test_storage_path:TestStoragePath = TestStoragePath::get();
-		let storage_manager:&mut StorageManager = StorageManager::get_mut();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
 		storage_manager.add_category_from_file(&test_storage_path.path()).unwrap();
 	}
 
@@ -93,14 +93,14 @@ mod test {
 		// Store value.
 		let storage_value:u8 = 192;
 		{
-			let storage_manager_a:&mut StorageManager = StorageManager::get_mut();
+			let storage_manager_a:&mut StorageManager = &mut StorageManager::get_mut();
 			storage_manager_a.set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
 			storage_manager_a.set_value::<u8>(CAT_NAME, VAR_NAME, &storage_value).unwrap();
 		}
 
 		// Load value.
 		{
-			let storage_manager_b:&StorageManager = StorageManager::get();
+			let storage_manager_b:&StorageManager = &StorageManager::get();
 			let validation_value:u8 = storage_manager_b.get_value::<u8>(CAT_NAME, VAR_NAME).unwrap();
 
 			// Compare.
@@ -116,25 +116,28 @@ mod test {
 
 		// Create storage manager.
 		StorageManager::hard_reset();
-		let storage_manager:&mut StorageManager = StorageManager::get_mut();
-		
-		// Create categories.
 		let mut storage_paths:Vec<TestStoragePath> = Vec::new();
-		for category_index in 0..categories {
-			let category_name:String = format!("category_{category_index}");
-			let test_storage_path:TestStoragePath = TestStoragePath::get();
-			storage_manager.add_category(&category_name, &test_storage_path.path());
-			storage_paths.push(test_storage_path);
-
-			// Create variables.
-			for variable_index in 0..vars_per_category {
-				let var_name:String = format!("category_{category_index}_variable_{variable_index}");
-				storage_manager.set_value::<u16>(&category_name, &var_name, &(variable_index * category_index)).unwrap();
+		{
+			let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+
+			// Create categories.
+			for category_index in 0..categories {
+				let category_name:String = format!("category_{category_index}");
+				let test_storage_path:TestStoragePath = TestStoragePath::get();
+				storage_manager.add_category(&category_name, &test_storage_path.path()).unwrap();
+				storage_paths.push(test_storage_path);
+
+				// Create variables.
+				for variable_index in 0..vars_per_category {
+					let var_name:String = format!("category_{category_index}_variable_{variable_index}");
+					storage_manager.set_value::<u16>(&category_name, &var_name, &(variable_index * category_index)).unwrap();
+				}
 			}
 		}
 
 		// Reset storage manager and read from dir.
 		StorageManager::hard_reset();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
 		assert!(storage_manager.find_category("category_1").is_none());
 		storage_manager.add_categories_from_dir("./", true);
 
@@ -159,7 +162,7 @@ mod test {
 		// Create StorageManager.
 		StorageManager::hard_reset();
 		let test_storage_path:TestStoragePath = TestStoragePath::get();
-		let storage_manager:&mut StorageManager = StorageManager::get_mut();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
 		storage_manager.set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
 
 		// Create and validate file.
@@ -170,4 +173,34 @@ mod test {
 		storage_manager.remove_category(CAT_NAME).unwrap();
 		assert!(!Path::new(&test_storage_path.path()).exists());
 	}
+	#[test]
+	#[serial]
+	pub fn test_storage_store_at_new_path() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let new_storage_path:TestStoragePath = TestStoragePath::get();
+		let storage_value:u8 = 192;
+
+		// Store value and flush it.
+		{
+			let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+			storage_manager.set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
+			storage_manager.set_value::<u8>(CAT_NAME, VAR_NAME, &storage_value).unwrap();
+			storage_manager.flush().unwrap();
+		}
+
+		// Move the category to a new path without changing any value.
+		{
+			let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+			storage_manager.set_categories(vec![(CAT_NAME, &new_storage_path.path())]).unwrap();
+			assert!(storage_manager.is_dirty());
+			storage_manager.flush().unwrap();
+		}
+
+		// The new path holds the value.
+		StorageManager::hard_reset();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.add_category_from_file(&new_storage_path.path()).unwrap();
+		assert_eq!(storage_manager.get_value::<u8>(CAT_NAME, VAR_NAME).unwrap(), storage_value);
+	}
 }
\ No newline at end of file
@@ -0,0 +1,195 @@
+/* As DataStorage is a SingleTon, make sure all tests run synchronically using #[serial] and hard reset the StorageManager before each test to make sure no data leaks into other tests. */
+
+#[cfg(test)]
+mod test {
+	use crate::{ GeneralDataType, StorageManager, TextFormat, TextValue, _unit_testing::TestStoragePath };
+	use serial_test::serial;
+	use std::collections::HashMap;
+
+	static CAT_NAME:&str = "main";
+	static VAR_NAME:&str = "test_var";
+
+
+
+	/// Validate a value stays the same after storing and parsing it.
+	fn assert_round_trip<T:GeneralDataType + PartialEq + std::fmt::Debug>(value:T) {
+		assert_eq!(T::from_bytes(&value.to_bytes()).unwrap(), value);
+
+		// Parsing by consuming only removes the bytes of the value.
+		let mut bytes:Vec<u8> = [value.to_bytes(), vec![9]].concat();
+		assert_eq!(T::from_bytes_consume(&mut bytes).unwrap(), value);
+		assert_eq!(bytes, vec![9]);
+	}
+
+
+
+	/// Validate a value stays the same after converting it to text and parsing it, in all text formats.
+	fn assert_text_round_trip<T:GeneralDataType + PartialEq + std::fmt::Debug>(value:T) {
+		assert_eq!(T::from_text_value(&value.to_text_value()).unwrap(), value);
+		for format in [TextFormat::Json, TextFormat::Toml] {
+			let text:String = format.write(&TextValue::Map(vec![(String::from("value"), value.to_text_value())])).unwrap();
+			assert_eq!(T::from_text_value(format.parse(&text).unwrap().field("value")).unwrap(), value);
+		}
+	}
+
+
+
+	#[test]
+	pub fn test_data_type_bool() {
+		assert_eq!(true.to_bytes(), vec![1]);
+		assert_round_trip(true);
+		assert_round_trip(false);
+		assert!(bool::from_bytes(&[2]).is_err());
+	}
+
+	#[test]
+	pub fn test_data_type_char() {
+		assert_eq!(char::byte_size(), 4);
+		assert_round_trip('a');
+		assert_round_trip('ü');
+		assert!(char::from_bytes(&0xD800u32.to_bytes()).is_err());
+	}
+
+	#[test]
+	pub fn test_data_type_string() {
+		assert_eq!("ab".to_string().to_bytes(), vec![2, 0, 0, 0, b'a', b'b']);
+		assert_eq!("ü".to_string().to_bytes(), vec![2, 0, 0, 0, 0xC3, 0xBC]);
+		assert_round_trip(String::new());
+		assert_round_trip("Müller".to_string());
+		assert_round_trip("ロボットアーム".to_string());
+		assert_round_trip("🦾 arm\0 with null".to_string());
+		assert!(String::from_bytes(&[2, 0, 0, 0, 0xC3]).is_err());
+		assert!(String::from_bytes(&[2, 0, 0, 0, 0xC3, 0x28]).is_err());
+	}
+
+	#[test]
+	pub fn test_data_type_legacy_string() {
+		assert_eq!(crate::string_from_legacy_bytes(b"text\0"), "text");
+		assert_eq!(crate::string_from_legacy_bytes(&[0x4D, 0xFC, 0x6C, 0x6C, 0x65, 0x72, 0]), "Müller");
+		let mut bytes:Vec<u8> = b"first\0second\0".to_vec();
+		assert_eq!(crate::string_from_legacy_bytes_consume(&mut bytes), "first");
+		assert_eq!(crate::string_from_legacy_bytes_consume(&mut bytes), "second");
+		assert!(bytes.is_empty());
+	}
+
+	#[test]
+	pub fn test_data_type_option() {
+		assert_eq!(Some(2u16).to_bytes(), vec![1, 2, 0]);
+		assert_eq!(None::<u16>.to_bytes(), vec![0]);
+		assert_round_trip(Some(2u16));
+		assert_round_trip(None::<u16>);
+		assert_round_trip(Some(Some(1.5f32)));
+	}
+
+	#[test]
+	pub fn test_data_type_vec() {
+		assert_eq!(vec![1u8, 2].to_bytes(), vec![2, 0, 0, 0, 1, 2]);
+		assert_round_trip(vec![1u8, 2, 3]);
+		assert_round_trip(Vec::<u64>::new());
+		assert_round_trip(vec![vec![1i32], Vec::new(), vec![-2, 3]]);
+		assert_round_trip(vec!["a".to_string(), "bc".to_string()]);
+		assert!(Vec::<u16>::from_bytes(&[2, 0, 0, 0, 1, 0, 2, 0, 3]).is_err());
+
+		// Lists have a fixed schema version and keep track of the versions of their items.
+		assert_eq!(Vec::<String>::schema_version(), 1);
+		assert_eq!(Vec::<Vec<String>>::item_schema_versions(), vec![1, 1]);
+	}
+
+	#[test]
+	pub fn test_data_type_array() {
+		assert_eq!(<[f32; 3]>::byte_size(), 12);
+		assert_eq!(<[String; 2]>::byte_size(), 0);
+		assert_eq!([1u8, 2, 3].to_bytes(), vec![1, 2, 3]);
+		assert_round_trip([1.0f32, -2.0, 3.5]);
+		assert_round_trip([[1u16, 2], [3, 4]]);
+		assert_round_trip(["a".to_string(), String::new()]);
+	}
+
+	#[test]
+	pub fn test_data_type_tuple() {
+		assert_eq!(<(u8, f32)>::byte_size(), 5);
+		assert_eq!(<(u8, Vec<u8>)>::byte_size(), 0);
+		assert_eq!((1u8, 2u16).to_bytes(), vec![1, 2, 0]);
+		assert_round_trip((1u8,));
+		assert_round_trip((1u8, "text".to_string(), Some(3.0f64)));
+		assert_round_trip((1u8, 2u16, 3u32, 4u64, 5i8, true));
+		assert_eq!(<(String, u8)>::schema_version(), 0);
+		assert_eq!(<(String, Option<u8>)>::item_schema_versions(), vec![1, 0, 0]);
+	}
+
+	#[test]
+	pub fn test_data_type_hashmap() {
+		let mut map:HashMap<String, Vec<u16>> = HashMap::new();
+		map.insert("first".to_string(), vec![1, 2]);
+		map.insert("second".to_string(), Vec::new());
+		assert_round_trip(map);
+		assert_round_trip(HashMap::<u8, u8>::new());
+	}
+
+	#[test]
+	pub fn test_data_type_text_numbers() {
+		assert_eq!(0.1f32.to_text_value(), TextValue::Float(0.1));
+		assert_eq!(u128::MAX.to_text_value(), TextValue::Text(u128::MAX.to_string()));
+		assert_text_round_trip(0.1f32);
+		assert_text_round_trip(-1.5e-20f64);
+		assert_text_round_trip(f32::INFINITY);
+		assert_text_round_trip(u8::MAX);
+		assert_text_round_trip(i64::MIN);
+		assert_text_round_trip(u64::MAX);
+		assert_text_round_trip(u128::MAX);
+		assert_text_round_trip(usize::MAX);
+		assert!(f32::from_text_value(&f32::NAN.to_text_value()).unwrap().is_nan());
+		assert!(u8::from_text_value(&TextValue::Integer(256)).is_err());
+	}
+
+	#[test]
+	pub fn test_data_type_text_others() {
+		assert_text_round_trip(true);
+		assert_text_round_trip('ü');
+		assert_text_round_trip("Müller 🦾".to_string());
+		assert_text_round_trip(Some(2u16));
+		assert_text_round_trip(None::<u16>);
+		assert_text_round_trip(vec![Some(1u8), None, Some(3)]);
+		assert_text_round_trip([[1.0f32, 2.0], [3.0, 4.0]]);
+		assert_text_round_trip((1u8, "text".to_string(), Some(3.0f64)));
+		assert!(<[u8; 3]>::from_text_value(&vec![1u8, 2].to_text_value()).is_err());
+		assert!(char::from_text_value(&TextValue::Text(String::from("ab"))).is_err());
+	}
+
+	#[test]
+	pub fn test_data_type_text_hashmap() {
+		let mut text_keys:HashMap<String, Vec<u16>> = HashMap::new();
+		text_keys.insert("second".to_string(), vec![1, 2]);
+		text_keys.insert("first".to_string(), Vec::new());
+		assert_eq!(text_keys.to_text_value(), TextValue::Map(vec![
+			(String::from("first"), TextValue::List(Vec::new())),
+			(String::from("second"), TextValue::List(vec![TextValue::Integer(1), TextValue::Integer(2)]))
+		]));
+		assert_text_round_trip(text_keys);
+		let mut number_keys:HashMap<u32, String> = HashMap::new();
+		number_keys.insert(2, "two".to_string());
+		number_keys.insert(1, "one".to_string());
+		assert_text_round_trip(number_keys);
+		assert_text_round_trip(HashMap::<String, u8>::new());
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_data_type_composed_in_storage() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+		let mut map:HashMap<u32, (String, Option<[f32; 3]>)> = HashMap::new();
+		map.insert(1, ("first".to_string(), Some([1.0, 2.0, 3.0])));
+		map.insert(2, ("second".to_string(), None));
+
+		// Store var.
+		StorageManager::get_mut().set_categories(vec![(CAT_NAME, &path)]).unwrap();
+		StorageManager::get_mut().set_value(CAT_NAME, VAR_NAME, &map).unwrap();
+
+		// Load var.
+		StorageManager::hard_reset();
+		StorageManager::get_mut().add_category_from_file(&path).unwrap();
+		assert_eq!(StorageManager::get().get_value::<HashMap<u32, (String, Option<[f32; 3]>)>>(CAT_NAME, VAR_NAME).unwrap(), map);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod test {
+	use crate::{ GeneralDataType, TextFormat, TextValue };
+
+
+
+	/* TEST TYPES */
+
+	#[derive(GeneralDataType, PartialEq, Debug)]
+	struct FixedStruct {
+		position:[f32; 3],
+		axis:u8,
+		rotation:f64
+	}
+
+	#[derive(GeneralDataType, PartialEq, Debug)]
+	#[general_data_type(schema_version = 3)]
+	struct VariableStruct {
+		name:String,
+		parent:Option<usize>,
+		points:Vec<[f32; 2]>,
+		fixed:Option<FixedStruct>,
+		#[general_data_type(skip)]
+		cache:Option<f32>,
+		tail:u16
+	}
+
+	#[derive(GeneralDataType, PartialEq, Debug)]
+	struct TupleStruct(u8, Vec<u16>);
+
+	#[derive(GeneralDataType, PartialEq, Debug)]
+	enum Shape {
+		Empty,
+		Circle(f32),
+		Polygon { corners:Vec<[f32; 2]>, closed:u8 }
+	}
+
+	#[derive(GeneralDataType, PartialEq, Debug)]
+	struct Wrapper<T> {
+		value:T,
+		list:Vec<T>
+	}
+
+	/// Validate a value stays the same after storing and parsing it.
+	fn assert_round_trip<T:GeneralDataType + PartialEq + std::fmt::Debug>(value:T) {
+		assert_eq!(T::from_bytes(&value.to_bytes()).unwrap(), value);
+
+		// Parsing by consuming only removes the bytes of the value.
+		let mut bytes:Vec<u8> = [value.to_bytes(), vec![9]].concat();
+		assert_eq!(T::from_bytes_consume(&mut bytes).unwrap(), value);
+		assert_eq!(bytes, vec![9]);
+	}
+
+	/// Validate a value stays the same after converting it to text and parsing it, in all text formats.
+	fn assert_text_round_trip<T:GeneralDataType + PartialEq + std::fmt::Debug>(value:T) {
+		assert_eq!(T::from_text_value(&value.to_text_value()).unwrap(), value);
+		for format in [TextFormat::Json, TextFormat::Toml] {
+			let text:String = format.write(&TextValue::Map(vec![(String::from("value"), value.to_text_value())])).unwrap();
+			assert_eq!(T::from_text_value(format.parse(&text).unwrap().field("value")).unwrap(), value);
+		}
+	}
+
+	fn fixed_struct() -> FixedStruct {
+		FixedStruct { position: [1.0, -2.0, 3.5], axis: 2, rotation: 90.0 }
+	}
+
+
+
+	/* TESTS */
+
+	#[test]
+	pub fn test_derive_fixed_struct() {
+		assert_eq!(FixedStruct::byte_size(), 21);
+		assert_eq!(fixed_struct().to_bytes(), [1.0f32.to_bytes(), (-2.0f32).to_bytes(), 3.5f32.to_bytes(), vec![2], 90.0f64.to_bytes()].concat());
+		assert_round_trip(fixed_struct());
+	}
+
+	#[test]
+	pub fn test_derive_variable_struct() {
+		assert_eq!(VariableStruct::byte_size(), 0);
+		assert_eq!(VariableStruct::schema_version(), 3);
+		assert_eq!(FixedStruct::schema_version(), 0);
+		assert_round_trip(VariableStruct { name: "leg".to_string(), parent: Some(4), points: vec![[0.0, 1.0], [2.0, 3.0]], fixed: Some(fixed_struct()), cache: None, tail: 7 });
+		assert_round_trip(VariableStruct { name: String::new(), parent: None, points: Vec::new(), fixed: None, cache: None, tail: 0 });
+	}
+
+	#[test]
+	pub fn test_derive_skipped_field() {
+		let value:VariableStruct = VariableStruct { name: "leg".to_string(), parent: None, points: Vec::new(), fixed: None, cache: Some(1.0), tail: 7 };
+		let parsed:VariableStruct = VariableStruct::from_bytes(&value.to_bytes()).unwrap();
+		assert_eq!(parsed.cache, None);
+		assert_eq!(parsed.tail, 7);
+	}
+
+	#[test]
+	pub fn test_derive_tuple_struct() {
+		assert_eq!(TupleStruct(1, vec![2, 3]).to_bytes(), vec![1, 2, 0, 0, 0, 2, 0, 3, 0]);
+		assert_round_trip(TupleStruct(1, vec![2, 3]));
+	}
+
+	#[test]
+	pub fn test_derive_enum() {
+		assert_eq!(Shape::Empty.to_bytes(), vec![0]);
+		assert_eq!(Shape::Circle(1.0).to_bytes(), [vec![1], 1.0f32.to_bytes()].concat());
+		assert_round_trip(Shape::Empty);
+		assert_round_trip(Shape::Circle(2.5));
+		assert_round_trip(Shape::Polygon { corners: vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]], closed: 1 });
+		assert!(Shape::from_bytes(&[3]).is_err());
+	}
+
+	#[test]
+	pub fn test_derive_generic_struct() {
+		assert_round_trip(Wrapper { value: 1u32, list: vec![2, 3, 4] });
+		assert_round_trip(Wrapper { value: fixed_struct(), list: vec![fixed_struct()] });
+	}
+
+	#[test]
+	pub fn test_derive_text() {
+		assert_eq!(fixed_struct().to_text_value(), TextValue::Map(vec![
+			(String::from("position"), TextValue::List(vec![TextValue::Float(1.0), TextValue::Float(-2.0), TextValue::Float(3.5)])),
+			(String::from("axis"), TextValue::Integer(2)),
+			(String::from("rotation"), TextValue::Float(90.0))
+		]));
+		assert_text_round_trip(fixed_struct());
+		assert_text_round_trip(VariableStruct { name: "leg".to_string(), parent: Some(4), points: vec![[0.0, 1.0], [2.0, 3.0]], fixed: Some(fixed_struct()), cache: None, tail: 7 });
+		assert_text_round_trip(VariableStruct { name: String::new(), parent: None, points: Vec::new(), fixed: None, cache: None, tail: 0 });
+		assert_text_round_trip(TupleStruct(1, vec![2, 3]));
+		assert_text_round_trip(Wrapper { value: fixed_struct(), list: vec![fixed_struct()] });
+
+		// Errors name the field that could not be read.
+		let text:TextValue = TextValue::Map(vec![(String::from("position"), TextValue::List(vec![TextValue::Float(1.0); 3])), (String::from("axis"), TextValue::Integer(-1))]);
+		assert_eq!(FixedStruct::from_text_value(&text).err().unwrap().to_string(), "Could not read field 'axis': Could not read -1 as u8.");
+	}
+
+	#[test]
+	pub fn test_derive_text_skipped_field() {
+		let value:VariableStruct = VariableStruct { name: "leg".to_string(), parent: None, points: Vec::new(), fixed: None, cache: Some(1.0), tail: 7 };
+		assert!(!value.to_text_value().has_field("cache"));
+		assert_eq!(VariableStruct::from_text_value(&value.to_text_value()).unwrap().cache, None);
+	}
+
+	#[test]
+	pub fn test_derive_text_enum() {
+		assert_eq!(Shape::Empty.to_text_value(), TextValue::Text(String::from("Empty")));
+		assert_eq!(Shape::Circle(1.0).to_text_value(), TextValue::Map(vec![(String::from("Circle"), TextValue::List(vec![TextValue::Float(1.0)]))]));
+		assert_text_round_trip(Shape::Empty);
+		assert_text_round_trip(Shape::Circle(2.5));
+		assert_text_round_trip(Shape::Polygon { corners: vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]], closed: 1 });
+		assert!(Shape::from_text_value(&TextValue::Text(String::from("Square"))).is_err());
+	}
+
+	#[test]
+	pub fn test_derive_trailing_bytes() {
+		assert!(FixedStruct::from_bytes(&[fixed_struct().to_bytes(), vec![0]].concat()).is_err());
+		assert!(VariableStruct::from_bytes(&[VariableStruct { name: String::new(), parent: None, points: Vec::new(), fixed: None, cache: None, tail: 0 }.to_bytes(), vec![0]].concat()).is_err());
+	}
+}
\ No newline at end of file
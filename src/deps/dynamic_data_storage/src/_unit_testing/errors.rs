@@ -0,0 +1,134 @@
+/* As DataStorage is a SingleTon, make sure all tests run synchronically using #[serial] and hard reset the StorageManager before each test to make sure no data leaks into other tests. */
+
+#[cfg(test)]
+mod test {
+	use crate::{ GeneralDataType, StorageError, StorageManager, TextFormat, _unit_testing::TestStoragePath };
+	use serial_test::serial;
+
+	static CAT_NAME:&str = "main";
+	static VAR_NAME:&str = "test_var";
+	static FILE_RECOGNITION_TAG:&str = "<<DynamicDataStorageFile>>";
+
+
+
+	/// Create a versioned storage file holding the given variable bytes, with a checksum that matches them.
+	fn file_bytes(version:u16, variable_bytes:&[u8]) -> Vec<u8> {
+		let contents:Vec<u8> = [CAT_NAME.as_bytes(), &[0], variable_bytes].concat();
+		let checksum:u32 = contents.iter().fold(0x811c9dc5, |hash:u32, byte| (hash ^ *byte as u32).wrapping_mul(0x01000193));
+		[FILE_RECOGNITION_TAG.as_bytes(), &[0], &version.to_bytes(), &checksum.to_bytes(), &contents].concat()
+	}
+
+	/// Try to load a category from a file with the given contents.
+	fn load_file_contents(path:&str, bytes:&[u8]) -> Result<(), StorageError> {
+		std::fs::write(path, bytes).unwrap();
+		StorageManager::hard_reset();
+		StorageManager::get_mut().add_category_from_file(path)
+	}
+
+
+
+	#[test]
+	pub fn test_error_wrong_size() {
+		assert!(matches!(u32::from_bytes(&[1, 2]), Err(StorageError::WrongSize { expected: 4, found: 2, .. })));
+		assert!(matches!(u8::from_bytes(&[]), Err(StorageError::WrongSize { expected: 1, found: 0, .. })));
+		assert!(matches!(String::from_bytes(&[9, 0, 0, 0, b'a']), Err(StorageError::WrongSize { expected: 9, found: 1, .. })));
+		assert!(matches!(Vec::<u16>::from_bytes(&[5, 0, 0, 0, 1]), Err(StorageError::WrongSize { .. })));
+		assert!(matches!(Option::<u64>::from_bytes(&[1, 0]), Err(StorageError::WrongSize { .. })));
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_error_not_found() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
+
+		// Missing variables and categories are recognized as such.
+		let missing_variable:StorageError = storage_manager.get_value::<u8>(CAT_NAME, VAR_NAME).unwrap_err();
+		assert!(matches!(&missing_variable, StorageError::NotFound { category: Some(category), variable } if category == CAT_NAME && variable == VAR_NAME));
+		assert!(missing_variable.is_not_found());
+		assert!(matches!(storage_manager.find_value::<u8>(VAR_NAME), Err(StorageError::NotFound { category: None, .. })));
+		assert!(matches!(storage_manager.get_value::<u8>("other", VAR_NAME), Err(StorageError::UnknownCategory(category)) if category == "other"));
+		assert!(matches!(storage_manager.set_value::<u8>("other", VAR_NAME, &1), Err(StorageError::UnknownCategory(_))));
+		assert!(matches!(storage_manager.remove_category("other"), Err(StorageError::UnknownCategory(_))));
+
+		// Existing data of the wrong size is not missing.
+		storage_manager.set_value::<u8>(CAT_NAME, VAR_NAME, &1).unwrap();
+		let wrong_size:StorageError = storage_manager.get_value::<u32>(CAT_NAME, VAR_NAME).unwrap_err();
+		assert!(matches!(wrong_size, StorageError::WrongSize { expected: 4, found: 1, .. }));
+		assert!(!wrong_size.is_not_found());
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_error_truncated_file() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+
+		// Store some vars.
+		{
+			let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+			storage_manager.set_categories(vec![(CAT_NAME, &path)]).unwrap();
+			storage_manager.set_value::<String>(CAT_NAME, VAR_NAME, &"value".to_string()).unwrap();
+		}
+		let bytes:Vec<u8> = std::fs::read(&path).unwrap();
+
+		// Every truncation of the file fails to load without panicking.
+		for length in 0..bytes.len() {
+			match load_file_contents(&path, &bytes[..length]) {
+				Err(StorageError::Corrupt { offset, .. }) => assert!(offset <= length),
+				result => panic!("Loading file truncated to {length} bytes resulted in {result:?}.")
+			}
+		}
+		load_file_contents(&path, &bytes).unwrap();
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_error_corrupt_offset() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+
+		// Files that are not storage files are corrupt from the start.
+		assert!(matches!(load_file_contents(&path, b"not a storage file"), Err(StorageError::Corrupt { offset: 0, .. })));
+
+		// A variable name longer than the file points at the name.
+		let bytes:Vec<u8> = file_bytes(1, &[&100u32.to_bytes()[..], b"short"].concat());
+		let name_offset:usize = bytes.len() - b"short".len();
+		assert!(matches!(load_file_contents(&path, &bytes), Err(StorageError::Corrupt { offset, .. }) if offset == name_offset));
+
+		// Text that is not valid JSON points at the problem in the text.
+		let text:&str = "{\n  \"a\": ,\n}";
+		match TextFormat::Json.parse(text) {
+			Err(StorageError::Corrupt { offset, .. }) => assert_eq!(&text[offset..offset + 1], ","),
+			result => panic!("Parsing invalid JSON resulted in {result:?}.")
+		}
+		let text:&str = "a = 1\nb = ]\n";
+		match TextFormat::Toml.parse(text) {
+			Err(StorageError::Corrupt { offset, .. }) => assert_eq!(&text[offset..offset + 1], "]"),
+			result => panic!("Parsing invalid TOML resulted in {result:?}.")
+		}
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_error_version_mismatch() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+
+		// A file written by a newer version of the storage.
+		assert!(matches!(load_file_contents(&path, &file_bytes(99, &[])), Err(StorageError::VersionMismatch { found: 99, expected: 1, .. })));
+
+		// Values stored with a newer schema version, both as bytes and as text.
+		test_storage_path.remove();
+		StorageManager::hard_reset();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.set_categories(vec![(CAT_NAME, &path)]).unwrap();
+		let text:String = format!("{{ \"{CAT_NAME}\": {{ \"bytes\": {{ \"schema_version\": 7, \"bytes\": \"01\" }}, \"text\": {{ \"schema_version\": 7, \"value\": 1 }} }} }}");
+		storage_manager.import_categories_from_text(&text, TextFormat::Json).unwrap();
+		assert!(matches!(storage_manager.get_value::<u8>(CAT_NAME, "bytes"), Err(StorageError::VersionMismatch { found: 7, expected: 0, .. })));
+		assert!(matches!(storage_manager.get_value::<u8>(CAT_NAME, "text"), Err(StorageError::VersionMismatch { found: 7, expected: 0, .. })));
+	}
+}
\ No newline at end of file
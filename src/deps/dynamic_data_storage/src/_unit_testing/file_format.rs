@@ -0,0 +1,295 @@
+/* As DataStorage is a SingleTon, make sure all tests run synchronically using #[serial] and hard reset the StorageManager before each test to make sure no data leaks into other tests. */
+
+#[cfg(test)]
+mod test {
+	use crate::{ GeneralDataType, StorageError, StorageManager, _unit_testing::TestStoragePath };
+	use serial_test::serial;
+
+	static CAT_NAME:&str = "main";
+	static VAR_NAME:&str = "test_var";
+
+
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_large_value() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+		let storage_value:Vec<u8> = (0..200_000).map(|index| (index % 251) as u8).collect::<Vec<u8>>();
+
+		// Store var.
+		{
+			let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+			storage_manager.set_categories(vec![(CAT_NAME, &path)]).unwrap();
+			storage_manager.set_value::<Vec<u8>>(CAT_NAME, VAR_NAME, &storage_value).unwrap();
+			storage_manager.set_value::<u8>(CAT_NAME, "after_large_value", &7).unwrap();
+		}
+
+		// Load var.
+		StorageManager::hard_reset();
+		{
+			let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+			storage_manager.add_category_from_file(&path).unwrap();
+			assert_eq!(storage_manager.get_value::<Vec<u8>>(CAT_NAME, VAR_NAME).unwrap(), storage_value);
+			assert_eq!(storage_manager.get_value::<u8>(CAT_NAME, "after_large_value").unwrap(), 7);
+		}
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_read_legacy() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+
+		// Write a file in the layout used before the format was versioned.
+		let mut legacy_bytes:Vec<u8> = format!("<<DynamicDataStorageFile>>{CAT_NAME}:\0").into_bytes();
+		for (name, value) in [(VAR_NAME, 192u16.to_bytes()), ("other_var", b"text\0".to_vec())] {
+			let name:Vec<u8> = [name.as_bytes(), &[0]].concat();
+			legacy_bytes.extend_from_slice(&(name.len() as u16).to_bytes());
+			legacy_bytes.extend_from_slice(&name);
+			legacy_bytes.extend_from_slice(&(value.len() as u16).to_bytes());
+			legacy_bytes.extend_from_slice(&value);
+		}
+		std::fs::write(&path, legacy_bytes).unwrap();
+
+		// Load and validate.
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.add_category_from_file(&path).unwrap();
+		assert_eq!(storage_manager.get_value::<u16>(CAT_NAME, VAR_NAME).unwrap(), 192);
+		assert_eq!(storage_manager.get_value::<String>(CAT_NAME, "other_var").unwrap(), "text");
+	}
+
+	#[test]
+	#[serial]
+	#[should_panic]
+	pub fn test_file_format_truncated_file() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+
+		// Store var, then cut off the end of the file.
+		StorageManager::get_mut().set_categories(vec![(CAT_NAME, &path)]).unwrap();
+		StorageManager::get_mut().set_value::<u64>(CAT_NAME, VAR_NAME, &1).unwrap();
+		let bytes:Vec<u8> = std::fs::read(&path).unwrap();
+		std::fs::write(&path, &bytes[..bytes.len() - 3]).unwrap();
+
+		// Load var.
+		StorageManager::hard_reset();
+		StorageManager::get_mut().add_category_from_file(&path).unwrap();
+	}
+
+
+
+	/* FIXTURE TESTS */
+
+	static FIXTURE_DIR:&str = "src/_unit_testing/fixtures";
+	static FIXTURE_CAT_NAME:&str = "fixture";
+
+	/// Validate that a fixture holds the values every fixture was created with.
+	fn validate_fixture(file_name:&str) {
+		StorageManager::hard_reset();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.add_category_from_file(&format!("{FIXTURE_DIR}/{file_name}")).unwrap();
+		assert_eq!(storage_manager.get_value::<u16>(FIXTURE_CAT_NAME, "number").unwrap(), 192);
+		assert_eq!(storage_manager.get_value::<String>(FIXTURE_CAT_NAME, "text").unwrap(), "text");
+		assert_eq!(storage_manager.get_value::<Vec<u8>>(FIXTURE_CAT_NAME, "list").unwrap(), vec![1, 2, 3]);
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_fixture_legacy() {
+		validate_fixture("legacy.ddstrg");
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_fixture_v1() {
+		validate_fixture("format_v1.ddstrg");
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_fixture_v1_legacy_values() {
+		validate_fixture("format_v1_legacy_values.ddstrg");
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_matches_current_fixture() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+
+		// Store the fixture values.
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.set_categories(vec![(FIXTURE_CAT_NAME, &path)]).unwrap();
+		storage_manager.set_value::<u16>(FIXTURE_CAT_NAME, "number", &192).unwrap();
+		storage_manager.set_value::<String>(FIXTURE_CAT_NAME, "text", &"text".to_string()).unwrap();
+		storage_manager.set_value::<Vec<u8>>(FIXTURE_CAT_NAME, "list", &vec![1, 2, 3]).unwrap();
+
+		// A change in the written layout requires a new fixture, keep the previous one to validate it can still be read.
+		assert_eq!(std::fs::read(&path).unwrap(), std::fs::read(format!("{FIXTURE_DIR}/format_v1.ddstrg")).unwrap());
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_upgrade_on_store() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+
+		// Copy the legacy fixture and store it again.
+		std::fs::copy(format!("{FIXTURE_DIR}/legacy.ddstrg"), &path).unwrap();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.add_category_from_file(&path).unwrap();
+		storage_manager.find_category(FIXTURE_CAT_NAME).unwrap().store_to_file(true).unwrap();
+
+		// The file is now written in the current layout.
+		assert_eq!(std::fs::read(&path).unwrap(), std::fs::read(format!("{FIXTURE_DIR}/format_v1_legacy_values.ddstrg")).unwrap());
+	}
+
+
+
+	/* BACKUP TESTS */
+
+	/// Store two values for the same variable one after another.
+	fn store_twice(path:&str) {
+		StorageManager::hard_reset();
+		StorageManager::get_mut().set_categories(vec![(CAT_NAME, path)]).unwrap();
+		StorageManager::get_mut().set_value::<u64>(CAT_NAME, VAR_NAME, &1).unwrap();
+		StorageManager::get_mut().set_value::<u64>(CAT_NAME, VAR_NAME, &2).unwrap();
+		StorageManager::hard_reset();
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_keeps_backup() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		store_twice(&test_storage_path.path());
+
+		// The backup holds the state before the last write.
+		StorageManager::get_mut().add_category_from_file(&test_storage_path.path()).unwrap();
+		assert_eq!(StorageManager::get().get_value::<u64>(CAT_NAME, VAR_NAME).unwrap(), 2);
+		std::fs::rename(test_storage_path.backup_path(), test_storage_path.path()).unwrap();
+		StorageManager::hard_reset();
+		StorageManager::get_mut().add_category_from_file(&test_storage_path.path()).unwrap();
+		assert_eq!(StorageManager::get().get_value::<u64>(CAT_NAME, VAR_NAME).unwrap(), 1);
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_restore_truncated_from_backup() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+		store_twice(&path);
+
+		// Cut off the end of the file.
+		let bytes:Vec<u8> = std::fs::read(&path).unwrap();
+		std::fs::write(&path, &bytes[..bytes.len() - 3]).unwrap();
+
+		// Load the backup instead and restore the file. The truncated file is kept aside.
+		StorageManager::get_mut().add_category_from_file(&path).unwrap();
+		assert_eq!(StorageManager::get().get_value::<u64>(CAT_NAME, VAR_NAME).unwrap(), 1);
+		assert_eq!(std::fs::read(&path).unwrap(), std::fs::read(test_storage_path.backup_path()).unwrap());
+		assert_eq!(std::fs::read(test_storage_path.corrupt_path()).unwrap(), &bytes[..bytes.len() - 3]);
+		assert_eq!(StorageManager::get_mut().take_restored_categories().len(), 1);
+		assert!(StorageManager::get_mut().take_restored_categories().is_empty());
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_newer_version_not_restored() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+		store_twice(&path);
+
+		// Mark the file as written by a newer version of the storage.
+		let mut bytes:Vec<u8> = std::fs::read(&path).unwrap();
+		let version_offset:usize = "<<DynamicDataStorageFile>>".len() + 1;
+		bytes[version_offset..version_offset + 2].copy_from_slice(&99u16.to_bytes());
+		std::fs::write(&path, &bytes).unwrap();
+
+		// The file is neither replaced by its backup nor by an empty category.
+		assert!(matches!(StorageManager::get_mut().add_category_from_file(&path), Err(StorageError::VersionMismatch { found: 99, .. })));
+		StorageManager::hard_reset();
+		assert!(StorageManager::get_mut().set_categories(vec![(CAT_NAME, &path)]).is_err());
+		assert!(StorageManager::get().find_category(CAT_NAME).is_none());
+		assert_eq!(std::fs::read(&path).unwrap(), bytes);
+		assert!(!std::path::Path::new(&test_storage_path.corrupt_path()).exists());
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_restore_corrupt_from_backup() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+		store_twice(&path);
+
+		// Change the value without updating the checksum.
+		let mut bytes:Vec<u8> = std::fs::read(&path).unwrap();
+		let last_index:usize = bytes.len() - 1;
+		bytes[last_index] ^= 0xFF;
+		std::fs::write(&path, &bytes).unwrap();
+
+		// Load the backup instead.
+		StorageManager::get_mut().set_categories(vec![(CAT_NAME, &path)]).unwrap();
+		assert_eq!(StorageManager::get().get_value::<u64>(CAT_NAME, VAR_NAME).unwrap(), 1);
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_restore_missing_from_backup() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+		store_twice(&path);
+
+		// Remove the file as if the application stopped while replacing it.
+		std::fs::remove_file(&path).unwrap();
+
+		// Load the backup instead.
+		StorageManager::get_mut().set_categories(vec![(CAT_NAME, &path)]).unwrap();
+		assert_eq!(StorageManager::get().get_value::<u64>(CAT_NAME, VAR_NAME).unwrap(), 1);
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_corrupt_without_backup() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+
+		// Store var, then change its value without updating the checksum.
+		StorageManager::get_mut().set_categories(vec![(CAT_NAME, &path)]).unwrap();
+		StorageManager::get_mut().set_value::<u64>(CAT_NAME, VAR_NAME, &1).unwrap();
+		let mut bytes:Vec<u8> = std::fs::read(&path).unwrap();
+		let last_index:usize = bytes.len() - 1;
+		bytes[last_index] ^= 0xFF;
+		std::fs::write(&path, &bytes).unwrap();
+
+		// Loading fails instead of returning wrong data or an empty category that would overwrite the file.
+		StorageManager::hard_reset();
+		assert!(StorageManager::get_mut().add_category_from_file(&path).is_err());
+		assert!(StorageManager::get_mut().set_categories(vec![(CAT_NAME, &path)]).is_err());
+		assert_eq!(std::fs::read(&path).unwrap(), bytes);
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_file_format_without_sync() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let path:String = test_storage_path.path();
+
+		// Store var without flushing to disk.
+		StorageManager::get_mut().set_sync_writes(false);
+		StorageManager::get_mut().set_categories(vec![(CAT_NAME, &path)]).unwrap();
+		StorageManager::get_mut().set_value::<u64>(CAT_NAME, VAR_NAME, &1).unwrap();
+
+		// Load var.
+		StorageManager::hard_reset();
+		StorageManager::get_mut().add_category_from_file(&path).unwrap();
+		assert_eq!(StorageManager::get().get_value::<u64>(CAT_NAME, VAR_NAME).unwrap(), 1);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,147 @@
+/* As DataStorage and MigrationRegistry are SingleTons, make sure all tests run synchronically using #[serial] and hard reset both before each test to make sure no data leaks into other tests. */
+
+#[cfg(test)]
+mod test {
+	use crate::{ GeneralDataType, MigrationRegistry, StorageError, StorageManager, _unit_testing::TestStoragePath };
+	use serial_test::serial;
+	use std::collections::HashMap;
+
+	static CAT_NAME:&str = "main";
+	static VAR_NAME:&str = "test_var";
+
+
+
+	/* TEST TYPES */
+
+	// A point that used to only store its x coordinate.
+	#[derive(PartialEq, Debug)]
+	struct PointV0 {
+		x:f32
+	}
+	impl GeneralDataType for PointV0 {
+		fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(PointV0 { x: f32::from_bytes(&bytes[0..4])? }) }
+		fn to_bytes(&self) -> Vec<u8> { self.x.to_bytes() }
+		fn byte_size() -> usize { 4 }
+	}
+
+	// The current version of the point, which also stores its y coordinate.
+	#[derive(PartialEq, Debug)]
+	struct Point {
+		x:f32,
+		y:f32
+	}
+	impl GeneralDataType for Point {
+		fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(Point { x: f32::from_bytes(&bytes[0..4])?, y: f32::from_bytes(&bytes[4..8])? }) }
+		fn to_bytes(&self) -> Vec<u8> { [self.x.to_bytes(), self.y.to_bytes()].concat() }
+		fn byte_size() -> usize { 8 }
+		fn schema_version() -> u16 { 1 }
+	}
+
+	/// Upgrade a point without y coordinate by placing it on the x axis.
+	fn migrate_point_v0(bytes:&[u8]) -> Result<Vec<u8>, StorageError> {
+		Ok([bytes.to_vec(), 0.0f32.to_bytes()].concat())
+	}
+
+	/// Parse a point without y coordinate directly by placing it on the x axis.
+	fn parse_point_v0(bytes:&mut Vec<u8>) -> Result<Point, StorageError> {
+		Ok(Point { x: PointV0::from_bytes_consume(bytes)?.x, y: 0.0 })
+	}
+
+	/// Store a value of a type in a new storage file and reload the storage from disk.
+	fn store_and_reload<T:GeneralDataType>(path:&str, value:&T) {
+		StorageManager::hard_reset();
+		StorageManager::get_mut().set_categories(vec![(CAT_NAME, path)]).unwrap();
+		StorageManager::get_mut().set_value::<T>(CAT_NAME, VAR_NAME, value).unwrap();
+		StorageManager::hard_reset();
+		StorageManager::get_mut().add_category_from_file(path).unwrap();
+	}
+
+
+
+	/* TESTS */
+
+	#[test]
+	#[serial]
+	pub fn test_migration_upgrades_old_value() {
+		MigrationRegistry::hard_reset();
+		MigrationRegistry::get_mut().register::<Point>(0, migrate_point_v0);
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+
+		// Store an old point and read it as a new point.
+		store_and_reload(&test_storage_path.path(), &PointV0 { x: 4.0 });
+		assert_eq!(StorageManager::get().get_value::<Point>(CAT_NAME, VAR_NAME).unwrap(), Point { x: 4.0, y: 0.0 });
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_migration_parses_old_value() {
+		MigrationRegistry::hard_reset();
+		MigrationRegistry::get_mut().register_parser::<Point>(0, parse_point_v0);
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+
+		// Store an old point and read it as a new point.
+		store_and_reload(&test_storage_path.path(), &PointV0 { x: 4.0 });
+		assert_eq!(StorageManager::get().get_value::<Point>(CAT_NAME, VAR_NAME).unwrap(), Point { x: 4.0, y: 0.0 });
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_migration_not_needed_for_current_value() {
+		MigrationRegistry::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+
+		// Without registered migrations, values of the current schema version still load.
+		store_and_reload(&test_storage_path.path(), &Point { x: 4.0, y: 2.0 });
+		assert_eq!(StorageManager::get().get_value::<Point>(CAT_NAME, VAR_NAME).unwrap(), Point { x: 4.0, y: 2.0 });
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_migration_missing() {
+		MigrationRegistry::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+
+		store_and_reload(&test_storage_path.path(), &PointV0 { x: 4.0 });
+		assert!(StorageManager::get().get_value::<Point>(CAT_NAME, VAR_NAME).is_err());
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_migration_newer_schema() {
+		MigrationRegistry::hard_reset();
+		MigrationRegistry::get_mut().register::<Point>(0, migrate_point_v0);
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+
+		// A value stored by a newer version of the type cannot be downgraded.
+		store_and_reload(&test_storage_path.path(), &Point { x: 4.0, y: 2.0 });
+		assert!(StorageManager::get().get_value::<PointV0>(CAT_NAME, VAR_NAME).is_err());
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_migration_upgrades_items() {
+		MigrationRegistry::hard_reset();
+		MigrationRegistry::get_mut().register_parser::<Point>(0, parse_point_v0);
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+
+		// Items of containers are upgraded through the parsers of their own type.
+		store_and_reload(&test_storage_path.path(), &vec![PointV0 { x: 1.0 }, PointV0 { x: 2.0 }]);
+		assert_eq!(StorageManager::get().get_value::<Vec<Point>>(CAT_NAME, VAR_NAME).unwrap(), vec![Point { x: 1.0, y: 0.0 }, Point { x: 2.0, y: 0.0 }]);
+		store_and_reload(&test_storage_path.path(), &(Some(PointV0 { x: 3.0 }), String::from("a"), [PointV0 { x: 4.0 }]));
+		assert_eq!(StorageManager::get().get_value::<(Option<Point>, String, [Point; 1])>(CAT_NAME, VAR_NAME).unwrap(), (Some(Point { x: 3.0, y: 0.0 }), String::from("a"), [Point { x: 4.0, y: 0.0 }]));
+		store_and_reload(&test_storage_path.path(), &HashMap::from([(1u8, PointV0 { x: 5.0 })]));
+		assert_eq!(StorageManager::get().get_value::<HashMap<u8, Point>>(CAT_NAME, VAR_NAME).unwrap(), HashMap::from([(1u8, Point { x: 5.0, y: 0.0 })]));
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_migration_items_require_parser() {
+		MigrationRegistry::hard_reset();
+		MigrationRegistry::get_mut().register::<Point>(0, migrate_point_v0);
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+
+		// A migration cannot tell where an item ends, so it is not used for items.
+		store_and_reload(&test_storage_path.path(), &vec![PointV0 { x: 1.0 }]);
+		assert!(StorageManager::get().get_value::<Vec<Point>>(CAT_NAME, VAR_NAME).is_err());
+	}
+}
\ No newline at end of file
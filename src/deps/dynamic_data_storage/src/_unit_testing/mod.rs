@@ -4,4 +4,12 @@ pub(super) use support::TestStoragePath;
 // auto-publib no-use
 
 mod data_storage;
-mod support;
\ No newline at end of file
+mod data_types;
+mod derive;
+mod errors;
+mod file_format;
+mod migrations;
+mod subscriptions;
+mod support;
+mod text_export;
+mod transactions;
\ No newline at end of file
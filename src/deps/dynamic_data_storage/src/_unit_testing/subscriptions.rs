@@ -0,0 +1,125 @@
+/* As DataStorage is a SingleTon, make sure all tests run synchronically using #[serial] and hard reset the StorageManager before each test to make sure no data leaks into other tests. */
+
+#[cfg(test)]
+mod test {
+	use crate::{ StorageChange, StorageManager, SubscriptionId, TextFormat, _unit_testing::TestStoragePath };
+	use serial_test::serial;
+	use std::sync::{ Arc, Mutex };
+
+	static CAT_NAME:&str = "main";
+	static VAR_NAME:&str = "test_var";
+
+
+
+	/// Create a storage with a single category and subscribe to it. Returns the list all received changes are added to.
+	fn subscribed_storage(path:&str, variable_name:Option<&str>) -> (Arc<Mutex<Vec<StorageChange>>>, SubscriptionId) {
+		StorageManager::hard_reset();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.set_categories(vec![(CAT_NAME, path)]).unwrap();
+		let changes:Arc<Mutex<Vec<StorageChange>>> = Arc::new(Mutex::new(Vec::new()));
+		let handler_changes:Arc<Mutex<Vec<StorageChange>>> = changes.clone();
+		let id:SubscriptionId = storage_manager.subscribe(CAT_NAME, variable_name, move |change| handler_changes.lock().unwrap().push(change.clone()));
+		(changes, id)
+	}
+
+	/// Get the names of all variables in a list of changes.
+	fn changed_variables(changes:&Arc<Mutex<Vec<StorageChange>>>) -> Vec<String> {
+		changes.lock().unwrap().iter().map(|change| change.variable().to_string()).collect::<Vec<String>>()
+	}
+
+
+
+	#[test]
+	#[serial]
+	pub fn test_subscription_variable() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let (changes, _) = subscribed_storage(&test_storage_path.path(), Some(VAR_NAME));
+
+		// Only changes to the subscribed variable are received.
+		StorageManager::get_mut().set_value::<u8>(CAT_NAME, "other_var", &1).unwrap();
+		StorageManager::get_mut().set_value::<u8>(CAT_NAME, VAR_NAME, &1).unwrap();
+		assert_eq!(*changes.lock().unwrap(), vec![StorageChange::new(CAT_NAME, VAR_NAME)]);
+
+		// Setting the same value again is not a change.
+		StorageManager::get_mut().set_value::<u8>(CAT_NAME, VAR_NAME, &1).unwrap();
+		StorageManager::get_mut().set_value::<u8>(CAT_NAME, VAR_NAME, &2).unwrap();
+		assert_eq!(changed_variables(&changes), vec![VAR_NAME, VAR_NAME]);
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_subscription_category() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let (changes, _) = subscribed_storage(&test_storage_path.path(), None);
+
+		// Changes are received once the manager is released.
+		{
+			let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+			storage_manager.set_value::<u8>(CAT_NAME, "a", &1).unwrap();
+			storage_manager.set_value::<u8>(CAT_NAME, "b", &1).unwrap();
+			assert!(changes.lock().unwrap().is_empty());
+		}
+		assert_eq!(changed_variables(&changes), vec!["a", "b"]);
+
+		// Importing notifies of all variables that were added, changed or removed.
+		changes.lock().unwrap().clear();
+		let text:String = format!("{{ \"{CAT_NAME}\": {{ \"a\": {{ \"schema_version\": 0, \"bytes\": \"01\" }}, \"c\": {{ \"schema_version\": 0, \"value\": 3 }} }} }}");
+		StorageManager::get_mut().import_categories_from_text(&text, TextFormat::Json).unwrap();
+		assert_eq!(changed_variables(&changes), vec!["c", "b"]);
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_subscription_handler_uses_storage() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		StorageManager::hard_reset();
+		StorageManager::get_mut().set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
+
+		// The handler can read and change the storage itself.
+		StorageManager::get_mut().subscribe(CAT_NAME, Some(VAR_NAME), |change| {
+			let value:u8 = StorageManager::get().get_value::<u8>(change.category(), change.variable()).unwrap();
+			StorageManager::get_mut().set_value::<u8>(CAT_NAME, "copy", &value).unwrap();
+		});
+		StorageManager::get_mut().set_value::<u8>(CAT_NAME, VAR_NAME, &7).unwrap();
+		assert_eq!(StorageManager::get().get_value::<u8>(CAT_NAME, "copy").unwrap(), 7);
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_subscription_unsubscribe() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let (changes, id) = subscribed_storage(&test_storage_path.path(), Some(VAR_NAME));
+
+		assert!(StorageManager::get_mut().unsubscribe(id));
+		assert!(!StorageManager::get_mut().unsubscribe(id));
+		StorageManager::get_mut().set_value::<u8>(CAT_NAME, VAR_NAME, &1).unwrap();
+		assert!(changes.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_subscription_threads() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let (changes, _) = subscribed_storage(&test_storage_path.path(), None);
+		StorageManager::get_mut().set_autosave_delay(Some(std::time::Duration::from_secs(60)));
+
+		// Set values from multiple threads at once.
+		let threads:Vec<std::thread::JoinHandle<()>> = (0..8u32).map(|thread_index| std::thread::spawn(move || {
+			for value_index in 0..50u32 {
+				StorageManager::get_mut().set_value::<u32>(CAT_NAME, &format!("var_{thread_index}_{value_index}"), &(thread_index * value_index)).unwrap();
+			}
+		})).collect::<Vec<std::thread::JoinHandle<()>>>();
+		for thread in threads {
+			thread.join().unwrap();
+		}
+
+		// Validate all values were stored and notified.
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		for thread_index in 0..8u32 {
+			for value_index in 0..50u32 {
+				assert_eq!(storage_manager.get_value::<u32>(CAT_NAME, &format!("var_{thread_index}_{value_index}")).unwrap(), thread_index * value_index);
+			}
+		}
+		assert_eq!(changes.lock().unwrap().len(), 400);
+	}
+}
\ No newline at end of file
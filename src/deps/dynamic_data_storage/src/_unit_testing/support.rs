@@ -26,10 +26,12 @@ impl TestStoragePath {
 		std::path::Path::new(&self.path).exists()
 	}
 
-	/// Remove the physical file if it exists.
+	/// Remove the physical file and its backup and temporary files if they exist.
 	pub fn remove(&self) {
-		if self.exists() {
-			std::fs::remove_file(self.path()).expect("Could not remove test storage file");
+		for path in [self.path(), self.backup_path(), format!("{}.tmp", self.path), self.corrupt_path(), self.text_path("json"), self.text_path("toml")] {
+			if std::path::Path::new(&path).exists() {
+				std::fs::remove_file(path).expect("Could not remove test storage file");
+			}
 		}
 	}
 
@@ -37,6 +39,21 @@ impl TestStoragePath {
 	pub fn path(&self) -> String {
 		String::from(&self.path)
 	}
+
+	/// Get the path of the backup of the storage.
+	pub fn backup_path(&self) -> String {
+		format!("{}.bak", self.path)
+	}
+
+	/// Get the path a damaged storage is moved to when it is restored from its backup.
+	pub fn corrupt_path(&self) -> String {
+		format!("{}.corrupt", self.path)
+	}
+
+	/// Get the path of a text export of the storage with a specific extension.
+	pub fn text_path(&self, extension:&str) -> String {
+		format!("{}.{extension}", self.path)
+	}
 }
 impl Drop for TestStoragePath {
 	fn drop(&mut self) {
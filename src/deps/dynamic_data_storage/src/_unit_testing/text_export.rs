@@ -0,0 +1,152 @@
+/* As DataStorage is a SingleTon, make sure all tests run synchronically using #[serial] and hard reset the StorageManager before each test to make sure no data leaks into other tests. */
+
+#[cfg(test)]
+mod test {
+	use crate::{ GeneralDataType, StorageError, StorageManager, TextFormat, TextValue, _unit_testing::TestStoragePath };
+	use serial_test::serial;
+
+	static CAT_NAME:&str = "main";
+
+	// A type that gained an optional field in its latest schema version.
+	#[derive(GeneralDataType, PartialEq, Debug)]
+	#[general_data_type(schema_version = 2)]
+	struct Labeled {
+		name:String,
+		label:Option<String>
+	}
+
+
+
+	/// Create a storage with a category holding values of a few types, all set with their type.
+	fn store_values(path:&str) {
+		StorageManager::hard_reset();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.set_categories(vec![(CAT_NAME, path)]).unwrap();
+		storage_manager.set_value::<u16>(CAT_NAME, "number", &192).unwrap();
+		storage_manager.set_value::<String>(CAT_NAME, "text", &"Müller".to_string()).unwrap();
+		storage_manager.set_value::<Vec<Option<f32>>>(CAT_NAME, "list", &vec![Some(0.1), None]).unwrap();
+	}
+
+	/// Validate the storage holds the values created by `store_values`.
+	fn validate_values() {
+		let storage_manager:&StorageManager = &StorageManager::get();
+		assert_eq!(storage_manager.get_value::<u16>(CAT_NAME, "number").unwrap(), 192);
+		assert_eq!(storage_manager.get_value::<String>(CAT_NAME, "text").unwrap(), "Müller");
+		assert_eq!(storage_manager.get_value::<Vec<Option<f32>>>(CAT_NAME, "list").unwrap(), vec![Some(0.1), None]);
+	}
+
+
+
+	#[test]
+	#[serial]
+	pub fn test_text_export_readable() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		store_values(&test_storage_path.path());
+
+		// Values set with their type are exported as readable values.
+		let json:String = StorageManager::get().categories_to_text(&[CAT_NAME], TextFormat::Json).unwrap();
+		assert!(json.contains("\"text\": {\n      \"schema_version\": 1,\n      \"value\": \"Müller\"\n    }"));
+		let toml:String = StorageManager::get().categories_to_text(&[CAT_NAME], TextFormat::Toml).unwrap();
+		assert!(toml.contains("[main.number]\nschema_version = 0\nvalue = 192\n"));
+		assert!(toml.contains("value = [0.1, {}]"));
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_text_export_round_trip() {
+		for extension in ["json", "toml"] {
+			let test_storage_path:TestStoragePath = TestStoragePath::get();
+			let text_path:String = test_storage_path.text_path(extension);
+			store_values(&test_storage_path.path());
+			StorageManager::get().export_categories(&[CAT_NAME], &text_path).unwrap();
+
+			// Import into an empty storage.
+			let import_storage_path:TestStoragePath = TestStoragePath::get();
+			StorageManager::hard_reset();
+			StorageManager::get_mut().set_categories(vec![(CAT_NAME, &import_storage_path.path())]).unwrap();
+			assert_eq!(StorageManager::get_mut().import_categories(&text_path).unwrap(), vec![CAT_NAME.to_string()]);
+			validate_values();
+
+			// Imported values are kept when stored to file before they are requested with their type.
+			StorageManager::hard_reset();
+			StorageManager::get_mut().add_category_from_file(&import_storage_path.path()).unwrap();
+			validate_values();
+		}
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_text_export_untyped_values() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let text_path:String = test_storage_path.text_path("json");
+		store_values(&test_storage_path.path());
+
+		// Values loaded from file are not known by type yet, so they are exported as bytes.
+		StorageManager::hard_reset();
+		StorageManager::get_mut().add_category_from_file(&test_storage_path.path()).unwrap();
+		StorageManager::get().export_categories(&[CAT_NAME], &text_path).unwrap();
+		assert!(std::fs::read_to_string(&text_path).unwrap().contains("\"bytes\": \"c000\""));
+
+		// Import them again.
+		StorageManager::get_mut().import_categories(&text_path).unwrap();
+		validate_values();
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_text_export_edited_value() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		store_values(&test_storage_path.path());
+
+		// Change a value in the text and import it.
+		let toml:String = StorageManager::get().categories_to_text(&[CAT_NAME], TextFormat::Toml).unwrap().replace("value = 192", "value = 7");
+		StorageManager::get_mut().import_categories_from_text(&toml, TextFormat::Toml).unwrap();
+		assert_eq!(StorageManager::get().get_value::<u16>(CAT_NAME, "number").unwrap(), 7);
+
+		// Values that do not fit their type cannot be read.
+		let toml:String = toml.replace("value = 7", "value = -7");
+		StorageManager::get_mut().import_categories_from_text(&toml, TextFormat::Toml).unwrap();
+		assert!(StorageManager::get().get_value::<u16>(CAT_NAME, "number").is_err());
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_text_export_unknown_category() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		store_values(&test_storage_path.path());
+		let json:String = StorageManager::get().categories_to_text(&[CAT_NAME], TextFormat::Json).unwrap();
+
+		// Nothing is imported when one of the categories does not exist.
+		let json:String = json.replacen('{', "{\n  \"other\": {},", 1).replace("192", "7");
+		assert!(StorageManager::get_mut().import_categories_from_text(&json, TextFormat::Json).is_err());
+		assert_eq!(StorageManager::get().get_value::<u16>(CAT_NAME, "number").unwrap(), 192);
+		assert!(StorageManager::get().categories_to_text(&["other"], TextFormat::Json).is_err());
+	}
+	#[test]
+	#[serial]
+	pub fn test_text_export_from_variable() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		store_values(&test_storage_path.path());
+
+		// A variable created outside of storage has the same representation as an exported one.
+		let json:String = StorageManager::get().categories_to_text(&[CAT_NAME], TextFormat::Json).unwrap();
+		assert_eq!(TextFormat::Json.parse(&json).unwrap().field(CAT_NAME).field("number"), &TextValue::from_variable::<u16>(&192));
+
+		// It can be imported.
+		let document:TextValue = TextValue::Map(vec![(CAT_NAME.to_string(), TextValue::Map(vec![("number".to_string(), TextValue::from_variable::<u16>(&7))]))]);
+		StorageManager::get_mut().import_categories_from_text(&TextFormat::Json.write(&document).unwrap(), TextFormat::Json).unwrap();
+		assert_eq!(StorageManager::get().get_value::<u16>(CAT_NAME, "number").unwrap(), 7);
+	}
+	#[test]
+	#[serial]
+	pub fn test_text_export_older_schema() {
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		store_values(&test_storage_path.path());
+
+		// Text of an older schema version is read when it fits the current type, fields added since are empty.
+		let json:String = format!("{{ \"{CAT_NAME}\": {{ \"fitting\": {{ \"schema_version\": 1, \"value\": {{ \"name\": \"leg\" }} }}, \"other\": {{ \"schema_version\": 1, \"value\": 5 }} }} }}");
+		StorageManager::get_mut().import_categories_from_text(&json, TextFormat::Json).unwrap();
+		assert_eq!(StorageManager::get().get_value::<Labeled>(CAT_NAME, "fitting").unwrap(), Labeled { name: "leg".to_string(), label: None });
+		assert!(matches!(StorageManager::get().get_value::<Labeled>(CAT_NAME, "other"), Err(StorageError::VersionMismatch { found: 1, expected: 2, .. })));
+	}
+}
\ No newline at end of file
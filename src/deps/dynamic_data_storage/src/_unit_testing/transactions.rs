@@ -0,0 +1,133 @@
+/* As DataStorage is a SingleTon, make sure all tests run synchronically using #[serial] and hard reset the StorageManager before each test to make sure no data leaks into other tests. */
+
+#[cfg(test)]
+mod test {
+	use crate::{ StorageManager, _unit_testing::TestStoragePath };
+	use serial_test::serial;
+	use std::time::Duration;
+
+	static CAT_NAME:&str = "main";
+	static VAR_NAME:&str = "test_var";
+
+
+
+	/// Read the value of the test variable from disk.
+	fn stored_value(path:&str) -> Option<u32> {
+		let mut storage_manager:StorageManager = StorageManager::new();
+		storage_manager.add_category_from_file(path).ok()?;
+		storage_manager.get_value::<u32>(CAT_NAME, VAR_NAME).ok()
+	}
+
+
+
+	#[test]
+	#[serial]
+	pub fn test_transaction_defers_writes() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
+
+		// Set values during a transaction.
+		storage_manager.begin_transaction();
+		for value in 0..100 {
+			storage_manager.set_value::<u32>(CAT_NAME, VAR_NAME, &value).unwrap();
+		}
+		assert!(!test_storage_path.exists());
+		assert!(storage_manager.is_dirty());
+		assert_eq!(storage_manager.get_value::<u32>(CAT_NAME, VAR_NAME).unwrap(), 99);
+
+		// Commit.
+		storage_manager.commit_transaction().unwrap();
+		assert!(!storage_manager.is_dirty());
+		assert_eq!(stored_value(&test_storage_path.path()), Some(99));
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_transaction_nested() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
+
+		// Only committing the outer transaction stores the changes.
+		storage_manager.begin_transaction();
+		storage_manager.begin_transaction();
+		storage_manager.set_value::<u32>(CAT_NAME, VAR_NAME, &1).unwrap();
+		storage_manager.commit_transaction().unwrap();
+		assert!(!test_storage_path.exists());
+		storage_manager.commit_transaction().unwrap();
+		assert_eq!(stored_value(&test_storage_path.path()), Some(1));
+
+		// Committing without transaction fails.
+		assert!(storage_manager.commit_transaction().is_err());
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_transaction_handler() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
+
+		// Run a transaction through a handler.
+		storage_manager.transaction(|storage_manager| {
+			storage_manager.set_value::<u32>(CAT_NAME, VAR_NAME, &1)?;
+			storage_manager.set_value::<u32>(CAT_NAME, VAR_NAME, &2)
+		}).unwrap();
+		assert!(!storage_manager.in_transaction());
+		assert_eq!(stored_value(&test_storage_path.path()), Some(2));
+
+		// A failing handler still ends the transaction, but undoes its changes.
+		assert!(storage_manager.transaction(|storage_manager| {
+			storage_manager.set_value::<u32>(CAT_NAME, VAR_NAME, &3)?;
+			storage_manager.set_value::<u32>("unknown_category", VAR_NAME, &3)
+		}).is_err());
+		assert!(!storage_manager.in_transaction());
+		assert_eq!(storage_manager.get_value::<u32>(CAT_NAME, VAR_NAME).unwrap(), 2);
+		assert!(!storage_manager.is_dirty());
+		assert_eq!(stored_value(&test_storage_path.path()), Some(2));
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_transaction_flush() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
+
+		// Autosave does not store before its delay passed.
+		storage_manager.set_autosave_delay(Some(Duration::from_secs(3600)));
+		storage_manager.set_value::<u32>(CAT_NAME, VAR_NAME, &1).unwrap();
+		assert!(!storage_manager.autosave_if_due().unwrap());
+		assert!(!test_storage_path.exists());
+
+		// Flushing stores directly.
+		storage_manager.flush().unwrap();
+		assert!(!storage_manager.is_dirty());
+		assert_eq!(stored_value(&test_storage_path.path()), Some(1));
+	}
+
+	#[test]
+	#[serial]
+	pub fn test_transaction_autosave() {
+		StorageManager::hard_reset();
+		let test_storage_path:TestStoragePath = TestStoragePath::get();
+		let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+		storage_manager.set_categories(vec![(CAT_NAME, &test_storage_path.path())]).unwrap();
+
+		// Set value with autosave.
+		storage_manager.set_autosave_delay(Some(Duration::from_millis(20)));
+		storage_manager.set_value::<u32>(CAT_NAME, VAR_NAME, &1).unwrap();
+		assert!(!test_storage_path.exists());
+
+		// Autosave after the delay.
+		std::thread::sleep(Duration::from_millis(30));
+		assert!(storage_manager.autosave_if_due().unwrap());
+		assert_eq!(stored_value(&test_storage_path.path()), Some(1));
+		assert!(!storage_manager.autosave_if_due().unwrap());
+	}
+}
\ No newline at end of file
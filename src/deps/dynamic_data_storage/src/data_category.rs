@@ -1,36 +1,44 @@
-use crate::{ GeneralDataType, STORAGE_FILE_EXTENSION };
-use std::error::Error;
+use crate::{ GeneralDataType, StorageError, TextFormat, TextValue, STORAGE_FILE_EXTENSION };
 use super::NamedData;
 
 static FILE_RECOGNITION_TAG:&str = "<<DynamicDataStorageFile>>";
+static FILE_FORMAT_VERSION:u16 = 1;
+static BACKUP_FILE_EXTENSION:&str = "bak";
+static CORRUPT_FILE_EXTENSION:&str = "corrupt";
+static TEMPORARY_FILE_EXTENSION:&str = "tmp";
+const VALUE_ENCODING_BYTES:u8 = 0;
+const VALUE_ENCODING_TEXT:u8 = 1;
 
 
+#[derive(Clone)]
 pub struct DataCategory {
 	name:String,
 	source:String,
-	data:Vec<NamedData>
+	data:Vec<NamedData>,
+	dirty:bool,
+	restored_from_backup:Option<String>
 }
 impl DataCategory {
 
-	/// Create a new instance.
-	pub fn new(name:&str, source:&str) -> DataCategory {
+	/// Create a new instance. Loads the data from the source or its backup if either exists. When stored data exists but cannot be read, the error is returned rather than an empty category that would overwrite the data once stored.
+	pub fn new(name:&str, source:&str) -> Result<DataCategory, StorageError> {
 		use std::path::Path;
 
 		let full_source:String = if source.contains(STORAGE_FILE_EXTENSION) { source.to_string() } else { format!("{source}.{STORAGE_FILE_EXTENSION}") };
 
-		// Try to load data from file if exist.
-		if Path::new(&full_source).exists() {
-			if let Ok(category) = Self::load_from_file(&full_source) {
-				return category;
-			}
+		// Load data from file or its backup if exist.
+		if Path::new(&full_source).exists() || Path::new(&Self::backup_path(&full_source)).exists() {
+			return Self::load_from_file(&full_source);
 		}
 
 		// Create new category.
-		DataCategory {
+		Ok(DataCategory {
 			name: name.to_string(),
 			source: full_source,
-			data: Vec::new()
-		}
+			data: Vec::new(),
+			dirty: false,
+			restored_from_backup: None
+		})
 	}
 
 
@@ -47,9 +55,11 @@ impl DataCategory {
 		&self.source
 	}
 
-	/// Update the source of the category. Does not read the data at the new path, but rather stores it there from now on.
+	/// Update the source of the category. Does not read the data at the new path, but rather stores it there from now on. A new path does not hold the data yet, so the category is marked as changed.
 	pub fn set_source(&mut self, source:&str) {
-		self.source = if source.contains(STORAGE_FILE_EXTENSION) { source.to_string() } else { format!("{source}.{STORAGE_FILE_EXTENSION}") };
+		let source:String = if source.contains(STORAGE_FILE_EXTENSION) { source.to_string() } else { format!("{source}.{STORAGE_FILE_EXTENSION}") };
+		self.dirty |= source != self.source;
+		self.source = source;
 	}
 
 	/// Return a reference to the data.
@@ -62,6 +72,16 @@ impl DataCategory {
 		&mut self.data
 	}
 
+	/// Check if the data in memory has changes that are not stored to file yet.
+	pub fn is_dirty(&self) -> bool {
+		self.dirty
+	}
+
+	/// Take the reason the source could not be read when the category was restored from its backup while loading. Returns None once taken.
+	pub fn take_restored_from_backup(&mut self) -> Option<String> {
+		self.restored_from_backup.take()
+	}
+
 
 	
 	/* VALUE METHODS */
@@ -77,125 +97,301 @@ impl DataCategory {
 	}
 
 	/// Get a specific variable.
-	pub fn get_value<T:GeneralDataType>(&self, name:&str) -> Result<T, Box<dyn Error>> {
+	pub fn get_value<T:GeneralDataType>(&self, name:&str) -> Result<T, StorageError> {
 		match self.find(name) {
 			Some(data) => data.get_value::<T>(),
-			None => Err(format!("Could not find variable '{}' in category '{}'.", name, self.name).into())
+			None => Err(StorageError::NotFound { category: Some(self.name.clone()), variable: name.to_string() })
 		}
 	}
 
-	/// Set a specific variable.
-	pub fn set_value<T:GeneralDataType>(&mut self, name:&str, value:&T) -> Result<(), Box<dyn Error>> {
-
-		// Write value to memory.
-		match self.find_mut(name) {
+	/// Set a specific variable in memory. Does not store it to file until flushed. Returns whether the variable changed.
+	pub fn set_value<T:GeneralDataType>(&mut self, name:&str, value:&T) -> bool {
+		let changed:bool = match self.find_mut(name) {
 			Some(data) => data.set_value::<T>(value),
-			None => self.data.push(NamedData::new::<T>(name, value))
-		}
+			None => {
+				self.data.push(NamedData::new::<T>(name, value));
+				true
+			}
+		};
+		self.dirty |= changed;
+		changed
+	}
+
+
 
-		// Write memory to file.
-		self.store_to_file()
+	/* TEXT METHODS */
+
+	/// Create a readable representation of all variables, used to export the category to a text format.
+	pub fn to_text_value(&self) -> TextValue {
+		TextValue::Map(self.data.iter().map(|variable| (variable.name().to_string(), variable.to_text_value())).collect::<Vec<(String, TextValue)>>())
+	}
+
+	/// Parse the variables from a readable representation created by `to_text_value`.
+	pub fn data_from_text_value(text:&TextValue) -> Result<Vec<NamedData>, StorageError> {
+		text.map()?.iter().map(|(name, variable)| NamedData::from_text_value(name, variable)).collect()
+	}
+
+	/// Replace all variables in memory. Does not store them to file until flushed. Returns the names of the variables that were added, changed or removed.
+	pub fn replace_data(&mut self, data:Vec<NamedData>) -> Vec<String> {
+		let mut changed_names:Vec<String> = data.iter().filter(|variable| !self.find(variable.name()).is_some_and(|current| current.has_same_data(variable))).map(|variable| variable.name().to_string()).collect::<Vec<String>>();
+		changed_names.extend(self.data.iter().filter(|current| !data.iter().any(|variable| variable.name() == current.name())).map(|current| current.name().to_string()));
+		self.data = data;
+		self.dirty = true;
+		changed_names
 	}
 
 
 
 	/* DISK STORAGE METHODS */
 
-	/// Write own memory to file.
-	pub fn store_to_file(&self) -> Result<(), Box<dyn Error>> {
-		use std::{ io::Write, path::Path, fs::{ File, create_dir } };
+	/// Write own memory to file if it has changes that are not stored yet.
+	pub fn flush(&mut self, sync_writes:bool) -> Result<(), StorageError> {
+		if self.dirty {
+			self.store_to_file(sync_writes)?;
+			self.dirty = false;
+		}
+		Ok(())
+	}
+
+	/// Write own memory to file. The data is written to a temporary file first, which then replaces the source file. The previous source file is kept as backup. When syncing writes, the temporary file is flushed to disk before it replaces the source file.
+	pub fn store_to_file(&self, sync_writes:bool) -> Result<(), StorageError> {
+		use std::{ io::Write, path::Path, fs::{ File, create_dir_all, rename } };
 		
 		// Turn data into writable bytes.
-		let mut output_bytes:Vec<u8> = format!("{}{}:", FILE_RECOGNITION_TAG, self.name).to_bytes();
+		let mut content_bytes:Vec<u8> = Self::terminated_bytes(&self.name);
 		for variable in &self.data {
-			let name:Vec<u8> = variable.name().to_string().to_bytes();
-			let value:Vec<u8> = variable.value::<Vec<u8>>().unwrap();
-			output_bytes.extend_from_slice(&(name.len() as u16).to_bytes());
-			output_bytes.extend_from_slice(&name);
-			output_bytes.extend_from_slice(&(value.len() as u16).to_bytes());
-			output_bytes.extend_from_slice(&value);
+			let name:Vec<u8> = Self::terminated_bytes(variable.name());
+			let (encoding, value) = match variable.text() {
+				Some(text) => (VALUE_ENCODING_TEXT, TextFormat::Json.write(text)?.into_bytes()),
+				None => (VALUE_ENCODING_BYTES, variable.bytes().to_vec())
+			};
+			content_bytes.extend_from_slice(&(name.len() as u32).to_bytes());
+			content_bytes.extend_from_slice(&name);
+			content_bytes.extend_from_slice(&variable.schema_version().to_bytes());
+			content_bytes.extend_from_slice(&variable.item_schema_versions().to_bytes());
+			content_bytes.push(encoding);
+			content_bytes.extend_from_slice(&(value.len() as u64).to_bytes());
+			content_bytes.extend_from_slice(&value);
 		}
+
+		// Add the header. The null terminator directly after the tag marks a versioned file, legacy files have their category name there.
+		let mut output_bytes:Vec<u8> = Self::terminated_bytes(FILE_RECOGNITION_TAG);
+		output_bytes.extend_from_slice(&FILE_FORMAT_VERSION.to_bytes());
+		output_bytes.extend_from_slice(&Self::checksum(&content_bytes).to_bytes());
+		output_bytes.extend_from_slice(&content_bytes);
 		
 		// Make sure parent dir exists.
-		let path_components:Vec<&str> = self.source.split('/').collect::<Vec<&str>>();
-		for length in 1..path_components.len() {
-			let dir:String = path_components[..length].join("/");
-			if !Path::new(&dir).exists() {
-				create_dir(dir)?;
-			}
+		if let Some(dir) = Path::new(&self.source).parent().filter(|dir| !dir.as_os_str().is_empty()) {
+			create_dir_all(dir)?;
 		}
 
-		// Write string to file.
-		let mut file:File = File::create(&self.source)?;
+		// Write bytes to a temporary file.
+		let temporary_path:String = format!("{}.{TEMPORARY_FILE_EXTENSION}", self.source);
+		let mut file:File = File::create(&temporary_path)?;
 		file.write_all(&output_bytes[..])?;
+		if sync_writes {
+			file.sync_all()?;
+		}
+		drop(file);
+
+		// Keep the current file as backup and move the new file in place.
+		if Path::new(&self.source).exists() {
+			rename(&self.source, Self::backup_path(&self.source))?;
+		}
+		rename(&temporary_path, &self.source)?;
 		
 		Ok(())
 	}
 
-	/// Read category from file.
-	pub fn load_from_file(file:&str) -> Result<DataCategory, Box<dyn Error>> {
-		use std::fs::read;
+	/// Get the path of the backup of a source file.
+	fn backup_path(source:&str) -> String {
+		format!("{source}.{BACKUP_FILE_EXTENSION}")
+	}
+
+	/// Calculate the checksum of a list of bytes using 32-bit FNV-1a.
+	fn checksum(bytes:&[u8]) -> u32 {
+		bytes.iter().fold(0x811c9dc5, |hash:u32, byte| (hash ^ *byte as u32).wrapping_mul(0x01000193))
+	}
+
+	/// Read category from file. When the file is missing, truncated or corrupt, the category is restored from its backup if possible. The unreadable file is kept next to it rather than overwritten. Other errors, like a file stored by a newer version, are returned as they are.
+	pub fn load_from_file(file:&str) -> Result<DataCategory, StorageError> {
+		use std::{ path::Path, fs::{ copy, rename } };
 
 		// Validate path.
 		if !file.ends_with(&format!(".{STORAGE_FILE_EXTENSION}")) {
-			return Err(format!("Could not read '{file}' as source, file is not recognized as DynamicDataStorage file due to its extension.").into());
+			return Err(StorageError::invalid_value(&format!("Could not read '{file}' as source, file is not recognized as DynamicDataStorage file due to its extension.")));
 		}
+
+		// Read file, fall back to the backup when it is missing or damaged.
+		let error:StorageError = match Self::read_file(file, file) {
+			Ok(category) => return Ok(category),
+			Err(error) if matches!(error, StorageError::Corrupt { .. }) || matches!(&error, StorageError::Io(io_error) if io_error.kind() == std::io::ErrorKind::NotFound) => error,
+			Err(error) => return Err(error)
+		};
+		let backup_path:String = Self::backup_path(file);
+		if !Path::new(&backup_path).exists() {
+			return Err(error);
+		}
+		let mut category:DataCategory = match Self::read_file(&backup_path, file) {
+			Ok(category) => category,
+			Err(_) => return Err(error)
+		};
+
+		// Move the damaged file aside and put the backup in its place.
+		if Path::new(file).exists() {
+			rename(file, Self::corrupt_path(file))?;
+		}
+		copy(&backup_path, file)?;
+		category.restored_from_backup = Some(error.to_string());
+		Ok(category)
+	}
+
+	/// Get the path a damaged source file is moved to when it is restored from its backup.
+	fn corrupt_path(source:&str) -> String {
+		format!("{source}.{CORRUPT_FILE_EXTENSION}")
+	}
+
+	/// Read a category from a specific file and let it use the given source from now on.
+	fn read_file(file:&str, source:&str) -> Result<DataCategory, StorageError> {
+		use std::fs::read;
 		
 		// Read file.
 		let file_contents:Vec<u8> = read(file)?;
 
 		// Validate file recognition tag.
-		if String::from_bytes(&file_contents[0..FILE_RECOGNITION_TAG.len()])? != FILE_RECOGNITION_TAG {
-			return Err(format!("Could not read '{file}' as source, file is not recognized as DynamicDataStorage file due to its contents.").into());
+		if file_contents.len() <= FILE_RECOGNITION_TAG.len() || &file_contents[0..FILE_RECOGNITION_TAG.len()] != FILE_RECOGNITION_TAG.as_bytes() {
+			return Err(StorageError::corrupt(0, &format!("Could not read '{file}' as source, file is not recognized as DynamicDataStorage file due to its contents.")));
 		}
 
-		// Find out category name.
+		// Parse the rest of the file in the layout it was written in.
 		let mut cursor:usize = FILE_RECOGNITION_TAG.len();
-		while file_contents[cursor] != b':' && cursor < file_contents.len() {
+		let (category_name, data) = if file_contents[cursor] == 0 {
 			cursor += 1;
+			let version:u16 = u16::from_bytes(Self::read_bytes(&file_contents, &mut cursor, 2)?)?;
+			if version > FILE_FORMAT_VERSION {
+				return Err(StorageError::VersionMismatch { name: file.to_string(), found: version, expected: FILE_FORMAT_VERSION });
+			}
+			let checksum:u32 = u32::from_bytes(Self::read_bytes(&file_contents, &mut cursor, 4)?)?;
+			if checksum != Self::checksum(&file_contents[cursor..]) {
+				return Err(StorageError::corrupt(cursor, &format!("Could not read '{file}' as source, its checksum does not match its contents.")));
+			}
+			Self::parse_contents(&file_contents, &mut cursor)?
+		} else {
+			Self::parse_legacy_contents(&file_contents, &mut cursor)?
+		};
+		
+		// Return category.
+		Ok(DataCategory { name: category_name, source: source.to_string(), data, dirty: false, restored_from_backup: None })
+	}
+
+	/// Parse the category name and variables of a versioned file, starting after the checksum. Each value stores its schema version and those of its items, followed by whether it is stored as bytes or as text imported from a text format.
+	fn parse_contents(file_contents:&[u8], cursor:&mut usize) -> Result<(String, Vec<NamedData>), StorageError> {
+
+		// Find out category name.
+		let name_end:usize = match file_contents[*cursor..].iter().position(|byte| *byte == 0) {
+			Some(offset) => *cursor + offset,
+			None => return Err(StorageError::corrupt(*cursor, "Could not parse storage file, no category name exists."))
+		};
+		let category_name:String = Self::terminated_string(&file_contents[*cursor..name_end]);
+		*cursor = name_end + 1;
+
+		// Read variables.
+		let mut data:Vec<NamedData> = Vec::new();
+		while *cursor < file_contents.len() {
+			let name_len:usize = u32::from_bytes(Self::read_bytes(file_contents, cursor, 4)?)? as usize;
+			let name:String = Self::terminated_string(Self::read_bytes(file_contents, cursor, name_len)?);
+			let schema_version:u16 = u16::from_bytes(Self::read_bytes(file_contents, cursor, 2)?)?;
+			let item_schema_versions:Vec<u16> = Self::read_item_schema_versions(file_contents, cursor)?;
+			let encoding_offset:usize = *cursor;
+			let encoding:u8 = Self::read_bytes(file_contents, cursor, 1)?[0];
+			let value_len:usize = u64::from_bytes(Self::read_bytes(file_contents, cursor, 8)?)? as usize;
+			let value_start:usize = *cursor;
+			let value:&[u8] = Self::read_bytes(file_contents, cursor, value_len)?;
+			data.push(match encoding {
+				VALUE_ENCODING_BYTES => NamedData::from_raw(&name, schema_version, &item_schema_versions, value),
+				VALUE_ENCODING_TEXT => NamedData::from_text(&name, schema_version, &item_schema_versions, Self::parse_text(value, value_start)?),
+				_ => return Err(StorageError::corrupt(encoding_offset, &format!("Could not parse storage file, unknown value encoding {encoding} for '{name}'.")))
+			});
+		}
+
+		Ok((category_name, data))
+	}
+
+	/// Parse the category name and variables of a file written before the format was versioned. These files store the length of names and values as u16.
+	fn parse_legacy_contents(file_contents:&[u8], cursor:&mut usize) -> Result<(String, Vec<NamedData>), StorageError> {
+
+		// Find out category name.
+		let name_start:usize = *cursor;
+		while *cursor < file_contents.len() && file_contents[*cursor] != b':' {
+			*cursor += 1;
 		}
-		if cursor >= file_contents.len() {
-			return Err(format!("Could not read '{file}' as source, no category name exists.").into());
+		if *cursor >= file_contents.len() {
+			return Err(StorageError::corrupt(name_start, "Could not parse storage file, no category name exists."));
 		}
-		let category_name:String = String::from_bytes(&file_contents[FILE_RECOGNITION_TAG.len()..cursor])?;
-		let mut category:DataCategory = DataCategory { name: category_name, source: file.to_string(), data: Vec::new() };
+		let category_name:String = Self::terminated_string(&file_contents[name_start..*cursor]);
+		*cursor += 2;
 
 		// Read variables.
-		cursor += 2;
-		while cursor < file_contents.len() {
+		let mut data:Vec<NamedData> = Vec::new();
+		while *cursor < file_contents.len() {
+			let name_len:usize = u16::from_bytes(Self::read_bytes(file_contents, cursor, 2)?)? as usize;
+			let name:String = Self::terminated_string(Self::read_bytes(file_contents, cursor, name_len)?);
+			let value_len:usize = u16::from_bytes(Self::read_bytes(file_contents, cursor, 2)?)? as usize;
+			let value:&[u8] = Self::read_bytes(file_contents, cursor, value_len)?;
+			data.push(NamedData::from_raw(&name, 0, &[], value));
+		}
+
+		Ok((category_name, data))
+	}
+
+	/// Parse a value stored as JSON text. Errors point at the offset of the problem in the file.
+	fn parse_text(value:&[u8], value_start:usize) -> Result<TextValue, StorageError> {
+		let text:&str = std::str::from_utf8(value).map_err(|error| StorageError::corrupt(value_start + error.valid_up_to(), "Could not parse storage file, stored text is not valid UTF-8."))?;
+		TextFormat::Json.parse(text).map_err(|error| match error {
+			StorageError::Corrupt { offset, message } => StorageError::Corrupt { offset: value_start + offset, message },
+			error => error
+		})
+	}
 
-			// Variable name.
-			let name_len:usize = u16::from_bytes(&file_contents[cursor..cursor+2])? as usize;
-			cursor += 2;
-			let name:String = String::from_bytes(&file_contents[cursor..cursor+name_len])?;
-			cursor += name_len;
+	/// Create the bytes of a null-terminated name as used in the header and variable names of the file.
+	fn terminated_bytes(text:&str) -> Vec<u8> {
+		[text.as_bytes(), &[0]].concat()
+	}
 
-			// Variable data.
-			let value_len:usize = u16::from_bytes(&file_contents[cursor..cursor+2])? as usize;
-			cursor += 2;
-			let value:&[u8] = &file_contents[cursor..cursor+value_len];
-			cursor += value_len;
+	/// Parse a null-terminated name as used in the header and variable names of the file.
+	fn terminated_string(bytes:&[u8]) -> String {
+		let end:usize = bytes.iter().position(|byte| *byte == 0).unwrap_or(bytes.len());
+		String::from_utf8_lossy(&bytes[..end]).to_string()
+	}
 
-			// Add to category.
-			category.set_value::<Vec<u8>>(&name, &value.to_vec())?;
+	/// Read the item schema versions of a value at the cursor, stored as a u32 count followed by the versions, and move the cursor past them.
+	fn read_item_schema_versions(file_contents:&[u8], cursor:&mut usize) -> Result<Vec<u16>, StorageError> {
+		let count:usize = u32::from_bytes(Self::read_bytes(file_contents, cursor, 4)?)? as usize;
+		let mut item_schema_versions:Vec<u16> = Vec::new();
+		for _ in 0..count {
+			item_schema_versions.push(u16::from_bytes(Self::read_bytes(file_contents, cursor, 2)?)?);
 		}
-		
-		// Return category.
-		Ok(category)
+		Ok(item_schema_versions)
 	}
 
-	/// Remove the file.
-	pub fn remove_file(&self) -> Result<(), Box<dyn Error>> {
+	/// Read a specific amount of bytes at the cursor and move the cursor past them.
+	fn read_bytes<'a>(file_contents:&'a [u8], cursor:&mut usize, length:usize) -> Result<&'a [u8], StorageError> {
+		let end:usize = cursor.checked_add(length).filter(|end| *end <= file_contents.len()).ok_or(StorageError::corrupt(*cursor, "Could not parse storage file, it ends unexpectedly."))?;
+		let bytes:&[u8] = &file_contents[*cursor..end];
+		*cursor = end;
+		Ok(bytes)
+	}
+
+	/// Remove the file and its backup.
+	pub fn remove_file(&self) -> Result<(), StorageError> {
 		use std::{path::Path, fs::remove_file};
 
-		let path:&Path = Path::new(&self.source);
-		if path.exists() {
-			match remove_file(path) {
-				Ok(_) => Ok(()),
-				Err(error) => Err(Box::new(error))
+		for file in [self.source.clone(), Self::backup_path(&self.source)] {
+			let path:&Path = Path::new(&file);
+			if path.exists() {
+				remove_file(path)?;
 			}
-		} else {
-			Ok(())
 		}
+		Ok(())
 	}
 }
\ No newline at end of file
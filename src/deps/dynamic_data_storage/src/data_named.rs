@@ -1,9 +1,16 @@
-use super::GeneralDataType;
-use std::error::Error;
+use super::{ GeneralDataType, MigrationRegistry, StorageError, TextValue };
+use std::cell::Cell;
 
+type TextConverter = fn(&NamedData) -> Result<(u16, Vec<u16>, TextValue), StorageError>;
+
+#[derive(Clone)]
 pub struct NamedData {
 	name:String,
+	schema_version:u16,
+	item_schema_versions:Vec<u16>,
 	data:Vec<u8>,
+	text:Option<TextValue>,
+	text_converter:Cell<Option<TextConverter>>
 }
 impl NamedData {
 
@@ -11,27 +18,148 @@ impl NamedData {
 	pub fn new<T:GeneralDataType>(name:&str, value:&T) -> NamedData {
 		NamedData {
 			name: name.to_string(),
+			schema_version: T::schema_version(),
+			item_schema_versions: T::item_schema_versions(),
 			data: value.to_bytes(),
+			text: None,
+			text_converter: Cell::new(Some(Self::convert_to_text::<T>))
+		}
+	}
+
+	/// Create a new instance from raw bytes stored with a specific schema version and item schema versions.
+	pub fn from_raw(name:&str, schema_version:u16, item_schema_versions:&[u16], data:&[u8]) -> NamedData {
+		NamedData {
+			name: name.to_string(),
+			schema_version,
+			item_schema_versions: item_schema_versions.to_vec(),
+			data: data.to_vec(),
+			text: None,
+			text_converter: Cell::new(None)
+		}
+	}
+
+	/// Create a new instance from a readable representation stored with a specific schema version and item schema versions. The value is parsed once it is requested with its type.
+	pub fn from_text(name:&str, schema_version:u16, item_schema_versions:&[u16], text:TextValue) -> NamedData {
+		NamedData {
+			name: name.to_string(),
+			schema_version,
+			item_schema_versions: item_schema_versions.to_vec(),
+			data: Vec::new(),
+			text: Some(text),
+			text_converter: Cell::new(None)
 		}
 	}
-	
+
 	/// Get the name of the data.
 	pub fn name(&self) -> &str {
 		&self.name
 	}
-	
-	/// Get the value from the data.
-	pub fn value<T:GeneralDataType>(&self) -> Result<T, Box<dyn Error>> {
-		T::from_bytes(&self.data)
+
+	/// Get the schema version the data was stored with.
+	pub fn schema_version(&self) -> u16 {
+		self.schema_version
+	}
+
+	/// Get the schema versions of the items the data was stored with. Empty for data that is not a container.
+	pub fn item_schema_versions(&self) -> &Vec<u16> {
+		&self.item_schema_versions
+	}
+
+	/// Get the raw bytes of the data. Empty for data imported from text that was not set since.
+	pub fn bytes(&self) -> &Vec<u8> {
+		&self.data
+	}
+
+	/// Get the readable representation of data imported from text that was not set since.
+	pub fn text(&self) -> &Option<TextValue> {
+		&self.text
 	}
-	
+
+	/// Check if another instance holds the same data, either as the same raw bytes or the same readable representation.
+	pub fn has_same_data(&self, other:&NamedData) -> bool {
+		self.schema_version == other.schema_version && self.item_schema_versions == other.item_schema_versions && self.data == other.data && self.text == other.text
+	}
+
+	/// Get the value from the data. Data stored with older schema versions is migrated first. Text has no migrations, but as it names its fields, older text is read when it still fits the type.
+	pub fn value<T:GeneralDataType>(&self) -> Result<T, StorageError> {
+		let stored_versions:Vec<u16> = self.stored_schema_versions::<T>();
+		let current_versions:Vec<u16> = [vec![T::schema_version()], T::item_schema_versions()].concat();
+		let is_newer:bool = stored_versions.len() == current_versions.len() && stored_versions.iter().zip(&current_versions).any(|(stored, current)| stored > current);
+		let value:T = match &self.text {
+			Some(text) if stored_versions == current_versions => T::from_text_value(text)?,
+			Some(text) if !is_newer => T::from_text_value(text).map_err(|_| StorageError::VersionMismatch { name: self.name.clone(), found: self.schema_version, expected: T::schema_version() })?,
+			Some(_) => return Err(StorageError::VersionMismatch { name: self.name.clone(), found: self.schema_version, expected: T::schema_version() }),
+			None if stored_versions == current_versions => T::from_bytes(&self.data)?,
+			None => T::from_bytes(&MigrationRegistry::get().migrate::<T>(&self.data, &stored_versions)?)?
+		};
+
+		// Now that the type is known, the data can be exported in a readable way.
+		self.text_converter.set(Some(Self::convert_to_text::<T>));
+		Ok(value)
+	}
+
+	/// Get the schema version of the data followed by the versions of its items. Data stored before item versions were recorded has no item versions, its items are expected to have their current version.
+	fn stored_schema_versions<T:GeneralDataType>(&self) -> Vec<u16> {
+		let item_schema_versions:Vec<u16> = if self.item_schema_versions.is_empty() { T::item_schema_versions() } else { self.item_schema_versions.clone() };
+		[vec![self.schema_version], item_schema_versions].concat()
+	}
+
 	/// Get the value from the data.
-	pub fn get_value<T:GeneralDataType>(&self) -> Result<T, Box<dyn Error>> {
+	pub fn get_value<T:GeneralDataType>(&self) -> Result<T, StorageError> {
 		self.value::<T>()
 	}
 
-	/// Set a new value.
-	pub fn set_value<T:GeneralDataType>(&mut self, value:&T) {
-		self.data = value.to_bytes();
+	/// Set a new value. Returns whether the stored data changed.
+	pub fn set_value<T:GeneralDataType>(&mut self, value:&T) -> bool {
+		let data:Vec<u8> = value.to_bytes();
+		let changed:bool = self.text.is_some() || self.schema_version != T::schema_version() || self.item_schema_versions != T::item_schema_versions() || self.data != data;
+		self.schema_version = T::schema_version();
+		self.item_schema_versions = T::item_schema_versions();
+		self.data = data;
+		self.text = None;
+		self.text_converter.set(Some(Self::convert_to_text::<T>));
+		changed
+	}
+
+
+
+	/* TEXT METHODS */
+
+	/// Create a readable representation of the data, containing its schema versions and either its value or raw bytes. The item schema versions are only included for containers. The value is only known when the data was set or requested with its type before.
+	pub fn to_text_value(&self) -> TextValue {
+		let converted:Option<(u16, Vec<u16>, TextValue)> = match &self.text {
+			Some(text) => Some((self.schema_version, self.item_schema_versions.clone(), text.clone())),
+			None => self.text_converter.get().and_then(|converter| converter(self).ok())
+		};
+		let (schema_version, item_schema_versions, content) = match converted {
+			Some((schema_version, item_schema_versions, value)) => (schema_version, item_schema_versions, (String::from("value"), value)),
+			None => (self.schema_version, self.item_schema_versions.clone(), (String::from("bytes"), TextValue::from_hex(&self.data)))
+		};
+		let mut fields:Vec<(String, TextValue)> = vec![(String::from("schema_version"), TextValue::from_integer(schema_version))];
+		if !item_schema_versions.is_empty() {
+			fields.push((String::from("item_schema_versions"), TextValue::List(item_schema_versions.iter().map(|version| TextValue::from_integer(*version)).collect::<Vec<TextValue>>())));
+		}
+		fields.push(content);
+		TextValue::Map(fields)
+	}
+
+	/// Create a new instance from a readable representation created by `to_text_value`.
+	pub fn from_text_value(name:&str, text:&TextValue) -> Result<NamedData, StorageError> {
+		let schema_version:u16 = text.field("schema_version").integer::<u16>().map_err(|error| StorageError::invalid_value(&format!("Could not read schema version of '{name}': {error}")))?;
+		let item_schema_versions:Vec<u16> = if text.has_field("item_schema_versions") {
+			text.field("item_schema_versions").list()?.iter().map(|version| version.integer::<u16>()).collect::<Result<Vec<u16>, StorageError>>().map_err(|error| StorageError::invalid_value(&format!("Could not read item schema versions of '{name}': {error}")))?
+		} else {
+			Vec::new()
+		};
+		if text.has_field("bytes") {
+			Ok(NamedData::from_raw(name, schema_version, &item_schema_versions, &text.field("bytes").hex()?))
+		} else {
+			Ok(NamedData::from_text(name, schema_version, &item_schema_versions, text.field("value").clone()))
+		}
 	}
-}
\ No newline at end of file
+
+	/// Convert the data to a readable representation using its type.
+	fn convert_to_text<T:GeneralDataType>(data:&NamedData) -> Result<(u16, Vec<u16>, TextValue), StorageError> {
+		Ok((T::schema_version(), T::item_schema_versions(), data.value::<T>()?.to_text_value()))
+	}
+}
@@ -1,14 +1,14 @@
-use std::error::Error;
+use super::{ MigrationRegistry, StorageError, TextValue };
+use std::{ collections::HashMap, hash::Hash };
 
 pub trait GeneralDataType:Sized {
 
 	/// Validate the value can be built from this byte count, then move on to from_bytes_inner. Will result in an error of value of the implemented type.
-	fn from_bytes(bytes:&[u8]) -> Result<Self, Box<dyn Error>> {
+	fn from_bytes(bytes:&[u8]) -> Result<Self, StorageError> {
 
 		// Check byte size.
-		let target_byte_size:usize = Self::byte_size();
-		if bytes.len() != target_byte_size {
-			return Err(format!("DRAWABLE DATA SETTING TYPE ERROR: Invalid byte size. expected {} bytes, got {}.", target_byte_size, bytes.len()).into());
+		if bytes.len() != Self::byte_size() {
+			return Err(StorageError::wrong_size::<Self>(bytes.len()));
 		}
 
 		// Continue on to inner function.
@@ -16,42 +16,324 @@ pub trait GeneralDataType:Sized {
 	}
 
 	/// Create a value of the implemented type from these bytes.
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>>;
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError>;
 
 	/// Create a value of the implemented type from these bytes while removing the bytes required from the bytes list. Useful for parsing more advances structs.
-	fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, Box<dyn Error>> { Self::from_bytes(&bytes.drain(0..Self::byte_size()).collect::<Vec<u8>>()) }
+	fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, StorageError> {
+		if bytes.len() < Self::byte_size() {
+			return Err(StorageError::wrong_size::<Self>(bytes.len()));
+		}
+		Self::from_bytes(&bytes.drain(..Self::byte_size()).collect::<Vec<u8>>())
+	}
 
 	/// Create a list of bytes from the value.
 	fn to_bytes(&self) -> Vec<u8>;
 
 	/// Get the byte size of this type.
 	fn byte_size() -> usize;
+
+	/// Get the version of the byte layout of this type. Increase it whenever the layout changes and register a migration from the previous version in the MigrationRegistry. Types containing other types should also increase it when the layout of a contained type changes. Containers are the exception, they have a fixed version and keep track of the versions of their items instead.
+	fn schema_version() -> u16 { 0 }
+
+	/// Get the schema versions of the items of a container, each followed by the versions of its own items. Empty for types that are not containers.
+	fn item_schema_versions() -> Vec<u16> { Vec::new() }
+
+	/// Upgrade the items of a value stored with the current schema version, while removing its bytes from the bytes list. The item versions are the stored counterpart of `item_schema_versions`. Types that are not containers have no items to upgrade.
+	fn migrate_items_consume(bytes:&mut Vec<u8>, _item_versions:&[u16], _registry:&MigrationRegistry) -> Result<Vec<u8>, StorageError> {
+		Ok(Self::from_bytes_consume(bytes)?.to_bytes())
+	}
+
+	/// Create a readable representation of the value, used when exporting storage to text formats. Defaults to the bytes of the value as hexadecimal text.
+	fn to_text_value(&self) -> TextValue { TextValue::from_hex(&self.to_bytes()) }
+
+	/// Create a value of the implemented type from its readable representation.
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { Self::from_bytes(&value.hex()?) }
 }
 
+// Stored as a u32 byte count followed by the UTF-8 bytes. Version 0 stored null-terminated single-byte characters, those are upgraded by a built-in migration.
 impl GeneralDataType for String {
-	fn from_bytes(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Self::from_bytes_inner(bytes) }
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> {
-		let end:usize = bytes.iter().enumerate().find(|(_, character)| *character == &0).map(|(end, _)| end).unwrap_or(bytes.len());
-		Ok(String::from_utf8_lossy(&bytes[..end]).to_string())
+	fn from_bytes(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+	fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, StorageError> {
+		let byte_count:usize = u32::from_bytes_consume(bytes)? as usize;
+		String::from_utf8(consume_bytes::<String>(bytes, byte_count)?).map_err(|error| StorageError::invalid_value(&format!("Could not read String: {error}")))
+	}
+	fn to_bytes(&self) -> Vec<u8> { [(self.len() as u32).to_bytes(), self.as_bytes().to_vec()].concat() }
+	fn byte_size() -> usize { 0 }
+	fn schema_version() -> u16 { 1 }
+	fn to_text_value(&self) -> TextValue { TextValue::Text(self.to_string()) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { Ok(value.text()?.to_string()) }
+}
+impl GeneralDataType for usize {
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(u64::from_bytes_inner(bytes)? as usize) }
+	fn to_bytes(&self) -> Vec<u8> { (*self as u64).to_bytes() }
+	fn byte_size() -> usize { 8 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_integer(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.integer() }
+}
+impl GeneralDataType for bool {
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> {
+		match u8::from_bytes(bytes)? {
+			0 => Ok(false),
+			1 => Ok(true),
+			value => Err(StorageError::invalid_value(&format!("Could not parse bool from value {value}.")))
+		}
+	}
+	fn to_bytes(&self) -> Vec<u8> { vec![*self as u8] }
+	fn byte_size() -> usize { 1 }
+	fn to_text_value(&self) -> TextValue { TextValue::Bool(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.bool() }
+}
+impl GeneralDataType for char {
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> {
+		let value:u32 = u32::from_bytes_inner(bytes)?;
+		char::from_u32(value).ok_or_else(|| StorageError::invalid_value(&format!("Could not parse char from value {value}.")))
+	}
+	fn to_bytes(&self) -> Vec<u8> { (*self as u32).to_bytes() }
+	fn byte_size() -> usize { 4 }
+	fn to_text_value(&self) -> TextValue { TextValue::Text(self.to_string()) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> {
+		let mut characters:std::str::Chars = value.text()?.chars();
+		match (characters.next(), characters.next()) {
+			(Some(character), None) => Ok(character),
+			_ => Err(StorageError::invalid_value(&format!("Could not read '{}' as a single character.", value.text()?)))
+		}
+	}
+}
+
+
+
+/* GENERIC METHODS */
+
+/// Parse a string stored in the layout used before strings were length-prefixed UTF-8. That layout stores each character as a single byte and ends at the first null byte.
+pub fn string_from_legacy_bytes(bytes:&[u8]) -> String {
+	bytes.iter().take_while(|byte| **byte != 0).map(|byte| *byte as char).collect::<String>()
+}
+
+/// Parse a string stored in the layout used before strings were length-prefixed UTF-8 while removing its bytes and null terminator from the bytes list.
+pub fn string_from_legacy_bytes_consume(bytes:&mut Vec<u8>) -> String {
+	let end:usize = bytes.iter().position(|byte| *byte == 0).map(|terminator| terminator + 1).unwrap_or(bytes.len());
+	string_from_legacy_bytes(&bytes.drain(..end).collect::<Vec<u8>>())
+}
+
+/// Create a value from these bytes by consuming them, failing if not all bytes were used. Used by types without a set byte size.
+pub fn from_bytes_exact<T:GeneralDataType>(bytes:&[u8]) -> Result<T, StorageError> {
+	let mut remaining_bytes:Vec<u8> = bytes.to_vec();
+	let value:T = T::from_bytes_consume(&mut remaining_bytes)?;
+	if !remaining_bytes.is_empty() {
+		return Err(StorageError::WrongSize { type_name: std::any::type_name::<T>().to_string(), expected: bytes.len() - remaining_bytes.len(), found: bytes.len() });
+	}
+	Ok(value)
+}
+
+/// Remove a specific amount of bytes from the start of the bytes list, as part of a value of type T. Fails without removing anything when not enough bytes are left.
+pub fn consume_bytes<T:GeneralDataType>(bytes:&mut Vec<u8>, count:usize) -> Result<Vec<u8>, StorageError> {
+	if bytes.len() < count {
+		return Err(StorageError::WrongSize { type_name: std::any::type_name::<T>().to_string(), expected: count, found: bytes.len() });
+	}
+	Ok(bytes.drain(..count).collect::<Vec<u8>>())
+}
+
+/// Split the schema versions of an item of type T, followed by the versions of its own items, from the start of a list of stored item versions.
+fn split_item_versions<T:GeneralDataType>(item_versions:&[u16]) -> Result<(&[u16], &[u16]), StorageError> {
+	let count:usize = 1 + T::item_schema_versions().len();
+	if item_versions.len() < count {
+		return Err(StorageError::invalid_value(&format!("Could not migrate {}, the item versions it was stored with do not match its type.", std::any::type_name::<T>())));
+	}
+	Ok(item_versions.split_at(count))
+}
+
+// Stored as a u8 flag followed by the value if there is one.
+impl<T:GeneralDataType> GeneralDataType for Option<T> {
+	fn from_bytes(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+	fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, StorageError> {
+		if bool::from_bytes_consume(bytes)? { Ok(Some(T::from_bytes_consume(bytes)?)) } else { Ok(None) }
 	}
-	fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, Box<dyn Error>> {
-		let mut end:usize = bytes.len();
-		if let Some(null_terminator) = bytes.iter().enumerate().find(|(_, character)| *character == &0).map(|(end, _)| end) {
-			end = null_terminator;
-			bytes.remove(null_terminator);
+	fn to_bytes(&self) -> Vec<u8> {
+		match self {
+			Some(value) => [vec![1], value.to_bytes()].concat(),
+			None => vec![0]
 		}
-		Ok(String::from_utf8_lossy(&bytes.drain(..end).collect::<Vec<u8>>()).to_string())
 	}
 	fn byte_size() -> usize { 0 }
-	fn to_bytes(&self) -> Vec<u8> { [self.chars().map(|character| character as u8).collect::<Vec<u8>>(), vec![0]].iter().flatten().copied().collect::<Vec<u8>>() }
+	fn item_schema_versions() -> Vec<u16> { [vec![T::schema_version()], T::item_schema_versions()].concat() }
+	fn migrate_items_consume(bytes:&mut Vec<u8>, item_versions:&[u16], registry:&MigrationRegistry) -> Result<Vec<u8>, StorageError> {
+		if bool::from_bytes_consume(bytes)? { Ok([vec![1], registry.migrate_consume::<T>(bytes, item_versions)?].concat()) } else { Ok(vec![0]) }
+	}
+	fn to_text_value(&self) -> TextValue {
+		match self {
+			Some(value) => value.to_text_value(),
+			None => TextValue::Null
+		}
+	}
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> {
+		match value {
+			TextValue::Null => Ok(None),
+			value => Ok(Some(T::from_text_value(value)?))
+		}
+	}
 }
-impl GeneralDataType for Vec<u8> {
-	fn from_bytes(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Self::from_bytes_inner(bytes) }
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(bytes.to_vec()) }
-	fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, Box<dyn Error>> { Ok(std::mem::take(bytes)) }
+
+// Stored as a u32 item count followed by the items. Version 0 stored lists of bytes as their bytes only, those are upgraded by a built-in migration.
+impl<T:GeneralDataType> GeneralDataType for Vec<T> {
+	fn from_bytes(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+	fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, StorageError> {
+		let item_count:usize = u32::from_bytes_consume(bytes)? as usize;
+		let mut items:Vec<T> = Vec::new();
+		for _ in 0..item_count {
+			items.push(T::from_bytes_consume(bytes)?);
+		}
+		Ok(items)
+	}
+	fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes:Vec<u8> = (self.len() as u32).to_bytes();
+		for item in self {
+			bytes.extend_from_slice(&item.to_bytes());
+		}
+		bytes
+	}
 	fn byte_size() -> usize { 0 }
-	fn to_bytes(&self) -> Vec<u8> { self[..].to_vec() }
+	fn schema_version() -> u16 { 1 }
+	fn item_schema_versions() -> Vec<u16> { [vec![T::schema_version()], T::item_schema_versions()].concat() }
+	fn migrate_items_consume(bytes:&mut Vec<u8>, item_versions:&[u16], registry:&MigrationRegistry) -> Result<Vec<u8>, StorageError> {
+		let item_count:u32 = u32::from_bytes_consume(bytes)?;
+		let mut migrated:Vec<u8> = item_count.to_bytes();
+		for _ in 0..item_count {
+			migrated.extend_from_slice(&registry.migrate_consume::<T>(bytes, item_versions)?);
+		}
+		Ok(migrated)
+	}
+	fn to_text_value(&self) -> TextValue { TextValue::List(self.iter().map(|item| item.to_text_value()).collect::<Vec<TextValue>>()) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.list()?.iter().map(T::from_text_value).collect() }
+}
+
+// Stored as the items without count.
+impl<T:GeneralDataType, const N:usize> GeneralDataType for [T; N] {
+	fn from_bytes(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+	fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, StorageError> {
+		let mut items:Vec<T> = Vec::new();
+		for _ in 0..N {
+			items.push(T::from_bytes_consume(bytes)?);
+		}
+		items.try_into().map_err(|_| StorageError::invalid_value("Could not parse array, invalid amount of items."))
+	}
+	fn to_bytes(&self) -> Vec<u8> { self.iter().flat_map(|item| item.to_bytes()).collect::<Vec<u8>>() }
+	fn byte_size() -> usize { T::byte_size() * N }
+	fn item_schema_versions() -> Vec<u16> { [vec![T::schema_version()], T::item_schema_versions()].concat() }
+	fn migrate_items_consume(bytes:&mut Vec<u8>, item_versions:&[u16], registry:&MigrationRegistry) -> Result<Vec<u8>, StorageError> {
+		let mut migrated:Vec<u8> = Vec::new();
+		for _ in 0..N {
+			migrated.extend_from_slice(&registry.migrate_consume::<T>(bytes, item_versions)?);
+		}
+		Ok(migrated)
+	}
+	fn to_text_value(&self) -> TextValue { TextValue::List(self.iter().map(|item| item.to_text_value()).collect::<Vec<TextValue>>()) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> {
+		let items:Vec<T> = value.list()?.iter().map(T::from_text_value).collect::<Result<Vec<T>, StorageError>>()?;
+		let item_count:usize = items.len();
+		items.try_into().map_err(|_| StorageError::invalid_value(&format!("Could not parse array of {N} items from a list of {item_count} items.")))
+	}
+}
+
+// Stored as a u32 entry count followed by the key and value of each entry, in no specific order. As text, maps with text keys are stored as a map and others as a list of key-value pairs, both sorted by key.
+impl<K:GeneralDataType + Eq + Hash, V:GeneralDataType> GeneralDataType for HashMap<K, V> {
+	fn from_bytes(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+	fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, StorageError> {
+		let entry_count:usize = u32::from_bytes_consume(bytes)? as usize;
+		let mut map:HashMap<K, V> = HashMap::new();
+		for _ in 0..entry_count {
+			let key:K = K::from_bytes_consume(bytes)?;
+			map.insert(key, V::from_bytes_consume(bytes)?);
+		}
+		Ok(map)
+	}
+	fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes:Vec<u8> = (self.len() as u32).to_bytes();
+		for (key, value) in self {
+			bytes.extend_from_slice(&key.to_bytes());
+			bytes.extend_from_slice(&value.to_bytes());
+		}
+		bytes
+	}
+	fn byte_size() -> usize { 0 }
+	fn item_schema_versions() -> Vec<u16> { [vec![K::schema_version()], K::item_schema_versions(), vec![V::schema_version()], V::item_schema_versions()].concat() }
+	fn migrate_items_consume(bytes:&mut Vec<u8>, item_versions:&[u16], registry:&MigrationRegistry) -> Result<Vec<u8>, StorageError> {
+		let (key_versions, value_versions) = split_item_versions::<K>(item_versions)?;
+		let entry_count:u32 = u32::from_bytes_consume(bytes)?;
+		let mut migrated:Vec<u8> = entry_count.to_bytes();
+		for _ in 0..entry_count {
+			migrated.extend_from_slice(&registry.migrate_consume::<K>(bytes, key_versions)?);
+			migrated.extend_from_slice(&registry.migrate_consume::<V>(bytes, value_versions)?);
+		}
+		Ok(migrated)
+	}
+	fn to_text_value(&self) -> TextValue {
+		let mut entries:Vec<(TextValue, TextValue)> = self.iter().map(|(key, value)| (key.to_text_value(), value.to_text_value())).collect::<Vec<(TextValue, TextValue)>>();
+		entries.sort_by_key(|(key, _)| format!("{key:?}"));
+		if entries.iter().all(|(key, _)| matches!(key, TextValue::Text(_))) {
+			TextValue::Map(entries.into_iter().map(|(key, value)| (key.text().unwrap_or_default().to_string(), value)).collect::<Vec<(String, TextValue)>>())
+		} else {
+			TextValue::List(entries.into_iter().map(|(key, value)| TextValue::List(vec![key, value])).collect::<Vec<TextValue>>())
+		}
+	}
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> {
+		let mut map:HashMap<K, V> = HashMap::new();
+		match value {
+			TextValue::List(entries) => for entry in entries {
+				map.insert(K::from_text_value(entry.item(0))?, V::from_text_value(entry.item(1))?);
+			},
+			value => for (key, value) in value.map()? {
+				map.insert(K::from_text_value(&TextValue::Text(key.to_string()))?, V::from_text_value(value)?);
+			}
+		}
+		Ok(map)
+	}
+}
+
+// Stored as their values in order. Implemented for tuples of up to 6 values.
+macro_rules! implement_tuple {
+	($($name:ident $index:tt),+) => {
+		impl<$($name:GeneralDataType),+> GeneralDataType for ($($name,)+) {
+			fn from_bytes(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+			fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { from_bytes_exact(bytes) }
+			fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, StorageError> { Ok(($($name::from_bytes_consume(bytes)?,)+)) }
+			fn to_bytes(&self) -> Vec<u8> { [$(self.$index.to_bytes()),+].concat() }
+			fn byte_size() -> usize {
+				let sizes:Vec<usize> = vec![$($name::byte_size()),+];
+				if sizes.contains(&0) { 0 } else { sizes.iter().sum() }
+			}
+			fn item_schema_versions() -> Vec<u16> { [$(vec![$name::schema_version()], $name::item_schema_versions()),+].concat() }
+			fn migrate_items_consume(bytes:&mut Vec<u8>, item_versions:&[u16], registry:&MigrationRegistry) -> Result<Vec<u8>, StorageError> {
+				let mut remaining_versions:&[u16] = item_versions;
+				let mut migrated:Vec<u8> = Vec::new();
+				$(
+					let (versions, remaining) = split_item_versions::<$name>(remaining_versions)?;
+					migrated.extend_from_slice(&registry.migrate_consume::<$name>(bytes, versions)?);
+					remaining_versions = remaining;
+				)+
+				if !remaining_versions.is_empty() {
+					return Err(StorageError::invalid_value(&format!("Could not migrate {}, the item versions it was stored with do not match its type.", std::any::type_name::<Self>())));
+				}
+				Ok(migrated)
+			}
+			fn to_text_value(&self) -> TextValue { TextValue::List(vec![$(self.$index.to_text_value()),+]) }
+			fn from_text_value(value:&TextValue) -> Result<Self, StorageError> {
+				value.list()?;
+				Ok(($($name::from_text_value(value.item($index))?,)+))
+			}
+		}
+	};
 }
+implement_tuple!(A 0);
+implement_tuple!(A 0, B 1);
+implement_tuple!(A 0, B 1, C 2);
+implement_tuple!(A 0, B 1, C 2, D 3);
+implement_tuple!(A 0, B 1, C 2, D 3, E 4);
+implement_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
 
 
 /* AUTOGENERATED */
@@ -61,29 +343,39 @@ impl GeneralDataType for Vec<u8> {
 /* UNSIGNED INT METHODS */
 
 impl GeneralDataType for u8 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(u8::from_le_bytes([bytes[0]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(u8::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 1 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_integer(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.integer() }
 }
 impl GeneralDataType for u16 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(u16::from_le_bytes([bytes[0], bytes[1]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(u16::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 2 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_integer(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.integer() }
 }
 impl GeneralDataType for u32 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(u32::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 4 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_integer(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.integer() }
 }
 impl GeneralDataType for u64 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(u64::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(u64::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 8 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_integer(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.integer() }
 }
 impl GeneralDataType for u128 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(u128::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7], bytes[8], bytes[9], bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(u128::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 16 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_integer(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.integer() }
 }
 
 
@@ -91,29 +383,39 @@ impl GeneralDataType for u128 {
 /* SIGNED INT METHODS */
 
 impl GeneralDataType for i8 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(i8::from_le_bytes([bytes[0]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(i8::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 1 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_integer(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.integer() }
 }
 impl GeneralDataType for i16 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(i16::from_le_bytes([bytes[0], bytes[1]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(i16::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 2 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_integer(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.integer() }
 }
 impl GeneralDataType for i32 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(i32::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 4 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_integer(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.integer() }
 }
 impl GeneralDataType for i64 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(i64::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(i64::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 8 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_integer(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.integer() }
 }
 impl GeneralDataType for i128 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(i128::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7], bytes[8], bytes[9], bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(i128::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 16 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_integer(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.integer() }
 }
 
 
@@ -121,14 +423,18 @@ impl GeneralDataType for i128 {
 /* FLOATING POINT NUMBER METHODS */
 
 impl GeneralDataType for f32 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(f32::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 4 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_float(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.float() }
 }
 impl GeneralDataType for f64 {
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> { Ok(f64::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]])) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, StorageError> { Ok(f64::from_le_bytes(bytes.try_into().map_err(|_| StorageError::wrong_size::<Self>(bytes.len()))?)) }
 	fn to_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
 	fn byte_size() -> usize { 8 }
+	fn to_text_value(&self) -> TextValue { TextValue::from_float(*self) }
+	fn from_text_value(value:&TextValue) -> Result<Self, StorageError> { value.float() }
 }
 
 
@@ -1,9 +1,20 @@
+extern crate self as dynamic_data_storage;
+pub use dynamic_data_storage_derive::GeneralDataType;
+
 mod data_type;
+mod migration_registry;
+mod storage_error;
 mod storage_manager;
+mod storage_subscription;
+mod text_value;
 pub use data_type::*;
+pub use migration_registry::*;
+pub use storage_error::*;
 pub use storage_manager::*;
+pub use storage_subscription::*;
+pub use text_value::*;
 
-// auto-publib crate-only exclude=[data_type,storage_manager]
+// auto-publib crate-only exclude=[data_type,migration_registry,storage_error,storage_manager,storage_subscription,text_value]
 pub(crate) mod _unit_testing;
 
 mod data_category;
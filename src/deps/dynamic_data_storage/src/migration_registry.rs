@@ -0,0 +1,157 @@
+use super::{ GeneralDataType, StorageError, string_from_legacy_bytes };
+use std::sync::{ Mutex, MutexGuard, PoisonError };
+
+pub type Migration = fn(&[u8]) -> Result<Vec<u8>, StorageError>;
+pub type LegacyParser<T> = fn(&mut Vec<u8>) -> Result<T, StorageError>;
+type MigrationHandler = Box<dyn Fn(&[u8]) -> Result<Vec<u8>, StorageError> + Send>;
+type ItemMigrationHandler = Box<dyn Fn(&mut Vec<u8>) -> Result<Vec<u8>, StorageError> + Send>;
+
+struct RegisteredMigration {
+	type_name:&'static str,
+	from_version:u16,
+	to_version:u16,
+	handler:MigrationHandler,
+	item_handler:Option<ItemMigrationHandler>
+}
+
+// A simpleton struct that keeps track of how to upgrade stored values from older schema versions of their type. Access is synchronized, so it can be used from any thread.
+static STATIC_REGISTRY:Mutex<MigrationRegistry> = Mutex::new(MigrationRegistry { migrations: Vec::new() });
+pub struct MigrationRegistry {
+	migrations:Vec<RegisteredMigration>
+}
+impl MigrationRegistry {
+
+	/* GETTER METHODS */
+
+	/// Lock the static registry. Other threads wait until the returned guard is dropped, so do not keep it around longer than needed.
+	pub fn get() -> MutexGuard<'static, MigrationRegistry> {
+		STATIC_REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	/// Lock the static registry to register migrations. Identical to `get`, as the lock is exclusive either way.
+	pub fn get_mut() -> MutexGuard<'static, MigrationRegistry> {
+		MigrationRegistry::get()
+	}
+
+	/// Hard-reset all registered migrations.
+	#[allow(unused)]
+	pub fn hard_reset() {
+		*MigrationRegistry::get() = MigrationRegistry { migrations: Vec::new() };
+	}
+
+
+
+	/* REGISTRATION METHODS */
+
+	/// Register a migration that upgrades the bytes of a value of type T from the given schema version to the next one. Registering a migration or parser for the same type and version again replaces it. A migration cannot tell where a value ends, so values stored as items of a container require a parser instead.
+	pub fn register<T:GeneralDataType>(&mut self, from_version:u16, migration:Migration) {
+		self.register_handler::<T>(from_version, from_version + 1, Box::new(migration), None);
+	}
+
+	/// Register a parser that reads a value of type T stored with the given schema version directly, while removing the bytes it used from the bytes list. The parsed value is stored in the current layout of T, so no other migrations are required for older versions that have a parser. Parsers are also used to upgrade items of containers.
+	pub fn register_parser<T:GeneralDataType + 'static>(&mut self, from_version:u16, parser:LegacyParser<T>) {
+		let handler:MigrationHandler = Box::new(move |bytes| {
+			let mut bytes:Vec<u8> = bytes.to_vec();
+			let value:T = parser(&mut bytes)?;
+			if !bytes.is_empty() {
+				return Err(StorageError::invalid_value(&format!("Could not migrate {}, {} bytes were left unused.", std::any::type_name::<T>(), bytes.len())));
+			}
+			Ok(value.to_bytes())
+		});
+		self.register_handler::<T>(from_version, T::schema_version(), handler, Some(Box::new(move |bytes| Ok(parser(bytes)?.to_bytes()))));
+	}
+
+	/// Register a handler that upgrades the bytes of a value of type T between two schema versions.
+	fn register_handler<T:GeneralDataType>(&mut self, from_version:u16, to_version:u16, handler:MigrationHandler, item_handler:Option<ItemMigrationHandler>) {
+		let type_name:&'static str = std::any::type_name::<T>();
+		self.migrations.retain(|migration| migration.type_name != type_name || migration.from_version != from_version);
+		self.migrations.push(RegisteredMigration { type_name, from_version, to_version, handler, item_handler });
+	}
+
+	/// Find the migration for a type provided by the storage itself.
+	fn find_built_in<T:GeneralDataType>(from_version:u16) -> Option<Migration> {
+		let type_name:&'static str = std::any::type_name::<T>();
+		if type_name == std::any::type_name::<Vec<u8>>() && from_version == 0 {
+			Some(Self::migrate_unprefixed_byte_list)
+		} else if type_name == std::any::type_name::<String>() && from_version == 0 {
+			Some(Self::migrate_terminated_string)
+		} else {
+			None
+		}
+	}
+
+	/// Upgrade a list of bytes stored before lists were prefixed with their item count.
+	fn migrate_unprefixed_byte_list(bytes:&[u8]) -> Result<Vec<u8>, StorageError> {
+		Ok([(bytes.len() as u32).to_bytes(), bytes.to_vec()].concat())
+	}
+
+	/// Upgrade a string stored as null-terminated single-byte characters to length-prefixed UTF-8.
+	fn migrate_terminated_string(bytes:&[u8]) -> Result<Vec<u8>, StorageError> {
+		Ok(string_from_legacy_bytes(bytes).to_bytes())
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Upgrade the bytes of a value of type T to the current schema versions of T and its items. The stored versions start with the version of T itself, followed by the versions of its items like `item_schema_versions`.
+	pub fn migrate<T:GeneralDataType>(&self, bytes:&[u8], stored_versions:&[u16]) -> Result<Vec<u8>, StorageError> {
+		let (stored_version, item_versions) = Self::split_stored_versions::<T>(stored_versions)?;
+		if stored_version < T::schema_version() {
+			return self.migrate_own_version::<T>(bytes, stored_version);
+		}
+
+		// The layout of the value itself is current, so only its items might need to be upgraded.
+		let mut bytes:Vec<u8> = bytes.to_vec();
+		let migrated:Vec<u8> = T::migrate_items_consume(&mut bytes, item_versions, self)?;
+		if !bytes.is_empty() {
+			return Err(StorageError::invalid_value(&format!("Could not migrate {}, {} bytes were left unused.", std::any::type_name::<T>(), bytes.len())));
+		}
+		Ok(migrated)
+	}
+
+	/// Upgrade a value of type T stored as item of a container to the current schema versions of T and its items, while removing its bytes from the bytes list. Only registered parsers know where an older value ends, so those are the only migrations used.
+	pub fn migrate_consume<T:GeneralDataType>(&self, bytes:&mut Vec<u8>, stored_versions:&[u16]) -> Result<Vec<u8>, StorageError> {
+		let (stored_version, item_versions) = Self::split_stored_versions::<T>(stored_versions)?;
+		if stored_version == T::schema_version() {
+			return T::migrate_items_consume(bytes, item_versions, self);
+		}
+		let type_name:&'static str = std::any::type_name::<T>();
+		match self.migrations.iter().find(|migration| migration.type_name == type_name && migration.from_version == stored_version).and_then(|migration| migration.item_handler.as_ref()) {
+			Some(item_handler) => item_handler(bytes),
+			None => Err(StorageError::VersionMismatch { name: type_name.to_string(), found: stored_version, expected: T::schema_version() })
+		}
+	}
+
+	/// Split stored versions into the version of T itself and the versions of its items. Fails when they do not fit T or when T was stored by a newer version of the type.
+	fn split_stored_versions<T:GeneralDataType>(stored_versions:&[u16]) -> Result<(u16, &[u16]), StorageError> {
+		let type_name:&'static str = std::any::type_name::<T>();
+		match stored_versions.split_first() {
+			Some((stored_version, _)) if *stored_version > T::schema_version() => Err(StorageError::VersionMismatch { name: type_name.to_string(), found: *stored_version, expected: T::schema_version() }),
+			Some((stored_version, item_versions)) if item_versions.len() == T::item_schema_versions().len() => Ok((*stored_version, item_versions)),
+			_ => Err(StorageError::invalid_value(&format!("Could not migrate {type_name}, the item versions it was stored with do not match its type.")))
+		}
+	}
+
+	/// Upgrade the bytes of a value of type T stored with an older schema version of T itself to the current schema version of T.
+	fn migrate_own_version<T:GeneralDataType>(&self, bytes:&[u8], stored_version:u16) -> Result<Vec<u8>, StorageError> {
+		let target_version:u16 = T::schema_version();
+
+		// Run migrations in order until the current version is reached. Registered migrations and parsers take priority over the built-in ones.
+		let type_name:&'static str = std::any::type_name::<T>();
+		let mut bytes:Vec<u8> = bytes.to_vec();
+		let mut version:u16 = stored_version;
+		while version < target_version {
+			if let Some(migration) = self.migrations.iter().find(|migration| migration.type_name == type_name && migration.from_version == version) {
+				bytes = (migration.handler)(&bytes)?;
+				version = migration.to_version.max(version + 1);
+			} else if let Some(migration) = Self::find_built_in::<T>(version) {
+				bytes = migration(&bytes)?;
+				version += 1;
+			} else {
+				return Err(StorageError::VersionMismatch { name: type_name.to_string(), found: version, expected: target_version });
+			}
+		}
+		Ok(bytes)
+	}
+}
@@ -0,0 +1,83 @@
+use super::GeneralDataType;
+use std::{ error::Error, fmt::{ self, Display, Formatter } };
+
+
+
+// Everything that can go wrong while storing, loading or converting data.
+#[derive(Debug)]
+pub enum StorageError {
+
+	/// A variable does not exist. Without category, the variable was searched in all categories.
+	NotFound { category:Option<String>, variable:String },
+
+	/// A category does not exist in storage.
+	UnknownCategory(String),
+
+	/// A value could not be read, as the amount of bytes does not match the size of its type.
+	WrongSize { type_name:String, expected:usize, found:usize },
+
+	/// Stored data could not be read. The offset is the position of the problem within the file or text.
+	Corrupt { offset:usize, message:String },
+
+	/// Data was stored with a version that cannot be read.
+	VersionMismatch { name:String, found:u16, expected:u16 },
+
+	/// A value does not fit its type, like a bool stored as 2 or text where a number is expected.
+	InvalidValue(String),
+
+	/// Reading or writing a file failed.
+	Io(std::io::Error)
+}
+impl StorageError {
+
+	/// Create the error for bytes that do not match the size of a type.
+	pub fn wrong_size<T:GeneralDataType>(found:usize) -> StorageError {
+		StorageError::WrongSize { type_name: std::any::type_name::<T>().to_string(), expected: T::byte_size(), found }
+	}
+
+	/// Create the error for stored data that could not be read.
+	pub fn corrupt(offset:usize, message:&str) -> StorageError {
+		StorageError::Corrupt { offset, message: message.to_string() }
+	}
+
+	/// Create the error for a value that does not fit its type.
+	pub fn invalid_value(message:&str) -> StorageError {
+		StorageError::InvalidValue(message.to_string())
+	}
+
+	/// Check if the error means the requested data simply does not exist, rather than that it could not be read.
+	pub fn is_not_found(&self) -> bool {
+		match self {
+			StorageError::NotFound { .. } | StorageError::UnknownCategory(_) => true,
+			StorageError::Io(error) => error.kind() == std::io::ErrorKind::NotFound,
+			_ => false
+		}
+	}
+}
+impl Display for StorageError {
+	fn fmt(&self, f:&mut Formatter<'_>) -> fmt::Result {
+		match self {
+			StorageError::NotFound { category: Some(category), variable } => write!(f, "Could not find variable '{variable}' in category '{category}'."),
+			StorageError::NotFound { category: None, variable } => write!(f, "Could not find variable '{variable}' in any category in storage."),
+			StorageError::UnknownCategory(category) => write!(f, "Could not find category '{category}' in storage."),
+			StorageError::WrongSize { type_name, expected, found } => write!(f, "Could not read {type_name}, expected {expected} bytes but got {found}."),
+			StorageError::Corrupt { offset, message } => write!(f, "Could not read data at offset {offset}: {message}"),
+			StorageError::VersionMismatch { name, found, expected } => write!(f, "Could not read '{name}', it was stored with version {found} while version {expected} is supported."),
+			StorageError::InvalidValue(message) => write!(f, "{message}"),
+			StorageError::Io(error) => write!(f, "{error}")
+		}
+	}
+}
+impl Error for StorageError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			StorageError::Io(error) => Some(error),
+			_ => None
+		}
+	}
+}
+impl From<std::io::Error> for StorageError {
+	fn from(error:std::io::Error) -> StorageError {
+		StorageError::Io(error)
+	}
+}
\ No newline at end of file
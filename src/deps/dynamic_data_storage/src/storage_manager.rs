@@ -1,31 +1,82 @@
-use super::{ DataCategory, GeneralDataType };
-use std::error::Error;
+use super::{ ChangeHandler, DataCategory, GeneralDataType, NamedData, StorageChange, StorageError, Subscription, SubscriptionId, TextFormat, TextValue };
+use std::{ ops::{ Deref, DerefMut }, sync::{ Arc, Mutex, MutexGuard, PoisonError }, time::{ Duration, Instant } };
 
 pub static STORAGE_FILE_EXTENSION:&str = "ddstrg";
 
-// A simpleton struct that provides and stores data. Determines where and how to store it by itself.
-static mut STATIC_DATA:StorageManager = StorageManager { categories: Vec::new() };
+// A simpleton struct that provides and stores data. Determines where and how to store it by itself. Access is synchronized, so it can be used from any thread.
+static STATIC_DATA:Mutex<StorageManager> = Mutex::new(StorageManager::new());
 pub struct StorageManager {
-	categories:Vec<DataCategory>
+	categories:Vec<DataCategory>,
+	sync_writes:bool,
+	transaction_depth:usize,
+	autosave_delay:Option<Duration>,
+	last_change:Option<Instant>,
+	subscriptions:Vec<Subscription>,
+	next_subscription_id:u64,
+	pending_changes:Vec<StorageChange>
 }
 impl StorageManager {
 
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new instance without any categories.
+	pub(crate) const fn new() -> StorageManager {
+		StorageManager {
+			categories: Vec::new(),
+			sync_writes: true,
+			transaction_depth: 0,
+			autosave_delay: None,
+			last_change: None,
+			subscriptions: Vec::new(),
+			next_subscription_id: 0,
+			pending_changes: Vec::new()
+		}
+	}
+
+
+
 	/* GETTER METHODS */
 
-	/// Get a reference to the static manager.
-	pub fn get() -> &'static StorageManager {
-		unsafe { &STATIC_DATA }
+	/// Lock the static manager. Other threads wait until the returned guard is dropped, so do not keep it around longer than needed. Locking it again on the same thread while holding the guard never returns.
+	pub fn get() -> StorageManagerGuard {
+		StorageManagerGuard {
+			guard: Some(STATIC_DATA.lock().unwrap_or_else(PoisonError::into_inner))
+		}
 	}
 
-	/// Get a mutable reference to the static manager.
-	pub fn get_mut() -> &'static mut StorageManager {
-		unsafe { &mut STATIC_DATA }
+	/// Lock the static manager to make changes. Identical to `get`, as the lock is exclusive either way.
+	pub fn get_mut() -> StorageManagerGuard {
+		StorageManager::get()
 	}
 
-	/// Hard-reset data.
+	/// Hard-reset data, including all subscriptions.
 	#[allow(unused)]
 	pub fn hard_reset() {
-		unsafe { STATIC_DATA = StorageManager { categories: Vec::new() }; }
+		*STATIC_DATA.lock().unwrap_or_else(PoisonError::into_inner) = StorageManager::new();
+	}
+
+
+
+	/* SETTING METHODS */
+
+	/// Check if writes are flushed to disk before a stored file replaces the previous one.
+	pub fn sync_writes(&self) -> bool {
+		self.sync_writes
+	}
+
+	/// Set whether writes should be flushed to disk before a stored file replaces the previous one. Enabled by default, disabling it makes storing faster at the risk of losing the latest changes on a system crash.
+	pub fn set_sync_writes(&mut self, sync_writes:bool) {
+		self.sync_writes = sync_writes;
+	}
+
+	/// Get the delay after the last change before changes are stored automatically. None if changes are stored directly.
+	pub fn autosave_delay(&self) -> &Option<Duration> {
+		&self.autosave_delay
+	}
+
+	/// Set the delay after the last change before changes are stored automatically. Requires `autosave_if_due` to be called regularly. None stores each change directly.
+	pub fn set_autosave_delay(&mut self, autosave_delay:Option<Duration>) {
+		self.autosave_delay = autosave_delay;
 	}
 
 
@@ -43,13 +94,13 @@ impl StorageManager {
 	}
 
 	/// Set all categories.
-	pub fn set_categories(&mut self, category_data:Vec<(&str, &str)>) -> Result<(), Box<dyn Error>> {
+	pub fn set_categories(&mut self, category_data:Vec<(&str, &str)>) -> Result<(), StorageError> {
 
 		// Add non-existing categories and update the sources of existing categories.
 		for (name, source) in &category_data {
 			match self.find_category_mut(name) {
 				Some(category) => category.set_source(source),
-				None => self.add_category(name, source)
+				None => self.add_category(name, source)?
 			}
 		}
 
@@ -64,22 +115,29 @@ impl StorageManager {
 		Ok(())
 	}
 
-	/// Add a new category to the instance.
-	pub fn add_category(&mut self, name:&str, source:&str) {
+	/// Add a new category to the instance. Fails when the source holds data that cannot be read.
+	pub fn add_category(&mut self, name:&str, source:&str) -> Result<(), StorageError> {
 		if self.find_category(name).is_none() {
-			self.categories.push(DataCategory::new(name, source));
+			self.categories.push(DataCategory::new(name, source)?);
 		}
+		Ok(())
 	}
 
 	/// Add multiple categories.
-	pub fn add_categories(&mut self, category_data:Vec<(&str, &str)>) {
+	pub fn add_categories(&mut self, category_data:Vec<(&str, &str)>) -> Result<(), StorageError> {
 		for (name, source) in category_data {
-			self.add_category(name, source);
+			self.add_category(name, source)?;
 		}
+		Ok(())
+	}
+
+	/// Take the names of the categories that were restored from their backup while loading, with the reason their source could not be read. Each category is only reported once.
+	pub fn take_restored_categories(&mut self) -> Vec<(String, String)> {
+		self.categories.iter_mut().filter_map(|category| category.take_restored_from_backup().map(|reason| (category.name().to_string(), reason))).collect::<Vec<(String, String)>>()
 	}
 
 	/// Add a category from a file.
-	pub fn add_category_from_file(&mut self, file:&str) -> Result<(), Box<dyn Error>> {
+	pub fn add_category_from_file(&mut self, file:&str) -> Result<(), StorageError> {
 		self.categories.push(DataCategory::load_from_file(file)?);
 		Ok(())
 	}
@@ -111,13 +169,13 @@ impl StorageManager {
 	}
 
 	/// Remove a category.
-	pub fn remove_category(&mut self, category_name:&str) -> Result<(), Box<dyn Error>> {
+	pub fn remove_category(&mut self, category_name:&str) -> Result<(), StorageError> {
 		if let Some(index) = self.categories.iter().position(|category| category.name() == category_name) {
 			self.categories[index].remove_file()?;
 			self.categories.remove(index);
 			Ok(())
 		} else {
-			Err(format!("Could not find category {category_name} to remove").into())
+			Err(StorageError::UnknownCategory(category_name.to_string()))
 		}
 	}
 
@@ -135,23 +193,23 @@ impl StorageManager {
 	}
 
 	/// Get a value from any of the categories.
-	pub fn find_value<T:GeneralDataType>(&self, variable_name:&str) -> Result<T, Box<dyn Error>> {
+	pub fn find_value<T:GeneralDataType>(&self, variable_name:&str) -> Result<T, StorageError> {
 		for category in &self.categories {
 			if let Ok(value) = category.get_value::<T>(variable_name) {
 				return Ok(value);
 			}
 		}
-		Err(format!("Could not find value '{variable_name}' in any category in storage").into())
+		Err(StorageError::NotFound { category: None, variable: variable_name.to_string() })
 	}
 
 	/// Get a value from a specific category.
-	pub fn get_value<T:GeneralDataType>(&self, category_name:&str, variable_name:&str) -> Result<T, Box<dyn Error>> {
+	pub fn get_value<T:GeneralDataType>(&self, category_name:&str, variable_name:&str) -> Result<T, StorageError> {
 		match self.find_category(category_name)	{
 			Some(category) => match category.get_value::<T>(variable_name) {
 				Ok(value) => Ok(value),
 				Err(error) => Err(error)
 			},
-			None => Err(format!("Could not find category '{category_name}' in storage").into())
+			None => Err(StorageError::UnknownCategory(category_name.to_string()))
 		}
 	}
 
@@ -168,11 +226,214 @@ impl StorageManager {
 		}
 	}
 
-	/// Set a value from a specific category.
-	pub fn set_value<T:GeneralDataType>(&mut self, category_name:&str, variable_name:&str, value:&T) -> Result<(), Box<dyn Error>> {
-		match self.find_category_mut(category_name)	{
-			Some(category) => category.set_value(variable_name, value),
-			None => Err(format!("Could not find category '{category_name}' in storage").into())
+	/// Set a value from a specific category. The value is stored to file directly, unless a transaction is active or an autosave delay is set. Subscribers are notified when the value changed.
+	pub fn set_value<T:GeneralDataType>(&mut self, category_name:&str, variable_name:&str, value:&T) -> Result<(), StorageError> {
+		let store_directly:bool = self.transaction_depth == 0 && self.autosave_delay.is_none();
+		let sync_writes:bool = self.sync_writes;
+		let category:&mut DataCategory = self.find_category_mut(category_name).ok_or_else(|| StorageError::UnknownCategory(category_name.to_string()))?;
+		let changed:bool = category.set_value(variable_name, value);
+		if store_directly {
+			category.flush(sync_writes)?;
+		}
+
+		// Keep track of the change.
+		if changed {
+			self.pending_changes.push(StorageChange::new(category_name, variable_name));
+			if !store_directly {
+				self.last_change = Some(Instant::now());
+			}
+		}
+		Ok(())
+	}
+
+
+
+	/* TEXT METHODS */
+
+	/// Export categories to a readable text file, the format is based on the extension of the file. Values are only exported in a readable way when they were set or requested with their type before, others are exported as raw bytes.
+	pub fn export_categories(&self, category_names:&[&str], file:&str) -> Result<(), StorageError> {
+		std::fs::write(file, self.categories_to_text(category_names, TextFormat::from_path(file)?)?)?;
+		Ok(())
+	}
+
+	/// Create the text of a text file containing the given categories.
+	pub fn categories_to_text(&self, category_names:&[&str], format:TextFormat) -> Result<String, StorageError> {
+		let mut categories:Vec<(String, TextValue)> = Vec::new();
+		for category_name in category_names {
+			match self.find_category(category_name) {
+				Some(category) => categories.push((category_name.to_string(), category.to_text_value())),
+				None => return Err(StorageError::UnknownCategory(category_name.to_string()))
+			}
+		}
+		format.write(&TextValue::Map(categories))
+	}
+
+	/// Import categories from a text file created by `export_categories`, replacing all variables of the existing categories with the same name. Nothing is imported when any of the categories does not exist or cannot be parsed. Returns the names of the imported categories.
+	pub fn import_categories(&mut self, file:&str) -> Result<Vec<String>, StorageError> {
+		let text:String = std::fs::read_to_string(file)?;
+		self.import_categories_from_text(&text, TextFormat::from_path(file)?)
+	}
+
+	/// Import categories from the text of a text file created by `export_categories`.
+	pub fn import_categories_from_text(&mut self, text:&str, format:TextFormat) -> Result<Vec<String>, StorageError> {
+
+		// Parse all categories before changing any.
+		let mut imported:Vec<(String, Vec<NamedData>)> = Vec::new();
+		for (category_name, category_text) in format.parse(text)?.map()? {
+			if self.find_category(category_name).is_none() {
+				return Err(StorageError::UnknownCategory(category_name.to_string()));
+			}
+			let data:Vec<NamedData> = DataCategory::data_from_text_value(category_text).map_err(|error| StorageError::invalid_value(&format!("Could not import category '{category_name}': {error}")))?;
+			imported.push((category_name.to_string(), data));
+		}
+
+		// Replace the data of the categories.
+		let category_names:Vec<String> = imported.iter().map(|(category_name, _)| category_name.to_string()).collect::<Vec<String>>();
+		for (category_name, data) in imported {
+			if let Some(category) = self.find_category_mut(&category_name) {
+				for variable_name in category.replace_data(data) {
+					self.pending_changes.push(StorageChange::new(&category_name, &variable_name));
+				}
+			}
+		}
+		if self.transaction_depth == 0 && self.autosave_delay.is_none() {
+			self.flush()?;
+		} else {
+			self.last_change = Some(Instant::now());
+		}
+		Ok(category_names)
+	}
+
+
+
+	/* SUBSCRIPTION METHODS */
+
+	/// Subscribe to changes of a variable in a category, or of all variables in the category when no variable name is given. The handler is called on the thread that made the change, once it releases the manager, so the handler can use the manager itself.
+	pub fn subscribe<T:Fn(&StorageChange) + Send + Sync + 'static>(&mut self, category_name:&str, variable_name:Option<&str>, handler:T) -> SubscriptionId {
+		let subscription:Subscription = Subscription::new(self.next_subscription_id, category_name, variable_name, Arc::new(handler));
+		let id:SubscriptionId = subscription.id();
+		self.subscriptions.push(subscription);
+		self.next_subscription_id += 1;
+		id
+	}
+
+	/// Remove a subscription. Returns false if it did not exist.
+	pub fn unsubscribe(&mut self, id:SubscriptionId) -> bool {
+		let subscription_count:usize = self.subscriptions.len();
+		self.subscriptions.retain(|subscription| subscription.id() != id);
+		self.subscriptions.len() != subscription_count
+	}
+
+	/// Take all changes since the last call, together with the handlers to notify of each change.
+	fn take_notifications(&mut self) -> Vec<(StorageChange, ChangeHandler)> {
+		let mut notifications:Vec<(StorageChange, ChangeHandler)> = Vec::new();
+		for change in self.pending_changes.drain(..) {
+			for subscription in self.subscriptions.iter().filter(|subscription| subscription.matches(&change)) {
+				notifications.push((change.clone(), subscription.handler().clone()));
+			}
+		}
+		notifications
+	}
+
+
+
+	/* TRANSACTION METHODS */
+
+	/// Start a transaction. Values set during the transaction are only kept in memory until the transaction is committed. Transactions can be nested, only committing the outer one stores the changes.
+	pub fn begin_transaction(&mut self) {
+		self.transaction_depth += 1;
+	}
+
+	/// Commit the current transaction. Stores all changes to file when this ends the outer transaction and no autosave delay is set.
+	pub fn commit_transaction(&mut self) -> Result<(), StorageError> {
+		if self.transaction_depth == 0 {
+			return Err(StorageError::invalid_value("Could not commit transaction, no transaction was started."));
+		}
+		self.transaction_depth -= 1;
+		if self.transaction_depth == 0 && self.autosave_delay.is_none() {
+			self.flush()?;
+		}
+		Ok(())
+	}
+
+	/// Run a function within a transaction. When the function fails, all changes it made are undone before the error is returned. Undoing changes requires a copy of all categories, so keep transactions on large storages to a minimum.
+	pub fn transaction<T:FnOnce(&mut StorageManager) -> Result<(), StorageError>>(&mut self, handler:T) -> Result<(), StorageError> {
+		let categories:Vec<DataCategory> = self.categories.clone();
+		let transaction_depth:usize = self.transaction_depth;
+		let pending_change_count:usize = self.pending_changes.len();
+		let last_change:Option<Instant> = self.last_change;
+		self.begin_transaction();
+		if let Err(error) = handler(self) {
+			self.categories = categories;
+			self.transaction_depth = transaction_depth;
+			self.pending_changes.truncate(pending_change_count);
+			self.last_change = last_change;
+			return Err(error);
+		}
+		self.commit_transaction()
+	}
+
+	/// Check if a transaction is active.
+	pub fn in_transaction(&self) -> bool {
+		self.transaction_depth > 0
+	}
+
+
+
+	/* DISK STORAGE METHODS */
+
+	/// Check if any category has changes that are not stored to file yet.
+	pub fn is_dirty(&self) -> bool {
+		self.categories.iter().any(|category| category.is_dirty())
+	}
+
+	/// Store all categories with changes that are not stored to file yet.
+	pub fn flush(&mut self) -> Result<(), StorageError> {
+		for category in &mut self.categories {
+			category.flush(self.sync_writes)?;
+		}
+		self.last_change = None;
+		Ok(())
+	}
+
+	/// Store all changes when the autosave delay has passed since the last change. Does nothing during a transaction. Returns whether anything was stored.
+	pub fn autosave_if_due(&mut self) -> Result<bool, StorageError> {
+		match (self.autosave_delay, self.last_change) {
+			(Some(delay), Some(last_change)) if !self.in_transaction() && last_change.elapsed() >= delay => {
+				self.flush()?;
+				Ok(true)
+			},
+			_ => Ok(false)
+		}
+	}
+}
+
+
+
+// Grants access to the static StorageManager while it exists. Notifies subscribers of changes once dropped.
+pub struct StorageManagerGuard {
+	guard:Option<MutexGuard<'static, StorageManager>>
+}
+impl Deref for StorageManagerGuard {
+	type Target = StorageManager;
+
+	fn deref(&self) -> &StorageManager {
+		self.guard.as_ref().unwrap()
+	}
+}
+impl DerefMut for StorageManagerGuard {
+	fn deref_mut(&mut self) -> &mut StorageManager {
+		self.guard.as_mut().unwrap()
+	}
+}
+impl Drop for StorageManagerGuard {
+	fn drop(&mut self) {
+
+		// Release the lock before notifying, so handlers can use the manager.
+		let notifications:Vec<(StorageChange, ChangeHandler)> = self.guard.as_mut().map(|manager| manager.take_notifications()).unwrap_or_default();
+		self.guard = None;
+		for (change, handler) in notifications {
+			handler(&change);
 		}
 	}
 }
\ No newline at end of file
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+pub type ChangeHandler = Arc<dyn Fn(&StorageChange) + Send + Sync>;
+
+
+
+// A change to a variable in storage, passed to the handlers subscribed to it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StorageChange {
+	category:String,
+	variable:String
+}
+impl StorageChange {
+
+	/// Create a new instance.
+	pub(crate) fn new(category:&str, variable:&str) -> StorageChange {
+		StorageChange {
+			category: category.to_string(),
+			variable: variable.to_string()
+		}
+	}
+
+	/// Return a reference to the name of the category the changed variable is in.
+	pub fn category(&self) -> &str {
+		&self.category
+	}
+
+	/// Return a reference to the name of the changed variable.
+	pub fn variable(&self) -> &str {
+		&self.variable
+	}
+}
+
+
+
+// Identifies a subscription so it can be removed again.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SubscriptionId(u64);
+
+
+
+pub(crate) struct Subscription {
+	id:SubscriptionId,
+	category:String,
+	variable:Option<String>,
+	handler:ChangeHandler
+}
+impl Subscription {
+
+	/// Create a new instance.
+	pub(crate) fn new(id:u64, category:&str, variable:Option<&str>, handler:ChangeHandler) -> Subscription {
+		Subscription {
+			id: SubscriptionId(id),
+			category: category.to_string(),
+			variable: variable.map(|variable| variable.to_string()),
+			handler
+		}
+	}
+
+	/// Return the id of the subscription.
+	pub(crate) fn id(&self) -> SubscriptionId {
+		self.id
+	}
+
+	/// Return a reference to the handler.
+	pub(crate) fn handler(&self) -> &ChangeHandler {
+		&self.handler
+	}
+
+	/// Check if the subscription wants to be notified of a specific change.
+	pub(crate) fn matches(&self, change:&StorageChange) -> bool {
+		self.category == change.category && self.variable.as_ref().is_none_or(|variable| variable == &change.variable)
+	}
+}
\ No newline at end of file
@@ -0,0 +1,304 @@
+use super::{ GeneralDataType, NamedData, StorageError };
+use std::str::FromStr;
+
+static NULL_TEXT_VALUE:TextValue = TextValue::Null;
+
+
+
+// A readable representation of a value, used to export storage to text formats and import it back.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TextValue {
+	Null,
+	Bool(bool),
+	Integer(i128),
+	Float(f64),
+	Text(String),
+	List(Vec<TextValue>),
+	Map(Vec<(String, TextValue)>)
+}
+impl TextValue {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a text value from an integer. Integers too large to be represented are stored as text.
+	pub fn from_integer<T:TryInto<i128> + ToString + Copy>(value:T) -> TextValue {
+		match value.try_into() {
+			Ok(integer) => TextValue::Integer(integer),
+			Err(_) => TextValue::Text(value.to_string())
+		}
+	}
+
+	/// Create a text value from a floating point number. Uses the shortest representation of the number, so an f32 does not gain extra digits when stored as f64.
+	pub fn from_float<T:ToString>(value:T) -> TextValue {
+		TextValue::Float(value.to_string().parse::<f64>().unwrap_or(f64::NAN))
+	}
+
+	/// Create a text value from raw bytes, represented as hexadecimal text.
+	pub fn from_hex(bytes:&[u8]) -> TextValue {
+		TextValue::Text(bytes.iter().map(|byte| format!("{byte:02x}")).collect::<String>())
+	}
+
+	/// Create the readable representation of a variable as it appears in exported categories, containing its schema version and value. Allows building a document for `import_categories_from_text` from values that are not in storage.
+	pub fn from_variable<T:GeneralDataType>(value:&T) -> TextValue {
+		NamedData::new("", value).to_text_value()
+	}
+
+	/// Create a text value for a variant of an enum. Variants without value are represented by their name, others by a map with their name as only key.
+	pub fn from_variant(name:&str, value:TextValue) -> TextValue {
+		match value {
+			TextValue::Null => TextValue::Text(name.to_string()),
+			value => TextValue::Map(vec![(name.to_string(), value)])
+		}
+	}
+
+
+
+	/* GETTER METHODS */
+
+	/// Get the value as boolean.
+	pub fn bool(&self) -> Result<bool, StorageError> {
+		match self {
+			TextValue::Bool(value) => Ok(*value),
+			_ => Err(self.type_error("bool"))
+		}
+	}
+
+	/// Get the value as integer of a specific type. Also accepts integers stored as text.
+	pub fn integer<T:TryFrom<i128> + FromStr>(&self) -> Result<T, StorageError> {
+		let integer:Option<T> = match self {
+			TextValue::Integer(value) => T::try_from(*value).ok(),
+			TextValue::Text(value) => value.parse::<T>().ok(),
+			_ => None
+		};
+		integer.ok_or_else(|| self.type_error(std::any::type_name::<T>()))
+	}
+
+	/// Get the value as floating point number of a specific type. Also accepts integers and numbers stored as text, like 'NaN' and 'inf'.
+	pub fn float<T:FromStr>(&self) -> Result<T, StorageError> {
+		let float:Option<T> = match self {
+			TextValue::Float(value) => value.to_string().parse::<T>().ok(),
+			TextValue::Integer(value) => value.to_string().parse::<T>().ok(),
+			TextValue::Text(value) => value.parse::<T>().ok(),
+			_ => None
+		};
+		float.ok_or_else(|| self.type_error(std::any::type_name::<T>()))
+	}
+
+	/// Get the value as text.
+	pub fn text(&self) -> Result<&str, StorageError> {
+		match self {
+			TextValue::Text(value) => Ok(value),
+			_ => Err(self.type_error("text"))
+		}
+	}
+
+	/// Get the value as list.
+	pub fn list(&self) -> Result<&Vec<TextValue>, StorageError> {
+		match self {
+			TextValue::List(items) => Ok(items),
+			_ => Err(self.type_error("list"))
+		}
+	}
+
+	/// Get the value as map. Null is accepted as empty map, as not all text formats can tell them apart.
+	pub fn map(&self) -> Result<&[(String, TextValue)], StorageError> {
+		match self {
+			TextValue::Map(entries) => Ok(entries),
+			TextValue::Null => Ok(&[]),
+			_ => Err(self.type_error("map"))
+		}
+	}
+
+	/// Get the raw bytes from hexadecimal text.
+	pub fn hex(&self) -> Result<Vec<u8>, StorageError> {
+		let text:&str = self.text()?;
+		if !text.len().is_multiple_of(2) || !text.is_ascii() {
+			return Err(StorageError::invalid_value(&format!("Could not read '{text}' as hexadecimal bytes.")));
+		}
+		(0..text.len()).step_by(2).map(|index| u8::from_str_radix(&text[index..index + 2], 16).map_err(|_| StorageError::invalid_value(&format!("Could not read '{text}' as hexadecimal bytes.")))).collect()
+	}
+
+	/// Get a value from a map by its key. Returns null if the key does not exist.
+	pub fn field(&self, key:&str) -> &TextValue {
+		match self {
+			TextValue::Map(entries) => entries.iter().find(|(entry_key, _)| entry_key == key).map(|(_, value)| value).unwrap_or(&NULL_TEXT_VALUE),
+			_ => &NULL_TEXT_VALUE
+		}
+	}
+
+	/// Check if the value is a map containing the given key.
+	pub fn has_field(&self, key:&str) -> bool {
+		matches!(self, TextValue::Map(entries) if entries.iter().any(|(entry_key, _)| entry_key == key))
+	}
+
+	/// Get a value from a list by its index. Returns null if the index does not exist.
+	pub fn item(&self, index:usize) -> &TextValue {
+		match self {
+			TextValue::List(items) => items.get(index).unwrap_or(&NULL_TEXT_VALUE),
+			_ => &NULL_TEXT_VALUE
+		}
+	}
+
+	/// Get the name and value of an enum variant created by `from_variant`.
+	pub fn variant(&self) -> Result<(&str, &TextValue), StorageError> {
+		match self {
+			TextValue::Text(name) => Ok((name, &NULL_TEXT_VALUE)),
+			TextValue::Map(entries) if entries.len() == 1 => Ok((&entries[0].0, &entries[0].1)),
+			_ => Err(self.type_error("enum variant"))
+		}
+	}
+
+	/// Create the error for a value that could not be read as a specific type.
+	fn type_error(&self, expected:&str) -> StorageError {
+		let found:String = match self {
+			TextValue::Null => "null".to_string(),
+			TextValue::Bool(value) => value.to_string(),
+			TextValue::Integer(value) => value.to_string(),
+			TextValue::Float(value) => value.to_string(),
+			TextValue::Text(value) => format!("'{value}'"),
+			TextValue::List(_) => "a list".to_string(),
+			TextValue::Map(_) => "a map".to_string()
+		};
+		StorageError::invalid_value(&format!("Could not read {found} as {expected}."))
+	}
+}
+
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TextFormat {
+	Json,
+	Toml
+}
+impl TextFormat {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Find the format to use for a file based on its extension.
+	pub fn from_path(path:&str) -> Result<TextFormat, StorageError> {
+		match std::path::Path::new(path).extension().and_then(|extension| extension.to_str()) {
+			Some("json") => Ok(TextFormat::Json),
+			Some("toml") => Ok(TextFormat::Toml),
+			_ => Err(StorageError::invalid_value(&format!("Could not determine text format of '{path}', expected a .json or .toml file.")))
+		}
+	}
+
+	/// Get the file extension of the format.
+	pub fn extension(&self) -> &'static str {
+		match self {
+			TextFormat::Json => "json",
+			TextFormat::Toml => "toml"
+		}
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Write a value as text in this format. TOML requires the value to be a map, null values are left out of maps and written as empty maps in lists.
+	pub fn write(&self, value:&TextValue) -> Result<String, StorageError> {
+		match self {
+			TextFormat::Json => serde_json::to_string_pretty(&Self::to_json(value)).map(|text| text + "\n").map_err(|error| StorageError::invalid_value(&format!("Could not write value as JSON: {error}"))),
+			TextFormat::Toml => match Self::to_toml(value) {
+				toml::Value::Table(table) => toml::to_string(&table).map_err(|error| StorageError::invalid_value(&format!("Could not write value as TOML: {error}"))),
+				_ => Err(StorageError::invalid_value("Could not write value as TOML, only maps can be written at the top level."))
+			}
+		}
+	}
+
+	/// Parse text in this format. Invalid text results in an error with the offset of the problem in the text.
+	pub fn parse(&self, text:&str) -> Result<TextValue, StorageError> {
+		match self {
+			TextFormat::Json => match serde_json::from_str::<serde_json::Value>(text) {
+				Ok(value) => Ok(Self::from_json(&value)),
+				Err(error) => {
+					let offset:usize = text.split_inclusive('\n').take(error.line().saturating_sub(1)).map(|line| line.len()).sum::<usize>() + error.column().saturating_sub(1);
+					Err(StorageError::corrupt(offset, &error.to_string()))
+				}
+			},
+			TextFormat::Toml => match text.parse::<toml::Table>() {
+				Ok(table) => Ok(Self::from_toml(&toml::Value::Table(table))),
+				Err(error) => Err(StorageError::corrupt(error.span().map(|span| span.start).unwrap_or(0), error.message()))
+			}
+		}
+	}
+
+
+
+	/* JSON METHODS */
+
+	/// Convert a text value to JSON. Numbers JSON cannot represent are stored as text.
+	fn to_json(value:&TextValue) -> serde_json::Value {
+		use serde_json::Value;
+
+		match value {
+			TextValue::Null => Value::Null,
+			TextValue::Bool(value) => Value::Bool(*value),
+			TextValue::Integer(value) => match (i64::try_from(*value), u64::try_from(*value)) {
+				(Ok(integer), _) => Value::from(integer),
+				(_, Ok(integer)) => Value::from(integer),
+				_ => Value::String(value.to_string())
+			},
+			TextValue::Float(value) => serde_json::Number::from_f64(*value).map(Value::Number).unwrap_or(Value::String(value.to_string())),
+			TextValue::Text(value) => Value::String(value.to_string()),
+			TextValue::List(items) => Value::Array(items.iter().map(Self::to_json).collect()),
+			TextValue::Map(entries) => Value::Object(entries.iter().map(|(key, value)| (key.to_string(), Self::to_json(value))).collect())
+		}
+	}
+
+	/// Convert JSON to a text value.
+	fn from_json(value:&serde_json::Value) -> TextValue {
+		use serde_json::Value;
+
+		match value {
+			Value::Null => TextValue::Null,
+			Value::Bool(value) => TextValue::Bool(*value),
+			Value::Number(number) => match (number.as_i64(), number.as_u64()) {
+				(Some(integer), _) => TextValue::Integer(integer as i128),
+				(_, Some(integer)) => TextValue::Integer(integer as i128),
+				_ => TextValue::Float(number.as_f64().unwrap_or(f64::NAN))
+			},
+			Value::String(value) => TextValue::Text(value.to_string()),
+			Value::Array(items) => TextValue::List(items.iter().map(Self::from_json).collect()),
+			Value::Object(entries) => TextValue::Map(entries.iter().map(|(key, value)| (key.to_string(), Self::from_json(value))).collect())
+		}
+	}
+
+
+
+	/* TOML METHODS */
+
+	/// Convert a text value to TOML. Integers TOML cannot represent are stored as text, null values are left out of maps and written as empty maps in lists.
+	fn to_toml(value:&TextValue) -> toml::Value {
+		use toml::Value;
+
+		match value {
+			TextValue::Null => Value::Table(toml::Table::new()),
+			TextValue::Bool(value) => Value::Boolean(*value),
+			TextValue::Integer(value) => match i64::try_from(*value) {
+				Ok(integer) => Value::Integer(integer),
+				Err(_) => Value::String(value.to_string())
+			},
+			TextValue::Float(value) => Value::Float(*value),
+			TextValue::Text(value) => Value::String(value.to_string()),
+			TextValue::List(items) => Value::Array(items.iter().map(Self::to_toml).collect()),
+			TextValue::Map(entries) => Value::Table(entries.iter().filter(|(_, value)| value != &TextValue::Null).map(|(key, value)| (key.to_string(), Self::to_toml(value))).collect())
+		}
+	}
+
+	/// Convert TOML to a text value. Empty maps are read as null.
+	fn from_toml(value:&toml::Value) -> TextValue {
+		use toml::Value;
+
+		match value {
+			Value::Boolean(value) => TextValue::Bool(*value),
+			Value::Integer(value) => TextValue::Integer(*value as i128),
+			Value::Float(value) => TextValue::Float(*value),
+			Value::String(value) => TextValue::Text(value.to_string()),
+			Value::Datetime(value) => TextValue::Text(value.to_string()),
+			Value::Array(items) => TextValue::List(items.iter().map(Self::from_toml).collect()),
+			Value::Table(entries) if entries.is_empty() => TextValue::Null,
+			Value::Table(entries) => TextValue::Map(entries.iter().map(|(key, value)| (key.to_string(), Self::from_toml(value))).collect())
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,339 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ format_ident, quote };
+use syn::{ parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Fields, Ident, LitInt, Type };
+
+
+
+/// Implement GeneralDataType for a struct or enum by storing all fields in order of declaration. All stored field types should implement GeneralDataType themselves.
+/// Enums store the index of their variant as u8 followed by the fields of the variant, so variants should only be added at the end.
+/// As text, structs are represented by a map of their fields, tuple structs by a list and enum variants by their name or a map with their name as only key.
+/// Use `#[general_data_type(schema_version = 1)]` on the type to set its schema version and `#[general_data_type(skip)]` on a field to not store it and use its default value when parsing.
+#[proc_macro_derive(GeneralDataType, attributes(general_data_type))]
+pub fn derive_general_data_type(input:TokenStream) -> TokenStream {
+	let input:DeriveInput = parse_macro_input!(input as DeriveInput);
+	match implement_general_data_type(input) {
+		Ok(implementation) => implementation.into(),
+		Err(error) => error.to_compile_error().into()
+	}
+}
+
+
+
+/* IMPLEMENTATION METHODS */
+
+/// Create the full implementation of GeneralDataType for the given type.
+fn implement_general_data_type(mut input:DeriveInput) -> Result<TokenStream2, Error> {
+	let name:Ident = input.ident.clone();
+	let schema_version:Option<LitInt> = parse_type_attributes(&input.attrs)?;
+
+	// Require all generic types to implement GeneralDataType as well.
+	let type_params:Vec<Ident> = input.generics.type_params().map(|param| param.ident.clone()).collect::<Vec<Ident>>();
+	for type_param in type_params {
+		input.generics.make_where_clause().predicates.push(parse_quote!(#type_param: ::dynamic_data_storage::GeneralDataType));
+	}
+	let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+	// Create the parsing, writing and sizing code.
+	let (parser, writer, byte_size, text_parser, text_writer) = match &input.data {
+		Data::Struct(data) => {
+			let (pattern, bindings) = fields_pattern(&data.fields, quote!(Self))?;
+			let writers:Vec<TokenStream2> = bindings.iter().map(|(binding, field_type)| type_writer(field_type, &quote!(#binding))).collect::<Vec<TokenStream2>>();
+			let sizes:Vec<TokenStream2> = bindings.iter().map(|(_, field_type)| type_byte_size(field_type)).collect::<Vec<TokenStream2>>();
+			let size_count:usize = sizes.len();
+			let text_writer:TokenStream2 = fields_text_writer(&data.fields, &bindings)?;
+			(
+				fields_parser(&data.fields, quote!(Self))?,
+				quote! {
+					let #pattern = self;
+					#(#writers)*
+				},
+				quote! {
+					let field_sizes:[usize; #size_count] = [#(#sizes),*];
+					if field_sizes.contains(&0) { 0 } else { field_sizes.iter().sum() }
+				},
+				fields_text_parser(&data.fields, quote!(Self))?,
+				quote! {
+					let #pattern = self;
+					#text_writer
+				}
+			)
+		},
+		Data::Enum(data) => {
+			if data.variants.len() > u8::MAX as usize + 1 {
+				return Err(Error::new_spanned(&name, "GeneralDataType can only be derived for enums with up to 256 variants."));
+			}
+			let mut parsers:Vec<TokenStream2> = Vec::new();
+			let mut writers:Vec<TokenStream2> = Vec::new();
+			let mut text_parsers:Vec<TokenStream2> = Vec::new();
+			let mut text_writers:Vec<TokenStream2> = Vec::new();
+			for (variant_index, variant) in data.variants.iter().enumerate() {
+				let variant_index:u8 = variant_index as u8;
+				let variant_name:&Ident = &variant.ident;
+				let variant_text_name:String = variant_name.to_string();
+				let parser:TokenStream2 = fields_parser(&variant.fields, quote!(Self::#variant_name))?;
+				let (pattern, bindings) = fields_pattern(&variant.fields, quote!(Self::#variant_name))?;
+				let field_writers:Vec<TokenStream2> = bindings.iter().map(|(binding, field_type)| type_writer(field_type, &quote!(#binding))).collect::<Vec<TokenStream2>>();
+				let text_parser:TokenStream2 = fields_text_parser(&variant.fields, quote!(Self::#variant_name))?;
+				let text_writer:TokenStream2 = fields_text_writer(&variant.fields, &bindings)?;
+				parsers.push(quote!(#variant_index => #parser));
+				writers.push(quote! {
+					#pattern => {
+						bytes.push(#variant_index);
+						#(#field_writers)*
+					}
+				});
+				text_parsers.push(quote! {
+					#variant_text_name => {
+						let value:&::dynamic_data_storage::TextValue = variant_value;
+						#text_parser
+					}
+				});
+				text_writers.push(quote!(#pattern => ::dynamic_data_storage::TextValue::from_variant(#variant_text_name, #text_writer)));
+			}
+			(
+				quote! {
+					match <u8 as ::dynamic_data_storage::GeneralDataType>::from_bytes_consume(bytes)? {
+						#(#parsers,)*
+						variant_index => return Err(::dynamic_data_storage::StorageError::InvalidValue(format!("Could not parse {}, unknown variant index {variant_index}.", stringify!(#name))))
+					}
+				},
+				quote! {
+					match self {
+						#(#writers)*
+					}
+				},
+				quote!(0),
+				quote!({
+					let (variant_name, variant_value) = value.variant()?;
+					match variant_name {
+						#(#text_parsers,)*
+						variant_name => return Err(::dynamic_data_storage::StorageError::InvalidValue(format!("Could not parse {}, unknown variant '{variant_name}'.", stringify!(#name))))
+					}
+				}),
+				quote! {
+					match self {
+						#(#text_writers,)*
+					}
+				}
+			)
+		},
+		Data::Union(_) => return Err(Error::new_spanned(&name, "GeneralDataType can not be derived for unions."))
+	};
+
+	// Only override the schema version when set.
+	let schema_version:TokenStream2 = match schema_version {
+		Some(version) => quote! {
+			fn schema_version() -> u16 {
+				#version
+			}
+		},
+		None => TokenStream2::new()
+	};
+
+	Ok(quote! {
+		impl #impl_generics ::dynamic_data_storage::GeneralDataType for #name #type_generics #where_clause {
+			fn from_bytes(bytes:&[u8]) -> Result<Self, ::dynamic_data_storage::StorageError> {
+				::dynamic_data_storage::from_bytes_exact(bytes)
+			}
+			fn from_bytes_inner(bytes:&[u8]) -> Result<Self, ::dynamic_data_storage::StorageError> {
+				::dynamic_data_storage::from_bytes_exact(bytes)
+			}
+			fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, ::dynamic_data_storage::StorageError> {
+				Ok(#parser)
+			}
+			fn to_bytes(&self) -> Vec<u8> {
+				let mut bytes:Vec<u8> = Vec::new();
+				#writer
+				bytes
+			}
+			fn byte_size() -> usize {
+				#byte_size
+			}
+			#schema_version
+			fn to_text_value(&self) -> ::dynamic_data_storage::TextValue {
+				#text_writer
+			}
+			fn from_text_value(value:&::dynamic_data_storage::TextValue) -> Result<Self, ::dynamic_data_storage::StorageError> {
+				Ok(#text_parser)
+			}
+		}
+	})
+}
+
+
+
+/* FIELD METHODS */
+
+/// Create the expression that parses the given fields into the given constructor. Skipped fields get their default value.
+fn fields_parser(fields:&Fields, constructor:TokenStream2) -> Result<TokenStream2, Error> {
+	let mut field_parsers:Vec<TokenStream2> = Vec::new();
+	for field in fields {
+		let parser:TokenStream2 = if parse_field_skip(&field.attrs)? { quote!(Default::default()) } else { type_parser(&field.ty) };
+		field_parsers.push(match &field.ident {
+			Some(field_name) => quote!(#field_name: #parser),
+			None => parser
+		});
+	}
+	Ok(match fields {
+		Fields::Named(_) => quote!(#constructor { #(#field_parsers),* }),
+		Fields::Unnamed(_) => quote!(#constructor(#(#field_parsers),*)),
+		Fields::Unit => constructor
+	})
+}
+
+/// Create a pattern that binds all stored fields of the given constructor. Returns the pattern and the name and type of each binding.
+fn fields_pattern(fields:&Fields, constructor:TokenStream2) -> Result<(TokenStream2, Vec<(Ident, Type)>), Error> {
+	let mut field_patterns:Vec<TokenStream2> = Vec::new();
+	let mut bindings:Vec<(Ident, Type)> = Vec::new();
+	for (field_index, field) in fields.iter().enumerate() {
+		let skip:bool = parse_field_skip(&field.attrs)?;
+		let binding:Ident = format_ident!("field_{field_index}");
+		field_patterns.push(match (&field.ident, skip) {
+			(Some(field_name), true) => quote!(#field_name: _),
+			(Some(field_name), false) => quote!(#field_name: #binding),
+			(None, true) => quote!(_),
+			(None, false) => quote!(#binding)
+		});
+		if !skip {
+			bindings.push((binding, field.ty.clone()));
+		}
+	}
+	let pattern:TokenStream2 = match fields {
+		Fields::Named(_) => quote!(#constructor { #(#field_patterns),* }),
+		Fields::Unnamed(_) => quote!(#constructor(#(#field_patterns),*)),
+		Fields::Unit => constructor
+	};
+	Ok((pattern, bindings))
+}
+
+
+
+/* TEXT METHODS */
+
+/// Create the expression that parses the given fields into the given constructor from the text value 'value'. Named fields are read from a map, unnamed fields from a list and units from null.
+fn fields_text_parser(fields:&Fields, constructor:TokenStream2) -> Result<TokenStream2, Error> {
+	let mut field_parsers:Vec<TokenStream2> = Vec::new();
+	let mut stored_index:usize = 0;
+	for field in fields {
+		let parser:TokenStream2 = if parse_field_skip(&field.attrs)? {
+			quote!(Default::default())
+		} else {
+			let parser:TokenStream2 = match &field.ident {
+				Some(field_name) => {
+					let field_text_name:String = field_name.to_string();
+					let field_type:&Type = &field.ty;
+					quote!(<#field_type as ::dynamic_data_storage::GeneralDataType>::from_text_value(value.field(#field_text_name)).map_err(|error| ::dynamic_data_storage::StorageError::InvalidValue(format!("Could not read field '{}': {}", #field_text_name, error)))?)
+				},
+				None => type_text_parser(&field.ty, &quote!(value.item(#stored_index)))
+			};
+			stored_index += 1;
+			parser
+		};
+		field_parsers.push(match &field.ident {
+			Some(field_name) => quote!(#field_name: #parser),
+			None => parser
+		});
+	}
+	Ok(match fields {
+		Fields::Named(_) => quote!({
+			value.map()?;
+			#constructor { #(#field_parsers),* }
+		}),
+		Fields::Unnamed(_) => quote!({
+			value.list()?;
+			#constructor(#(#field_parsers),*)
+		}),
+		Fields::Unit => quote!({
+			value.map()?;
+			#constructor
+		})
+	})
+}
+
+/// Create the expression that creates the text value of the given fields, using the bindings created by `fields_pattern`.
+fn fields_text_writer(fields:&Fields, bindings:&[(Ident, Type)]) -> Result<TokenStream2, Error> {
+	let values:Vec<TokenStream2> = bindings.iter().map(|(binding, field_type)| type_text_writer(field_type, &quote!(#binding))).collect::<Vec<TokenStream2>>();
+	Ok(match fields {
+		Fields::Named(_) => {
+			let mut field_text_names:Vec<String> = Vec::new();
+			for field in fields {
+				if !parse_field_skip(&field.attrs)? {
+					field_text_names.push(field.ident.as_ref().map(|field_name| field_name.to_string()).unwrap_or_default());
+				}
+			}
+			quote!(::dynamic_data_storage::TextValue::Map(vec![#((String::from(#field_text_names), #values)),*]))
+		},
+		Fields::Unnamed(_) => quote!(::dynamic_data_storage::TextValue::List(vec![#(#values),*])),
+		Fields::Unit => quote!(::dynamic_data_storage::TextValue::Null)
+	})
+}
+
+
+
+/* TYPE METHODS */
+
+/// Create the expression that parses a value of the given type from the 'bytes' list.
+fn type_parser(field_type:&Type) -> TokenStream2 {
+	quote!(<#field_type as ::dynamic_data_storage::GeneralDataType>::from_bytes_consume(bytes)?)
+}
+
+/// Create the statements that write a reference to a value of the given type to the 'bytes' list.
+fn type_writer(field_type:&Type, value:&TokenStream2) -> TokenStream2 {
+	quote! {
+		bytes.extend_from_slice(&<#field_type as ::dynamic_data_storage::GeneralDataType>::to_bytes(#value));
+	}
+}
+
+/// Create the expression that parses a value of the given type from a text value.
+fn type_text_parser(field_type:&Type, value:&TokenStream2) -> TokenStream2 {
+	quote!(<#field_type as ::dynamic_data_storage::GeneralDataType>::from_text_value(#value)?)
+}
+
+/// Create the expression that creates the text value of a reference to a value of the given type.
+fn type_text_writer(field_type:&Type, value:&TokenStream2) -> TokenStream2 {
+	quote!(<#field_type as ::dynamic_data_storage::GeneralDataType>::to_text_value(#value))
+}
+
+/// Create the expression that calculates the byte size of the given type, resulting in 0 for types without a set size.
+fn type_byte_size(field_type:&Type) -> TokenStream2 {
+	quote!(<#field_type as ::dynamic_data_storage::GeneralDataType>::byte_size())
+}
+
+
+
+/* ATTRIBUTE METHODS */
+
+/// Parse the general_data_type attributes of the type itself. Returns the schema version if set.
+fn parse_type_attributes(attributes:&[Attribute]) -> Result<Option<LitInt>, Error> {
+	let mut schema_version:Option<LitInt> = None;
+	for attribute in attributes.iter().filter(|attribute| attribute.path().is_ident("general_data_type")) {
+		attribute.parse_nested_meta(|meta| {
+			if meta.path.is_ident("schema_version") {
+				let version:LitInt = meta.value()?.parse()?;
+				version.base10_parse::<u16>()?;
+				schema_version = Some(version);
+				Ok(())
+			} else {
+				Err(meta.error("Unknown general_data_type attribute, expected 'schema_version'."))
+			}
+		})?;
+	}
+	Ok(schema_version)
+}
+
+/// Parse the general_data_type attributes of a field. Returns whether the field should be skipped.
+fn parse_field_skip(attributes:&[Attribute]) -> Result<bool, Error> {
+	let mut skip:bool = false;
+	for attribute in attributes.iter().filter(|attribute| attribute.path().is_ident("general_data_type")) {
+		attribute.parse_nested_meta(|meta| {
+			if meta.path.is_ident("skip") {
+				skip = true;
+				Ok(())
+			} else {
+				Err(meta.error("Unknown general_data_type attribute, expected 'skip'."))
+			}
+		})?;
+	}
+	Ok(skip)
+}
\ No newline at end of file
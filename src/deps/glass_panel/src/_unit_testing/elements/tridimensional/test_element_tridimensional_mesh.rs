@@ -2,6 +2,7 @@
 mod test {
 	use crate::tridimensional::model::{ Vertex, Face, Mesh };
 	use crate::_unit_testing::support::TEST_CUBE_LOCATION;
+	use std::f32::consts::PI;
 
 	#[test]
 	fn tridimensional_mesh_create_raw() {
@@ -157,4 +158,51 @@ mod test {
 			assert!((secondary_mesh.vertices()[0][axis] - expected_vertex[axis]).abs() < rounding_max_off);
 		}
 	}
+
+	#[test]
+	fn tridimensional_mesh_primitive_bounds() {
+		let rounding_max_off:f32 = 0.001;
+		let primitives:Vec<(Mesh, [f32; 3])> = vec![
+			(Mesh::cuboid(&[10.0, 20.0, 30.0]), [5.0, 10.0, 15.0]),
+			(Mesh::cylinder(4.0, 12.0, 16), [4.0, 4.0, 6.0]),
+			(Mesh::capsule(4.0, 12.0, 16), [4.0, 4.0, 10.0]),
+			(Mesh::sphere(7.0, 16), [7.0, 7.0, 7.0])
+		];
+
+		// All primitives are centered on the origin, so both sides of each axis should reach the same distance.
+		for (mesh, expected_extent) in &primitives {
+			for axis in 0..3 {
+				let minimum:f32 = mesh.vertices().iter().map(|vertex| vertex[axis]).reduce(f32::min).unwrap();
+				let maximum:f32 = mesh.vertices().iter().map(|vertex| vertex[axis]).reduce(f32::max).unwrap();
+				assert!((minimum + expected_extent[axis]).abs() < rounding_max_off, "Minimum {minimum} on axis {axis} does not match expected extent {}", expected_extent[axis]);
+				assert!((maximum - expected_extent[axis]).abs() < rounding_max_off, "Maximum {maximum} on axis {axis} does not match expected extent {}", expected_extent[axis]);
+			}
+		}
+	}
+
+	#[test]
+	fn tridimensional_mesh_primitive_closed_and_outward() {
+		let segments:usize = 32;
+		let primitives:Vec<(Mesh, f32)> = vec![
+			(Mesh::cuboid(&[10.0, 20.0, 30.0]), 10.0 * 20.0 * 30.0),
+			(Mesh::cylinder(4.0, 12.0, segments), PI * 4.0_f32.powi(2) * 12.0),
+			(Mesh::capsule(4.0, 12.0, segments), PI * 4.0_f32.powi(2) * 12.0 + PI * 4.0_f32.powi(3) * 4.0 / 3.0),
+			(Mesh::sphere(7.0, segments), PI * 7.0_f32.powi(3) * 4.0 / 3.0)
+		];
+
+		for (mesh, expected_volume) in &primitives {
+
+			// In a closed mesh every edge is used once in each direction by neighbouring faces.
+			let edges:Vec<[usize; 2]> = mesh.faces().iter().flat_map(|face| { let indexes:&[usize; 3] = face.vertex_indexes(); [[indexes[0], indexes[1]], [indexes[1], indexes[2]], [indexes[2], indexes[0]]] }).collect::<Vec<[usize; 2]>>();
+			for edge in &edges {
+				assert_eq!(edges.iter().filter(|other| *other == edge).count(), 1, "Edge {edge:?} is used more than once in the same direction");
+				assert_eq!(edges.iter().filter(|other| other[0] == edge[1] && other[1] == edge[0]).count(), 1, "Edge {edge:?} has no opposite edge");
+			}
+
+			// Faces pointing outwards result in a positive volume, close to the volume of the exact shape.
+			let volume:f32 = mesh.faces().iter().map(|face| face.vertex_indexes().map(|index| mesh.vertices()[index])).map(|[a, b, c]| (a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0]) + a[2] * (b[0] * c[1] - b[1] * c[0])) / 6.0).sum::<f32>();
+			assert!(volume > 0.0, "Primitive has inward facing faces");
+			assert!((volume - expected_volume).abs() < expected_volume * 0.05, "Volume {volume} differs too much from expected volume {expected_volume}");
+		}
+	}
 }
\ No newline at end of file
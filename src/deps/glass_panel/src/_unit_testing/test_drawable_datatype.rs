@@ -25,5 +25,8 @@ mod test {
 
 		// Miscellaneous.
 		assert_eq!(String::from("Baguette"), String::from_bytes(&String::from("Baguette").to_bytes()[..]).expect("Could not convert bytes to String"));
+		assert_eq!(String::from("Crème brûlée"), String::from_bytes(&String::from("Crème brûlée").to_bytes()[..]).expect("Could not convert bytes to String"));
+		assert_eq!(String::from("ロボット 🦾"), String::from_bytes(&String::from("ロボット 🦾").to_bytes()[..]).expect("Could not convert bytes to String"));
+		assert!(String::from_bytes(&[0xC3, 0x28]).is_err());
 	}
 }
\ No newline at end of file
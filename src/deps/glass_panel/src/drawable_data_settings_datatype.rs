@@ -108,10 +108,10 @@ impl DrawableDataSettingDataType for bool {
 	fn to_bytes(&self) -> Vec<u8> { vec![if *self { 1 } else { 0 }] }
 }
 impl DrawableDataSettingDataType for String {
-	fn from_bytes(bytes:&[u8]) -> Result<Self, Box<dyn std::error::Error>> { Ok(bytes.iter().map(|byte| *byte as char).collect::<String>()) }
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn std::error::Error>> { Ok(String::from_utf8_lossy(bytes).to_string()) }
+	fn from_bytes(bytes:&[u8]) -> Result<Self, Box<dyn std::error::Error>> { Self::from_bytes_inner(bytes) }
+	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn std::error::Error>> { Ok(String::from_utf8(bytes.to_vec())?) }
 	fn byte_size() -> usize { 0 }
-	fn to_bytes(&self) -> Vec<u8> { self.chars().map(|character| character as u8).collect::<Vec<u8>>() }
+	fn to_bytes(&self) -> Vec<u8> { self.as_bytes().to_vec() }
 }
 impl DrawableDataSettingDataType for Vec<u8> {
 	fn from_bytes(bytes:&[u8]) -> Result<Self, Box<dyn std::error::Error>> { Ok(bytes.to_vec()) }
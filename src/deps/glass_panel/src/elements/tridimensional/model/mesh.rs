@@ -4,7 +4,7 @@
 
 
 use super::{ materials::{ MultiMaterial, SimpleColorMaterial }, Face, Material, Vertex, VertexMath };
-use std::error::Error;
+use std::{ error::Error, f32::consts::PI };
 
 pub struct Mesh {
 	vertices:Vec<Vertex>,
@@ -99,6 +99,81 @@ impl Mesh {
 
 
 
+	/* PRIMITIVE CONSTRUCTOR METHODS */
+
+	/// Create a box of the given size, centered on the origin.
+	pub fn cuboid(size:&[f32; 3]) -> Mesh {
+		let vertices:Vec<Vertex> = (0..8).map(|corner:usize| [0, 1, 2].map(|axis| if corner >> axis & 1 == 0 { -size[axis] * 0.5 } else { size[axis] * 0.5 })).collect::<Vec<Vertex>>();
+		Mesh::raw(vertices, vec![
+			[0, 2, 1], [1, 2, 3], [4, 5, 6], [5, 7, 6], // Bottom and top.
+			[0, 1, 4], [1, 5, 4], [2, 6, 3], [3, 6, 7], // Front and back.
+			[0, 4, 2], [2, 4, 6], [1, 3, 5], [3, 7, 5]  // Sides.
+		])
+	}
+
+	/// Create a cylinder around the Z axis, centered on the origin. The segments are the amount of sides used to approximate the circle.
+	pub fn cylinder(radius:f32, length:f32, segments:usize) -> Mesh {
+		Self::revolved(&[[0.0, -length * 0.5], [radius, -length * 0.5], [radius, length * 0.5], [0.0, length * 0.5]], segments)
+	}
+
+	/// Create a capsule around the Z axis, centered on the origin. The length is the length of the cylinder between the two half spheres.
+	pub fn capsule(radius:f32, length:f32, segments:usize) -> Mesh {
+		let quarter_rings:usize = (segments / 4).max(1);
+		let mut profile:Vec<[f32; 2]> = Vec::new();
+		for (cap_center, ring_range) in [(-length * 0.5, 0..=quarter_rings), (length * 0.5, quarter_rings..=quarter_rings * 2)] {
+			for ring in ring_range {
+				let angle:f32 = PI * 0.5 * ring as f32 / quarter_rings as f32;
+				profile.push([angle.sin() * radius, cap_center - angle.cos() * radius]);
+			}
+		}
+		Self::revolved(&profile, segments)
+	}
+
+	/// Create a sphere centered on the origin. The segments are the amount of sides used to approximate the equator.
+	pub fn sphere(radius:f32, segments:usize) -> Mesh {
+		let rings:usize = (segments / 2).max(2);
+		let profile:Vec<[f32; 2]> = (0..=rings).map(|ring| PI * ring as f32 / rings as f32).map(|angle| [angle.sin() * radius, -angle.cos() * radius]).collect::<Vec<[f32; 2]>>();
+		Self::revolved(&profile, segments)
+	}
+
+	/// Create a mesh by revolving a profile around the Z axis. The profile is a list of radius and height pairs from the bottom up, a radius of zero creates a single point on the axis.
+	fn revolved(profile:&[[f32; 2]], segments:usize) -> Mesh {
+		let segments:usize = segments.max(3);
+		let axis_tolerance:f32 = profile.iter().map(|[radius, height]| radius.abs().max(height.abs())).fold(0.0, f32::max) * 0.00001; // Calculated points on the axis are not exactly zero.
+
+		// Create a ring of vertices for each step of the profile.
+		let mut vertices:Vec<Vertex> = Vec::new();
+		let mut rings:Vec<Vec<usize>> = Vec::new();
+		for [radius, height] in profile {
+			let ring_start:usize = vertices.len();
+			if radius.abs() <= axis_tolerance {
+				vertices.push([0.0, 0.0, *height]);
+				rings.push(vec![ring_start; segments]);
+			} else {
+				vertices.extend((0..segments).map(|segment| PI * 2.0 * segment as f32 / segments as f32).map(|angle| [angle.cos() * radius, angle.sin() * radius, *height]));
+				rings.push((ring_start..ring_start + segments).collect::<Vec<usize>>());
+			}
+		}
+
+		// Connect each ring to the next one, skipping the triangles that would collapse into a point on the axis.
+		let mut faces:Vec<[usize; 3]> = Vec::new();
+		for ring_pair in rings.windows(2) {
+			let (bottom, top) = (&ring_pair[0], &ring_pair[1]);
+			for segment in 0..segments {
+				let next_segment:usize = (segment + 1) % segments;
+				if bottom[segment] != bottom[next_segment] {
+					faces.push([bottom[segment], bottom[next_segment], top[next_segment]]);
+				}
+				if top[segment] != top[next_segment] {
+					faces.push([bottom[segment], top[next_segment], top[segment]]);
+				}
+			}
+		}
+		Mesh::raw(vertices, faces)
+	}
+
+
+
 	/* BUILDER METHODS */
 
 	/// Combine this mesh with another one.
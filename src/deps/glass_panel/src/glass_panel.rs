@@ -91,6 +91,16 @@ impl GlassPanel {
 		[self.window.get_mouse_down(Left), self.window.get_mouse_down(Right)]
 	}
 
+	/// Check if the user is holding a key down.
+	pub fn key_down(&self, key:minifb::Key) -> bool {
+		self.window.is_key_down(key)
+	}
+
+	/// Check if the user pressed a key since the last update. Holding a key down repeats the press.
+	pub fn key_pressed(&self, key:minifb::Key) -> bool {
+		self.window.is_key_pressed(key, minifb::KeyRepeat::Yes)
+	}
+
 
 
 	/* DISPLAY METHODS */
@@ -1,5 +1,6 @@
 mod _unit_testing;
 pub use elements::tridimensional;
+pub use minifb::Key;
 
 // auto-publib exclude=[_unit_testing]
 pub mod elements;
@@ -0,0 +1,54 @@
+use crate::{ kinematics::GaitTrajectory, robot_configuration::RobotConfig };
+
+
+
+/// Create an Arduino sketch that replays a gait trajectory using the Servo library. Motors are attached to consecutive pins starting at the first pin.
+pub fn arduino_sketch(trajectory:&GaitTrajectory, first_pin:u8, cycle_duration_ms:u32) -> String {
+	let motor_count:usize = trajectory.motors().len();
+	let frame_count:usize = trajectory.frames().len();
+	let frame_delay_ms:u32 = (cycle_duration_ms / frame_count.max(1) as u32).max(1);
+
+	// Create the table of servo angles. Servos are centered at 90 degrees.
+	let frame_rows:Vec<String> = trajectory.frames().iter().map(|frame| {
+		let angles:Vec<String> = frame.rotations().iter().map(|rotation| ((90.0 + rotation).round().clamp(0.0, 180.0) as u8).to_string()).collect::<Vec<String>>();
+		format!("\t{{ {} }}", angles.join(", "))
+	}).collect::<Vec<String>>();
+
+	// Describe which motor is attached to which pin.
+	let robot_config:&RobotConfig = RobotConfig::get();
+	let pin_comments:Vec<String> = trajectory.motors().iter().enumerate().map(|(motor_index, part_index)| {
+		format!("// Pin {}: {}.", first_pin as usize + motor_index, robot_config.part(*part_index).map(|part| part.name().as_str()).unwrap_or("unknown part"))
+	}).collect::<Vec<String>>();
+
+	// Create the sketch.
+	[
+		"// Generated by Kinematic Coder.".to_string(),
+		pin_comments.join("\n"),
+		String::new(),
+		"#include <Servo.h>".to_string(),
+		String::new(),
+		format!("const int MOTOR_COUNT = {motor_count};"),
+		format!("const int FRAME_COUNT = {frame_count};"),
+		format!("const int FIRST_PIN = {first_pin};"),
+		format!("const unsigned long FRAME_DELAY_MS = {frame_delay_ms};"),
+		String::new(),
+		format!("const byte GAIT[FRAME_COUNT][MOTOR_COUNT] PROGMEM = {{\n{}\n}};", frame_rows.join(",\n")),
+		String::new(),
+		"Servo servos[MOTOR_COUNT];".to_string(),
+		"int frame = 0;".to_string(),
+		String::new(),
+		"void setup() {".to_string(),
+		"\tfor (int motor = 0; motor < MOTOR_COUNT; motor++) {".to_string(),
+		"\t\tservos[motor].attach(FIRST_PIN + motor);".to_string(),
+		"\t}".to_string(),
+		"}".to_string(),
+		String::new(),
+		"void loop() {".to_string(),
+		"\tfor (int motor = 0; motor < MOTOR_COUNT; motor++) {".to_string(),
+		"\t\tservos[motor].write(pgm_read_byte(&GAIT[frame][motor]));".to_string(),
+		"\t}".to_string(),
+		"\tframe = (frame + 1) % FRAME_COUNT;".to_string(),
+		"\tdelay(FRAME_DELAY_MS);".to_string(),
+		"}".to_string()
+	].join("\n") + "\n"
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+// auto-publib 
+
+mod arduino;
+mod trajectory;
+mod urdf;
+
+pub use arduino::*;
+pub use trajectory::*;
+pub use urdf::*;
\ No newline at end of file
@@ -0,0 +1,22 @@
+use crate::{ kinematics::GaitTrajectory, robot_configuration::RobotConfig };
+
+
+
+/// Create a CSV table of a gait trajectory. Each row is a point in the step cycle, each column the rotation of a motor in degrees.
+pub fn trajectory_csv(trajectory:&GaitTrajectory) -> String {
+
+	// Create header, naming each motor after the part holding it.
+	let robot_config:&RobotConfig = RobotConfig::get();
+	let mut rows:Vec<String> = vec![
+		[vec!["progress".to_string()], trajectory.motors().iter().map(|part_index| robot_config.part(*part_index).map(|part| part.name().replace([',', '"'], "_")).unwrap_or_default()).collect::<Vec<String>>()].concat().join(",")
+	];
+
+	// Add a row per frame.
+	for frame in trajectory.frames() {
+		rows.push(
+			[vec![frame.progress().to_string()], frame.rotations().iter().map(|rotation| rotation.to_string()).collect::<Vec<String>>()].concat().join(",")
+		);
+	}
+
+	rows.join("\n") + "\n"
+}
\ No newline at end of file
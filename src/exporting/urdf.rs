@@ -0,0 +1,218 @@
+use crate::{ robot_configuration::{ RobotConfig, PartConfig, PrimitiveShape }, storage };
+use glass_panel::tridimensional::model::VertexMath;
+use std::{ error::Error, f32::consts::FRAC_PI_2, fs, path::Path };
+
+pub static URDF_MESHES_DIR:&str = "meshes";
+pub const URDF_DEFAULT_SCALE:f32 = 0.001;
+static URDF_BASE_LINK_NAME:&str = "base_link";
+pub(crate) static URDF_AXES:[[f32; 3]; 3] = [[-1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, -1.0]]; // Motors in the editor turn clockwise around X and Z, so a joint position equals the rotation of the motor.
+pub(crate) static URDF_CYLINDER_RPY:[[f32; 3]; 3] = [[0.0, FRAC_PI_2, 0.0], [-FRAC_PI_2, 0.0, 0.0], [0.0, 0.0, 0.0]]; // URDF cylinders lie along Z, these rotations align them with each axis.
+
+// The config has no motor strength or speed, so the limits of a common hobby servo are used.
+const URDF_JOINT_EFFORT:f32 = 1.0;
+const URDF_JOINT_VELOCITY:f32 = 6.0;
+
+
+
+/// Export the robot config as URDF robot description to a dir, with all meshes copied into its meshes dir. Positions are multiplied by the scale, the default converts the millimeters of the editor to the meters of URDF. Returns the path of the URDF file and the meshes that could not be found.
+pub fn export_urdf(output_dir:&str, scale:f32) -> Result<(String, Vec<String>), Box<dyn Error>> {
+	let export:UrdfExport = UrdfExport::from_current_project(scale);
+	let urdf_file:String = export.write(output_dir)?;
+	Ok((urdf_file, export.missing_meshes().to_vec()))
+}
+
+
+
+// The URDF robot description of the current project and the meshes it uses. Creating it reads the robot config, so it should happen on the UI thread, writing it to a dir does not have to.
+pub struct UrdfExport {
+	robot_name:String,
+	urdf:String,
+	mesh_copies:Vec<(String, String, String)>,
+	missing_meshes:Vec<String>
+}
+impl UrdfExport {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create the description of the current project. Positions are multiplied by the scale.
+	pub fn from_current_project(scale:f32) -> UrdfExport {
+		let robot_config:&RobotConfig = RobotConfig::get();
+		let robot_name:&str = if storage::project_name() == storage::AUTO_SAVE_PROJECT_NAME { "robot" } else { storage::project_name() };
+
+		// Copy each mesh once, even when used by multiple parts.
+		let mut mesh_copies:Vec<(String, String, String)> = Vec::new();
+		let mut missing_meshes:Vec<String> = Vec::new();
+		let mut mesh_files:Vec<Option<String>> = Vec::new();
+		for part in robot_config.parts() {
+			let mesh:&String = part.obj();
+			if let Some((_, _, mesh_file)) = mesh_copies.iter().find(|(copied_mesh, _, _)| copied_mesh == mesh) {
+				mesh_files.push(Some(mesh_file.to_string()));
+				continue;
+			}
+			let source_file:String = storage::asset_path(mesh);
+			if mesh.is_empty() || !Path::new(&source_file).is_file() {
+				if !mesh.is_empty() && !missing_meshes.contains(mesh) {
+					missing_meshes.push(mesh.to_string());
+				}
+				mesh_files.push(None);
+				continue;
+			}
+
+			// Number meshes from different dirs that share a file name.
+			let file_stem:String = Path::new(&source_file).file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or(String::from("mesh"));
+			let extension:String = Path::new(&source_file).extension().map(|extension| extension.to_string_lossy().to_string()).unwrap_or(String::from("obj"));
+			let mut mesh_file:String = format!("{URDF_MESHES_DIR}/{file_stem}.{extension}");
+			let mut index:usize = 1;
+			while mesh_copies.iter().any(|(_, _, copied_file)| copied_file == &mesh_file) {
+				mesh_file = format!("{URDF_MESHES_DIR}/{file_stem}_{index}.{extension}");
+				index += 1;
+			}
+			mesh_copies.push((mesh.to_string(), source_file, mesh_file.to_string()));
+			mesh_files.push(Some(mesh_file));
+		}
+
+		UrdfExport {
+			robot_name: robot_name.to_string(),
+			urdf: robot_urdf(robot_config, robot_name, &mesh_files, scale),
+			mesh_copies,
+			missing_meshes
+		}
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Return a reference to the meshes that could not be found. Their parts are exported without geometry.
+	pub fn missing_meshes(&self) -> &Vec<String> {
+		&self.missing_meshes
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Write the description to a dir and copy all meshes into its meshes dir. Returns the path of the URDF file.
+	pub fn write(&self, output_dir:&str) -> Result<String, Box<dyn Error>> {
+		for (mesh, source_file, mesh_file) in &self.mesh_copies {
+			fs::create_dir_all(format!("{output_dir}/{URDF_MESHES_DIR}"))?;
+			fs::copy(source_file, format!("{output_dir}/{mesh_file}")).map_err(|error| format!("Could not copy mesh '{mesh}': {error}"))?;
+		}
+		fs::create_dir_all(output_dir)?;
+		let urdf_file:String = format!("{output_dir}/{}.urdf", self.robot_name.replace(['/', '\\'], "_"));
+		fs::write(&urdf_file, &self.urdf).map_err(|error| format!("Could not write '{urdf_file}': {error}"))?;
+		Ok(urdf_file)
+	}
+}
+
+/// Create the URDF robot description of a robot config. Each part becomes a link using its primitive or the mesh file at the same index as visual and collision geometry. Parts with a motor are attached to their parent by a revolute joint at the motor, other parts by a fixed joint. Parts without parent are attached to a shared base link.
+pub fn robot_urdf(robot_config:&RobotConfig, robot_name:&str, mesh_files:&[Option<String>], scale:f32) -> String {
+	let parts:&Vec<PartConfig> = robot_config.parts();
+
+	// Links need unique names, number parts sharing a name and make sure no part is named like the base link.
+	let mut link_names:Vec<String> = Vec::new();
+	for part in parts {
+		let mut link_name:String = part.name().to_string();
+		let mut index:usize = 1;
+		while link_name == URDF_BASE_LINK_NAME || link_names.contains(&link_name) {
+			link_name = format!("{}_{index}", part.name());
+			index += 1;
+		}
+		link_names.push(link_name);
+	}
+
+	let mut lines:Vec<String> = vec![
+		"<?xml version=\"1.0\"?>".to_string(),
+		"<!-- Generated by Kinematic Coder. -->".to_string(),
+		format!("<robot name=\"{}\">", xml_escape(robot_name)),
+		format!("\t<link name=\"{}\"/>", xml_escape(URDF_BASE_LINK_NAME))
+	];
+	for (part_index, part) in parts.iter().enumerate() {
+
+		// The frame of a link with a motor lies on the motor, move the mesh back to the origin of the part.
+		let motor_offset:[f32; 3] = part.motor().as_ref().map(|motor| *motor.position()).unwrap_or_default();
+		lines.push(format!("\t<link name=\"{}\">", xml_escape(&link_names[part_index])));
+		let geometry:Option<([f32; 3], [f32; 3], String)> = match (part.primitive(), mesh_files.get(part_index)) {
+			(Some(primitive), _) => Some((*primitive.center(), primitive.shape().axis().map(|axis| URDF_CYLINDER_RPY[(axis % 3) as usize]).unwrap_or_default(), urdf_primitive_geometry(primitive.shape(), scale))),
+			(None, Some(Some(mesh_file))) => Some(([0.0; 3], [0.0; 3], format!("<mesh filename=\"{}\" scale=\"{scale} {scale} {scale}\"/>", xml_escape(mesh_file)))),
+			_ => None
+		};
+		if let Some((geometry_position, geometry_rpy, geometry)) = geometry {
+			for element in ["visual", "collision"] {
+				lines.extend([
+					format!("\t\t<{element}>"),
+					format!("\t\t\t<origin xyz=\"{}\" rpy=\"{}\"/>", urdf_vector(&geometry_position.displaced(&motor_offset.negative()), scale), urdf_values(&geometry_rpy)),
+					format!("\t\t\t<geometry>{geometry}</geometry>"),
+					format!("\t\t</{element}>")
+				]);
+			}
+		}
+		lines.push("\t</link>".to_string());
+
+		// Positions are relative to the origin of the parent part, which is not the frame of the parent link when it has a motor.
+		let parent_link:&str = part.parent().and_then(|parent_index| link_names.get(parent_index)).map(String::as_str).unwrap_or(URDF_BASE_LINK_NAME);
+		let parent_motor_offset:[f32; 3] = part.parent().and_then(|parent_index| parts.get(parent_index)).and_then(|parent| parent.motor().as_ref()).map(|motor| *motor.position()).unwrap_or_default();
+		let rotation:[f32; 3] = part.rotation().map(|degrees| degrees.to_radians());
+		let origin:[f32; 3] = part.position().displaced(&parent_motor_offset.negative()).displaced(&motor_offset.rotated(&rotation, &None));
+
+		// Create the joint.
+		let joint_type:&str = if part.motor().is_some() { "revolute" } else { "fixed" };
+		lines.extend([
+			format!("\t<joint name=\"{}_joint\" type=\"{joint_type}\">", xml_escape(&link_names[part_index])),
+			format!("\t\t<parent link=\"{}\"/>", xml_escape(parent_link)),
+			format!("\t\t<child link=\"{}\"/>", xml_escape(&link_names[part_index])),
+			format!("\t\t<origin xyz=\"{}\" rpy=\"{}\"/>", urdf_vector(&origin, scale), urdf_rpy(&rotation))
+		]);
+		if let Some(motor) = part.motor() {
+			lines.extend([
+				format!("\t\t<axis xyz=\"{}\"/>", urdf_values(&URDF_AXES[(*motor.rotation_axis() % 3) as usize])),
+				format!("\t\t<limit lower=\"{}\" upper=\"{}\" effort=\"{URDF_JOINT_EFFORT}\" velocity=\"{URDF_JOINT_VELOCITY}\"/>", motor.rotation_range()[0].to_radians(), motor.rotation_range()[1].to_radians())
+			]);
+		}
+		lines.push("\t</joint>".to_string());
+	}
+	lines.push("</robot>".to_string());
+
+	lines.join("\n") + "\n"
+}
+
+/// Write the scaled geometry of a primitive. URDF has no capsules, so they are written as cylinders of the same total length.
+fn urdf_primitive_geometry(shape:&PrimitiveShape, scale:f32) -> String {
+	match shape {
+		PrimitiveShape::Box { size } => format!("<box size=\"{}\"/>", urdf_vector(size, scale)),
+		PrimitiveShape::Cylinder { radius, length, .. } => format!("<cylinder radius=\"{}\" length=\"{}\"/>", radius * scale, length * scale),
+		PrimitiveShape::Capsule { radius, length, .. } => format!("<cylinder radius=\"{}\" length=\"{}\"/>", radius * scale, (length + radius * 2.0) * scale),
+		PrimitiveShape::Sphere { radius } => format!("<sphere radius=\"{}\"/>", radius * scale)
+	}
+}
+
+/// Write a scaled position as URDF vector.
+fn urdf_vector(position:&[f32; 3], scale:f32) -> String {
+	urdf_values(&position.map(|value| value * scale))
+}
+
+/// Write values separated by spaces. Adding zero turns negative zeros into zeros.
+fn urdf_values(values:&[f32; 3]) -> String {
+	values.map(|value| (value + 0.0).to_string()).join(" ")
+}
+
+/// Write a rotation of the editor as URDF roll, pitch and yaw.
+fn urdf_rpy(rotation:&[f32; 3]) -> String {
+	urdf_values(&urdf_rotation(rotation))
+}
+
+/// Convert a rotation in radians between the editor and URDF roll, pitch and yaw. The editor rotates the opposite way of URDF, so the angles are recalculated from the rotation the editor applies. Converting twice results in the original rotation.
+pub(crate) fn urdf_rotation(rotation:&[f32; 3]) -> [f32; 3] {
+	let columns:[[f32; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]].map(|axis| axis.rotated(rotation, &None));
+	let pitch:f32 = -columns[0][2].clamp(-1.0, 1.0).asin();
+	if columns[0][2].abs() < 0.9999 {
+		[columns[1][2].atan2(columns[2][2]), pitch, columns[0][1].atan2(columns[0][0])]
+	} else {
+		[0.0, pitch, (-columns[1][0]).atan2(columns[1][1])] // Locked gimbal, roll and yaw turn around the same axis.
+	}
+}
+
+/// Escape text to use in an XML attribute.
+fn xml_escape(text:&str) -> String {
+	text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
\ No newline at end of file
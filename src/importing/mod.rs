@@ -0,0 +1,7 @@
+// auto-publib 
+
+mod urdf;
+mod xml_element;
+
+pub use urdf::*;
+pub use xml_element::*;
\ No newline at end of file
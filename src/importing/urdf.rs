@@ -0,0 +1,326 @@
+use crate::{ exporting::{ urdf_rotation, URDF_AXES }, robot_configuration::{ RobotConfig, PartConfig, MotorConfig, KinematicsConfig, PrimitiveConfig, PrimitiveShape } };
+use glass_panel::tridimensional::model::VertexMath;
+use std::{ error::Error, fs, path::{ Path, PathBuf } };
+use super::XmlElement;
+
+static URDF_MESH_EXTENSION:&str = "obj";
+const URDF_CONTINUOUS_JOINT_RANGE:[f32; 2] = [-180.0, 180.0];
+
+
+
+/// Create a robot config from a URDF robot description. Each link becomes a part, revolute joints become motors and the last part of each chain of motors gets a kinematics config, so it becomes a leg. Positions are multiplied by the scale, to convert the meters of URDF to the millimeters of the editor. Returns the config and warnings about everything that could not be imported as it was described.
+pub fn import_urdf(path:&str, scale:f32) -> Result<(RobotConfig, Vec<String>), Box<dyn Error>> {
+	let text:String = fs::read_to_string(path).map_err(|error| format!("Could not read '{path}': {error}"))?;
+	let robot:XmlElement = XmlElement::parse(&text).map_err(|error| format!("Could not read '{path}': {error}"))?;
+	if robot.name() != "robot" {
+		return Err(format!("Could not read '{path}', file is not recognized as URDF robot description.").into());
+	}
+	let urdf_dir:PathBuf = Path::new(path).parent().map(|dir| dir.to_path_buf()).unwrap_or_default();
+	let mut warnings:Vec<String> = Vec::new();
+
+	// Read links and joints.
+	let links:Vec<UrdfLink> = robot.children_named("link").map(UrdfLink::parse).collect::<Result<Vec<UrdfLink>, Box<dyn Error>>>()?;
+	let joints:Vec<UrdfJoint> = robot.children_named("joint").map(UrdfJoint::parse).collect::<Result<Vec<UrdfJoint>, Box<dyn Error>>>()?;
+	for joint in &joints {
+		for link in [&joint.parent, &joint.child] {
+			if !links.iter().any(|existing_link| &existing_link.name == link) {
+				return Err(format!("Joint '{}' refers to link '{link}', which does not exist.", joint.name).into());
+			}
+		}
+		if joints.iter().filter(|other_joint| other_joint.child == joint.child).count() > 1 {
+			return Err(format!("Link '{}' is attached to multiple parents.", joint.child).into());
+		}
+	}
+	let parent_joint = |link:&str| joints.iter().find(|joint| joint.child == link);
+
+	// Links without geometry at the root, like a base link, only place the parts attached to them and are left out.
+	let mut link_order:Vec<(usize, Option<usize>)> = Vec::new();
+	let mut visited_links:usize = 0;
+	let mut stack:Vec<(usize, Option<usize>)> = links.iter().enumerate().filter(|(_, link)| parent_joint(&link.name).is_none()).map(|(link_index, _)| (link_index, None)).rev().collect::<Vec<(usize, Option<usize>)>>();
+	while let Some((link_index, parent_part)) = stack.pop() {
+		let link:&UrdfLink = &links[link_index];
+		visited_links += 1;
+		let children:Vec<usize> = joints.iter().filter(|joint| joint.parent == link.name).filter_map(|joint| links.iter().position(|child| child.name == joint.child)).collect::<Vec<usize>>();
+		let skipped:bool = parent_joint(&link.name).is_none() && link.geometry.is_none() && !children.is_empty();
+		let part_index:Option<usize> = if skipped { None } else { link_order.push((link_index, parent_part)); Some(link_order.len() - 1) };
+		stack.extend(children.into_iter().rev().map(|child_index| (child_index, part_index)));
+	}
+	if visited_links < links.len() {
+		return Err("The links of the robot description do not form a tree.".into());
+	}
+
+	// Create the parts.
+	let mut parts:Vec<PartConfig> = Vec::new();
+	let mut mesh_offsets:Vec<[f32; 3]> = Vec::new();
+	let mut meshes_loaded:bool = false;
+	for (link_index, parent_part) in &link_order {
+		let link:&UrdfLink = &links[*link_index];
+		let joint:Option<&UrdfJoint> = parent_joint(&link.name);
+
+		// Find the mesh or primitive.
+		let mut primitive:Option<PrimitiveConfig> = None;
+		let mut visual_rotation_used:bool = false;
+		let mesh:String = match &link.geometry {
+			Some(UrdfGeometry::Mesh(filename, mesh_scale)) => {
+				let mesh_path:String = resolve_mesh_path(filename, &urdf_dir);
+				let extension:String = Path::new(&mesh_path).extension().map(|extension| extension.to_string_lossy().to_lowercase()).unwrap_or_default();
+				if extension != URDF_MESH_EXTENSION {
+					warnings.push(format!("Mesh '{filename}' of link '{}' is not an OBJ file and cannot be loaded, the part has no mesh.", link.name));
+					String::new()
+				} else {
+					if !Path::new(&mesh_path).is_file() {
+						warnings.push(format!("Could not find mesh '{filename}' of link '{}', it can be relinked in the editor.", link.name));
+					}
+					if mesh_scale.iter().any(|axis_scale| (axis_scale - 1.0).abs() > 0.001) {
+						warnings.push(format!("Mesh of link '{}' is scaled by {mesh_scale:?}, meshes are shown at the size they are stored in.", link.name));
+					}
+					meshes_loaded = true;
+					mesh_path
+				}
+			},
+			Some(UrdfGeometry::Primitive(shape)) => {
+				let (shape, aligned) = aligned_primitive_shape(shape, &link.visual_rotation);
+				primitive = Some(PrimitiveConfig::new(shape.scaled(scale), [0.0; 3]));
+				visual_rotation_used = aligned;
+				String::new()
+			},
+			Some(UrdfGeometry::Unsupported(shape)) => {
+				warnings.push(format!("Link '{}' uses {shape} geometry, which is not supported, the part has no mesh.", link.name));
+				String::new()
+			},
+			None => String::new()
+		};
+		if !visual_rotation_used && link.visual_rotation.iter().any(|radians| radians.abs() > 0.0001) {
+			warnings.push(format!("The rotation of the mesh of link '{}' is ignored, meshes are placed as they are stored.", link.name));
+		}
+
+		// Parts start at their mesh instead of at the joint, positions of children are relative to the mesh of their parent.
+		let mesh_offset:[f32; 3] = link.visual_position.map(|value| value * scale);
+		let parent_mesh_offset:[f32; 3] = parent_part.map(|parent_index| mesh_offsets[parent_index]).unwrap_or_default();
+		let joint_position:[f32; 3] = joint.map(|joint| joint.position.map(|value| value * scale)).unwrap_or_default();
+		let rotation:[f32; 3] = urdf_rotation(&joint.map(|joint| joint.rotation).unwrap_or_default());
+		let mut part:PartConfig = PartConfig::new(&link.name, *parent_part, &mesh);
+		*part.primitive_mut() = primitive;
+		*part.position_mut() = joint_position.displaced(&mesh_offset.rotated(&rotation, &None)).displaced(&parent_mesh_offset.negative());
+		*part.rotation_mut() = rotation.map(|radians| radians.to_degrees());
+
+		// Create a motor at the joint.
+		if let Some(joint) = joint {
+			let range:Option<[f32; 2]> = match joint.joint_type.as_str() {
+				"revolute" => Some(joint.limit.map(|limit| limit.map(|radians| radians.to_degrees())).unwrap_or_else(|| {
+					warnings.push(format!("Joint '{}' has no limits, it is imported with the range of a continuous joint.", joint.name));
+					URDF_CONTINUOUS_JOINT_RANGE
+				})),
+				"continuous" => {
+					warnings.push(format!("Joint '{}' is continuous, which is not supported, it is imported as motor with a range of a single turn.", joint.name));
+					Some(URDF_CONTINUOUS_JOINT_RANGE)
+				},
+				"fixed" => None,
+				joint_type => {
+					warnings.push(format!("Joint '{}' is {joint_type}, which is not supported, it is imported as fixed joint.", joint.name));
+					None
+				}
+			};
+			if let Some(range) = range {
+				let (rotation_axis, direction) = motor_axis(&joint.axis);
+				if joint.axis.iter().filter(|value| value.abs() > 0.0001).count() > 1 {
+					warnings.push(format!("The axis of joint '{}' is not aligned with X, Y or Z, it is imported around the closest axis.", joint.name));
+				}
+				let range:[f32; 2] = if direction < 0.0 { [-range[1], -range[0]] } else { range };
+				let range:[f32; 2] = [range[0].min(range[1]), range[0].max(range[1])];
+				*part.motor_mut() = Some(MotorConfig::new(mesh_offset.negative(), rotation_axis, 0.0_f32.clamp(range[0], range[1]), range));
+			}
+		}
+		parts.push(part);
+		mesh_offsets.push(mesh_offset);
+	}
+	if meshes_loaded && (scale - 1.0).abs() > 0.001 {
+		warnings.push(format!("Positions are multiplied by {scale} but meshes are not, meshes stored in the meters of URDF are shown {scale} times too small."));
+	}
+
+	// The last part of each chain of motors is the end of a leg.
+	let mut robot_config:RobotConfig = RobotConfig::from_parts(parts);
+	for part_index in 0..robot_config.parts().len() {
+		if robot_config.children(part_index).is_empty() && robot_config.chain(part_index).iter().any(|chain_index| robot_config.parts()[*chain_index].motor().is_some()) {
+			*robot_config.parts_mut()[part_index].kinematics_config_mut() = Some(KinematicsConfig::empty());
+		}
+	}
+	Ok((robot_config, warnings))
+}
+
+/// Find the motor axis closest to an URDF axis. Returns the axis and if the motor turns the same way as the joint or the opposite way.
+pub(crate) fn motor_axis(axis:&[f32; 3]) -> (u8, f32) {
+	let rotation_axis:usize = (0..3).max_by(|a, b| axis[*a].abs().total_cmp(&axis[*b].abs())).unwrap_or(0);
+	(rotation_axis as u8, (axis[rotation_axis] * URDF_AXES[rotation_axis][rotation_axis]).signum())
+}
+
+/// Apply the rotation of a visual to a primitive shape by turning its dimensions onto other axes. Returns whether the rotation could be applied, which is only the case when it keeps the shape aligned with the axes.
+pub(crate) fn aligned_primitive_shape(shape:&PrimitiveShape, visual_rotation:&[f32; 3]) -> (PrimitiveShape, bool) {
+	let rotation:[f32; 3] = urdf_rotation(visual_rotation);
+	let aligned_axis = |axis:usize| {
+		let mut direction:[f32; 3] = [0.0; 3];
+		direction[axis] = 1.0;
+		direction.rotated(&rotation, &None).iter().position(|value| value.abs() > 0.9999)
+	};
+	let mut shape:PrimitiveShape = *shape;
+	match &mut shape {
+		PrimitiveShape::Box { size } => match [0, 1, 2].map(aligned_axis) {
+			[Some(x), Some(y), Some(z)] => {
+				let mut aligned_size:[f32; 3] = [0.0; 3];
+				for (axis, target_axis) in [x, y, z].into_iter().enumerate() {
+					aligned_size[target_axis] = size[axis];
+				}
+				*size = aligned_size;
+				(shape, true)
+			},
+			_ => (shape, false)
+		},
+		PrimitiveShape::Cylinder { axis, .. } | PrimitiveShape::Capsule { axis, .. } => match aligned_axis(2) {
+			Some(target_axis) => {
+				*axis = target_axis as u8;
+				(shape, true)
+			},
+			None => (shape, false)
+		},
+		PrimitiveShape::Sphere { .. } => (shape, true)
+	}
+}
+
+/// Find the file a mesh in a robot description refers to. Package paths are searched for in the dirs around the description, relative paths are relative to the description.
+fn resolve_mesh_path(filename:&str, urdf_dir:&Path) -> String {
+	let path:PathBuf = if let Some(package_path) = filename.strip_prefix("package://") {
+		let (package, relative_path) = package_path.split_once('/').unwrap_or((package_path, ""));
+		urdf_dir.ancestors().flat_map(|dir| [
+			if dir.file_name().is_some_and(|name| name == package) { Some(dir.join(relative_path)) } else { None },
+			Some(dir.join(package).join(relative_path))
+		]).flatten().find(|candidate| candidate.is_file()).unwrap_or(urdf_dir.join(relative_path))
+	} else {
+		urdf_dir.join(filename.strip_prefix("file://").unwrap_or(filename))
+	};
+	std::path::absolute(&path).unwrap_or(path).display().to_string().replace('\\', "/")
+}
+
+
+
+// A link of a robot description.
+struct UrdfLink {
+	name:String,
+	geometry:Option<UrdfGeometry>,
+	visual_position:[f32; 3],
+	visual_rotation:[f32; 3]
+}
+impl UrdfLink {
+
+	/// Read a link element. Only the first visual is used.
+	fn parse(element:&XmlElement) -> Result<UrdfLink, Box<dyn Error>> {
+		let name:String = required_attribute(element, "name")?.to_string();
+		let visual:Option<&XmlElement> = element.child("visual");
+		let geometry:Option<UrdfGeometry> = match visual.and_then(|visual| visual.child("geometry")).and_then(|geometry| geometry.children().first()) {
+			Some(shape) => Some(match shape.name() {
+				"mesh" => UrdfGeometry::Mesh(required_attribute(shape, "filename")?.to_string(), optional_vector(shape, "scale", [1.0; 3])?),
+				"box" => UrdfGeometry::Primitive(PrimitiveShape::Box { size: required_vector(shape, "size")? }),
+				"cylinder" => UrdfGeometry::Primitive(PrimitiveShape::Cylinder { radius: required_float(shape, "radius")?, length: required_float(shape, "length")?, axis: 2 }),
+				"capsule" => UrdfGeometry::Primitive(PrimitiveShape::Capsule { radius: required_float(shape, "radius")?, length: required_float(shape, "length")?, axis: 2 }),
+				"sphere" => UrdfGeometry::Primitive(PrimitiveShape::Sphere { radius: required_float(shape, "radius")? }),
+				name => UrdfGeometry::Unsupported(name.to_string())
+			}),
+			None => None
+		};
+		let (visual_position, visual_rotation) = parse_origin(visual.and_then(|visual| visual.child("origin")))?;
+		Ok(UrdfLink { name, geometry, visual_position, visual_rotation })
+	}
+}
+
+
+
+// The shape of a link.
+enum UrdfGeometry {
+	Mesh(String, [f32; 3]),
+	Primitive(PrimitiveShape),
+	Unsupported(String)
+}
+
+
+
+// A joint connecting two links of a robot description.
+struct UrdfJoint {
+	name:String,
+	joint_type:String,
+	parent:String,
+	child:String,
+	position:[f32; 3],
+	rotation:[f32; 3],
+	axis:[f32; 3],
+	limit:Option<[f32; 2]>
+}
+impl UrdfJoint {
+
+	/// Read a joint element.
+	fn parse(element:&XmlElement) -> Result<UrdfJoint, Box<dyn Error>> {
+		let name:String = required_attribute(element, "name")?.to_string();
+		let link_of = |child_name:&str| element.child(child_name).and_then(|child| child.attribute("link")).map(|link| link.to_string()).ok_or(format!("Joint '{name}' has no {child_name} link."));
+		let (position, rotation) = parse_origin(element.child("origin"))?;
+		let limit:Option<[f32; 2]> = match element.child("limit") {
+			Some(limit) => Some([optional_float(limit, "lower", 0.0)?, optional_float(limit, "upper", 0.0)?]),
+			None => None
+		};
+		Ok(UrdfJoint {
+			joint_type: required_attribute(element, "type")?.to_string(),
+			parent: link_of("parent")?,
+			child: link_of("child")?,
+			position,
+			rotation,
+			axis: match element.child("axis") { Some(axis) => optional_vector(axis, "xyz", [1.0, 0.0, 0.0])?, None => [1.0, 0.0, 0.0] },
+			limit,
+			name
+		})
+	}
+}
+
+
+
+/* HELPER METHODS */
+
+/// Get an attribute that has to exist.
+fn required_attribute<'a>(element:&'a XmlElement, name:&str) -> Result<&'a str, Box<dyn Error>> {
+	element.attribute(name).ok_or_else(|| format!("Element '{}' is missing attribute '{name}'.", element.name()).into())
+}
+
+/// Read an attribute of three numbers that has to exist.
+fn required_vector(element:&XmlElement, name:&str) -> Result<[f32; 3], Box<dyn Error>> {
+	required_attribute(element, name)?;
+	optional_vector(element, name, [0.0; 3])
+}
+
+/// Read an attribute holding a number that has to exist.
+fn required_float(element:&XmlElement, name:&str) -> Result<f32, Box<dyn Error>> {
+	required_attribute(element, name)?;
+	optional_float(element, name, 0.0)
+}
+
+/// Read an attribute of three numbers, or use the default when it does not exist.
+fn optional_vector(element:&XmlElement, name:&str, default:[f32; 3]) -> Result<[f32; 3], Box<dyn Error>> {
+	match element.attribute(name) {
+		Some(value) => {
+			let values:Vec<f32> = value.split_whitespace().map(|number| number.parse::<f32>()).collect::<Result<Vec<f32>, _>>().map_err(|_| format!("Could not read '{value}' as {name} of element '{}'.", element.name()))?;
+			values.try_into().map_err(|_| format!("Expected three numbers as {name} of element '{}' but found '{value}'.", element.name()).into())
+		},
+		None => Ok(default)
+	}
+}
+
+/// Read an attribute holding a number, or use the default when it does not exist.
+fn optional_float(element:&XmlElement, name:&str, default:f32) -> Result<f32, Box<dyn Error>> {
+	match element.attribute(name) {
+		Some(value) => value.trim().parse::<f32>().map_err(|_| format!("Could not read '{value}' as {name} of element '{}'.", element.name()).into()),
+		None => Ok(default)
+	}
+}
+
+/// Read the position and roll, pitch and yaw of an origin element.
+fn parse_origin(origin:Option<&XmlElement>) -> Result<([f32; 3], [f32; 3]), Box<dyn Error>> {
+	match origin {
+		Some(origin) => Ok((optional_vector(origin, "xyz", [0.0; 3])?, optional_vector(origin, "rpy", [0.0; 3])?)),
+		None => Ok(([0.0; 3], [0.0; 3]))
+	}
+}
\ No newline at end of file
@@ -0,0 +1,205 @@
+use std::error::Error;
+
+
+
+// A single element of an XML document with its attributes and child elements. Text between elements is not kept, as robot descriptions only use elements and attributes.
+pub struct XmlElement {
+	name:String,
+	attributes:Vec<(String, String)>,
+	children:Vec<XmlElement>
+}
+impl XmlElement {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Parse the root element of an XML document.
+	pub fn parse(text:&str) -> Result<XmlElement, Box<dyn Error>> {
+		let mut parser:XmlParser = XmlParser { text, cursor: 0 };
+		parser.skip_non_elements()?;
+		if !parser.remaining().starts_with('<') {
+			return Err(parser.error("expected root element"));
+		}
+		let root:XmlElement = parser.parse_element()?;
+		parser.skip_non_elements()?;
+		if !parser.remaining().is_empty() {
+			return Err(parser.error("expected end of document"));
+		}
+		Ok(root)
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Return a reference to the name.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the value of an attribute.
+	pub fn attribute(&self, name:&str) -> Option<&str> {
+		self.attributes.iter().find(|(attribute_name, _)| attribute_name == name).map(|(_, value)| value.as_str())
+	}
+
+	/// Return a reference to the child elements.
+	pub fn children(&self) -> &Vec<XmlElement> {
+		&self.children
+	}
+
+	/// Get the first child element with a name.
+	pub fn child(&self, name:&str) -> Option<&XmlElement> {
+		self.children.iter().find(|child| child.name == name)
+	}
+
+	/// Get all child elements with a name.
+	pub fn children_named<'a>(&'a self, name:&'a str) -> impl Iterator<Item = &'a XmlElement> {
+		self.children.iter().filter(move |child| child.name == name)
+	}
+}
+
+
+
+// Reads elements from XML text, keeping track of the position for error messages.
+struct XmlParser<'a> {
+	text:&'a str,
+	cursor:usize
+}
+impl XmlParser<'_> {
+
+	/// Parse the element at the cursor, including all its children.
+	fn parse_element(&mut self) -> Result<XmlElement, Box<dyn Error>> {
+		self.expect("<")?;
+		let name:String = self.read_name()?;
+		let mut element:XmlElement = XmlElement { name, attributes: Vec::new(), children: Vec::new() };
+
+		// Read attributes until the end of the tag.
+		loop {
+			self.skip_whitespace();
+			if self.remaining().starts_with("/>") {
+				self.cursor += 2;
+				return Ok(element);
+			}
+			if self.remaining().starts_with('>') {
+				self.cursor += 1;
+				break;
+			}
+			let attribute_name:String = self.read_name()?;
+			self.skip_whitespace();
+			self.expect("=")?;
+			self.skip_whitespace();
+			let quote:char = self.remaining().chars().next().filter(|character| *character == '"' || *character == '\'').ok_or_else(|| self.error("expected quoted attribute value"))?;
+			self.cursor += 1;
+			let value_length:usize = self.remaining().find(quote).ok_or_else(|| self.error("unterminated attribute value"))?;
+			let value:String = unescape(&self.remaining()[..value_length]);
+			self.cursor += value_length + 1;
+			element.attributes.push((attribute_name, value));
+		}
+
+		// Read children until the closing tag.
+		loop {
+			self.skip_non_elements()?;
+			if self.remaining().is_empty() {
+				return Err(self.error(&format!("missing closing tag of '{}'", element.name)));
+			}
+			if self.remaining().starts_with("</") {
+				self.cursor += 2;
+				let closing_name:String = self.read_name()?;
+				if closing_name != element.name {
+					return Err(self.error(&format!("expected closing tag of '{}' but found '{closing_name}'", element.name)));
+				}
+				self.skip_whitespace();
+				self.expect(">")?;
+				return Ok(element);
+			}
+			element.children.push(self.parse_element()?);
+		}
+	}
+
+	/// Skip everything up to the next element or closing tag, like text, comments, declarations and CDATA sections.
+	fn skip_non_elements(&mut self) -> Result<(), Box<dyn Error>> {
+		loop {
+			self.cursor += self.remaining().find('<').unwrap_or(self.remaining().len());
+			let terminator:&str = match self.remaining() {
+				remaining if remaining.starts_with("<!--") => "-->",
+				remaining if remaining.starts_with("<![CDATA[") => "]]>",
+				remaining if remaining.starts_with("<?") => "?>",
+				remaining if remaining.starts_with("<!") => ">",
+				_ => return Ok(())
+			};
+			let length:usize = self.remaining().find(terminator).ok_or_else(|| self.error(&format!("missing '{terminator}'")))?;
+			self.cursor += length + terminator.len();
+		}
+	}
+
+	/// Read the name of an element or attribute.
+	fn read_name(&mut self) -> Result<String, Box<dyn Error>> {
+		let length:usize = self.remaining().find(|character:char| character.is_whitespace() || ['=', '/', '>', '<'].contains(&character)).unwrap_or(self.remaining().len());
+		if length == 0 {
+			return Err(self.error("expected name"));
+		}
+		let name:String = self.remaining()[..length].to_string();
+		self.cursor += length;
+		Ok(name)
+	}
+
+	/// Skip the expected text or fail if it is not at the cursor.
+	fn expect(&mut self, expected:&str) -> Result<(), Box<dyn Error>> {
+		if !self.remaining().starts_with(expected) {
+			return Err(self.error(&format!("expected '{expected}'")));
+		}
+		self.cursor += expected.len();
+		Ok(())
+	}
+
+	/// Skip whitespace.
+	fn skip_whitespace(&mut self) {
+		self.cursor += self.remaining().len() - self.remaining().trim_start().len();
+	}
+
+	/// The text after the cursor.
+	fn remaining(&self) -> &str {
+		&self.text[self.cursor..]
+	}
+
+	/// Create an error at the current position.
+	fn error(&self, message:&str) -> Box<dyn Error> {
+		let line:usize = self.text[..self.cursor].matches('\n').count() + 1;
+		format!("Invalid XML on line {line}: {message}.").into()
+	}
+}
+
+
+
+/* HELPER METHODS */
+
+/// Replace the entities in XML text by the characters they represent.
+fn unescape(text:&str) -> String {
+	let mut output:String = String::new();
+	let mut remaining:&str = text;
+	while let Some(start) = remaining.find('&') {
+		output += &remaining[..start];
+		remaining = &remaining[start..];
+		let entity_length:Option<usize> = remaining.find(';');
+		let character:Option<char> = entity_length.and_then(|length| match &remaining[1..length] {
+			"lt" => Some('<'),
+			"gt" => Some('>'),
+			"amp" => Some('&'),
+			"quot" => Some('"'),
+			"apos" => Some('\''),
+			entity if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16).ok().and_then(char::from_u32),
+			entity if entity.starts_with('#') => entity[1..].parse::<u32>().ok().and_then(char::from_u32),
+			_ => None
+		});
+		match (character, entity_length) {
+			(Some(character), Some(length)) => {
+				output.push(character);
+				remaining = &remaining[length + 1..];
+			},
+			_ => {
+				output.push('&');
+				remaining = &remaining[1..];
+			}
+		}
+	}
+	output + remaining
+}
\ No newline at end of file
@@ -0,0 +1,127 @@
+use super::{ calculate_leg_rotations, LegSynchronizer, RobotSkeleton, RobotSkeletonLeg };
+use glass_panel::tridimensional::model::VertexMath;
+use crate::robot_configuration::RobotConfig;
+
+
+
+pub struct GaitTrajectory {
+	motors:Vec<usize>,
+	frames:Vec<GaitTrajectoryFrame>
+}
+impl GaitTrajectory {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Sample a full step cycle of the current robot config into a list of motor rotations. Does not require the UI or the realtime kinematics to be active.
+	pub fn create(sample_count:usize) -> GaitTrajectory {
+
+		// Build a fresh skeleton and synchronizer from the current config.
+		RobotSkeleton::create();
+		LegSynchronizer::create();
+		let robot_config:&RobotConfig = RobotConfig::get();
+		let skeleton:&RobotSkeleton = RobotSkeleton::get();
+		let synchronizer:&LegSynchronizer = LegSynchronizer::get();
+		let strafe_angle:f32 = synchronizer.strafe().to_radians();
+
+		// List all motors and their default rotation.
+		let mut motors:Vec<usize> = Vec::new();
+		let mut default_rotations:Vec<f32> = Vec::new();
+		for (part_index, part) in robot_config.parts().iter().enumerate() {
+			if let Some(motor) = part.motor() {
+				motors.push(part_index);
+				default_rotations.push(*motor.default_rotation());
+			}
+		}
+
+		// Sample the rotations of all motors throughout the step cycle.
+		let sample_count:usize = sample_count.max(1);
+		let frames:Vec<GaitTrajectoryFrame> = (0..sample_count).map(|sample_index| {
+			let progress:f32 = sample_index as f32 / sample_count as f32;
+			let mut rotations:Vec<f32> = default_rotations.clone();
+			let mut unreachable_legs:Vec<usize> = Vec::new();
+
+			// Calculate the rotations for each leg.
+			for (leg_index, target_offset) in synchronizer.target_offsets_at(progress) {
+				if let Some(Some(leg)) = skeleton.legs().get(leg_index) {
+					match calculate_leg_rotations(leg, Self::target_position(leg, &target_offset, strafe_angle)) {
+						Some(leg_rotations) => {
+							for (part_index, rotation) in leg_rotations {
+								if let Some(motor_index) = motors.iter().position(|motor| *motor == part_index) {
+									rotations[motor_index] = rotation;
+								}
+							}
+						},
+						None => unreachable_legs.push(leg_index)
+					}
+				}
+			}
+
+			GaitTrajectoryFrame { progress, rotations, unreachable_legs }
+		}).collect::<Vec<GaitTrajectoryFrame>>();
+
+		GaitTrajectory {
+			motors,
+			frames
+		}
+	}
+
+	/// Get the target position of the endpoint of a leg relative to the start of the leg.
+	pub fn target_position(leg:&RobotSkeletonLeg, target_offset:&[f32; 3], strafe_angle:f32) -> [f32; 3] {
+		leg.step_position().displaced(&target_offset.rotated(&[0.0, 0.0, strafe_angle], &None))
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Check if each leg could reach its target position in every frame.
+	pub fn is_reachable(&self) -> bool {
+		self.frames.iter().all(|frame| frame.unreachable_legs.is_empty())
+	}
+
+	/// List the progress and leg index for each moment a leg could not reach its target position.
+	pub fn unreachable_positions(&self) -> Vec<(f32, usize)> {
+		self.frames.iter().flat_map(|frame| frame.unreachable_legs.iter().map(|leg_index| (frame.progress, *leg_index))).collect::<Vec<(f32, usize)>>()
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Return a reference to the motors as indexes of the parts holding them.
+	pub fn motors(&self) -> &Vec<usize> {
+		&self.motors
+	}
+
+	/// Return a reference to the frames.
+	pub fn frames(&self) -> &Vec<GaitTrajectoryFrame> {
+		&self.frames
+	}
+}
+
+
+
+pub struct GaitTrajectoryFrame {
+	progress:f32,
+	rotations:Vec<f32>,
+	unreachable_legs:Vec<usize>
+}
+impl GaitTrajectoryFrame {
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Return a reference to the progress.
+	pub fn progress(&self) -> &f32 {
+		&self.progress
+	}
+
+	/// Return a reference to the rotation of each motor.
+	pub fn rotations(&self) -> &Vec<f32> {
+		&self.rotations
+	}
+
+	/// Return a reference to the unreachable_legs.
+	pub fn unreachable_legs(&self) -> &Vec<usize> {
+		&self.unreachable_legs
+	}
+}
\ No newline at end of file
@@ -2,7 +2,9 @@ use super::{ RobotSkeletonLeg, RobotSkeletonSegment };
 use glass_panel::tridimensional::model::VertexMath;
 use std::f32::consts::PI;
 
-/// Calculate how to rotate the motors to get to the target point.
+const PLANAR_TOLERANCE:f32 = 0.001;
+
+/// Calculate how to rotate the motors to get to the target point. Returns the rotation per index of the part holding the motor.
 pub fn calculate_leg_rotations(leg:&RobotSkeletonLeg, target_offset:[f32; 3]) -> Option<Vec<(usize, f32)>> {
 	let segments:&Vec<RobotSkeletonSegment> = leg.segments();
 	let total_endpoint_offset:[f32; 3] = leg.total_offset();
@@ -13,13 +15,19 @@ pub fn calculate_leg_rotations(leg:&RobotSkeletonLeg, target_offset:[f32; 3]) ->
 
 	let mut rotation_per_joint:Vec<(usize, f32)> = Vec::new();
 
+	// Legs without motor over the rigid axis can only move in a plane, which is fine as long as the target lies in that plane.
+	let planar:bool = motors_per_axis[rigid_axis].is_empty() && (target_offset[flexible_axis] - total_endpoint_offset[flexible_axis]).abs() < PLANAR_TOLERANCE;
+
 	// If the first motor rotates around the rigid axis, simply point it at the target position, then calculate the other axis.
-	if !segments.is_empty() && segments[0].axis() == &(rigid_axis as u8) {
+	if !segments.is_empty() && (segments[0].axis() == &(rigid_axis as u8) || planar) {
 
 		// Rotate the rigid axis to the correct position.
-		let neutral_rigid_offset_rotation:f32 = total_endpoint_offset[flexible_axis].atan2(total_endpoint_offset[alternate_axis]);
-		let rigid_rotation:f32 = (PI * 0.5) - target_offset[alternate_axis].atan2(target_offset[flexible_axis]) - neutral_rigid_offset_rotation;
-		rotation_per_joint.push((segments[0].joint_range()[0], rigid_rotation.to_degrees()));
+		let rigid_rotation:f32 = if planar { 0.0 } else {
+			let neutral_rigid_offset_rotation:f32 = total_endpoint_offset[flexible_axis].atan2(total_endpoint_offset[alternate_axis]);
+			let rigid_rotation:f32 = ((PI * 0.5) - target_offset[alternate_axis].atan2(target_offset[flexible_axis]) - neutral_rigid_offset_rotation + PI).rem_euclid(PI * 2.0) - PI; // Keep the rotation between -180 and 180 degrees, so targets behind the motor do not jump a full turn.
+			rotation_per_joint.push((segments[0].joint_range()[0], rigid_rotation.to_degrees()));
+			rigid_rotation
+		};
 
 		// Rotate the flexible axis to the correct position.
 		if !motors_per_axis[flexible_axis].is_empty() {
@@ -72,8 +80,8 @@ pub fn calculate_leg_rotations(leg:&RobotSkeletonLeg, target_offset:[f32; 3]) ->
 				let angle2:f32 = (flex_target_distance - intersection_x).atan2(intersection_y) - (PI / 2.0);
 
 				// Set angles to joints.
-				rotation_per_joint.push((first_flex_index, angle1.to_degrees() + z_offset_rotation));
-				rotation_per_joint.push((first_flex_index + 1, -angle1.to_degrees() + angle2.to_degrees()));
+				rotation_per_joint.push((segments[flex_joint_segments[0].0].joint_range()[0], angle1.to_degrees() + z_offset_rotation));
+				rotation_per_joint.push((segments[flex_joint_segments[1].0].joint_range()[0], -angle1.to_degrees() + angle2.to_degrees()));
 
 				// Return dataset.
 				return Some(rotation_per_joint);
@@ -82,9 +90,9 @@ pub fn calculate_leg_rotations(leg:&RobotSkeletonLeg, target_offset:[f32; 3]) ->
 				
 				// ToDo: Allow triple join legs with different distances, then create a recursive version to allow any amount of joints.
 				let angle:f32 = -(((flex_target_distance - distances[1]) / 2.0) / ((available_distance - distances[1]) / 2.0)).acos().to_degrees();
-				rotation_per_joint.push((first_flex_index, angle + z_offset_rotation));
-				rotation_per_joint.push((first_flex_index + 1, -angle));
-				rotation_per_joint.push((first_flex_index + 2, -angle));
+				rotation_per_joint.push((segments[flex_joint_segments[0].0].joint_range()[0], angle + z_offset_rotation));
+				rotation_per_joint.push((segments[flex_joint_segments[1].0].joint_range()[0], -angle));
+				rotation_per_joint.push((segments[flex_joint_segments[2].0].joint_range()[0], -angle));
 				return Some(rotation_per_joint);
 
 				/*
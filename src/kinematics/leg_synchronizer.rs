@@ -26,7 +26,7 @@ impl LegSynchronizer {
 		let skeleton_legs:Vec<(usize, &RobotSkeletonLeg)> = skeleton.legs_indexed();
 
 		// Find the smallest step the robot can make.
-		let smallest_step:f32 = skeleton_legs.iter().map(|(_, leg)| *leg.step_distance()).reduce(|a, b| a.min(b)).unwrap();
+		let smallest_step:f32 = skeleton_legs.iter().map(|(_, leg)| *leg.step_distance()).reduce(|a, b| a.min(b)).unwrap_or(0.0);
 
 		// Create the synchronizer.
 		let leg_synchronizer = LegSynchronizer {
@@ -56,6 +56,11 @@ impl LegSynchronizer {
 
 	/// Find the best set of delays based on a skeleton.
 	fn find_best_delays(skeleton_legs:&[(usize, &RobotSkeletonLeg)]) -> Vec<(usize, f32)> {
+
+		// Without legs there is nothing to synchronize, the brute-force loop below would never reach its end.
+		if skeleton_legs.is_empty() {
+			return Vec::new();
+		}
 		
 		// Calculate two groups of legs that are stable without another.
 		let min_legs_on_ground:usize = 3;
@@ -144,16 +149,18 @@ impl LegSynchronizer {
 
 	/// Get the realtime target position for each leg relative to the start of the step.
 	pub fn realtime_target_offsets(&mut self) -> Vec<(usize, [f32; 3])> {
-		let mut target_positions:Vec<(usize, [f32; 3])> = Vec::new();
-		for (leg_index, _) in RobotSkeleton::get().legs_indexed() {
+		self.realtime_progress();
+		self.target_offsets_at(self.progress)
+	}
 
-			// Get this leg's skeleton and progress.
-			if let Some(leg) = &RobotSkeleton::get().legs()[leg_index] {
-				if let Some(progress) = self.realtime_progress().iter().find(|(index, _)| index == &leg_index).map(|(_, progress)| progress) {
+	/// Get the target position for each leg relative to the start of the step at a specific point in the progress of a full step cycle.
+	pub fn target_offsets_at(&self, progress:f32) -> Vec<(usize, [f32; 3])> {
+		let mut target_positions:Vec<(usize, [f32; 3])> = Vec::new();
+		for (leg_index, progress_offset) in &self.leg_timing_offsets {
 
-					// Get the offset for this leg.
-					target_positions.push((leg_index, Self::target_offset_for(leg, *progress, self.smallest_step)));
-				}
+			// Get this leg's skeleton and add the offset for this leg.
+			if let Some(Some(leg)) = RobotSkeleton::get().legs().get(*leg_index) {
+				target_positions.push((*leg_index, Self::target_offset_for(leg, progress + progress_offset, self.smallest_step)));
 			}
 		}
 		target_positions
@@ -1,10 +1,12 @@
 // auto-publib 
 
+mod gait_trajectory;
 mod inversed_kinematics;
 mod leg_synchronizer;
 mod realtime_kinematics;
 mod robot_skeleton;
 
+pub use gait_trajectory::*;
 pub use inversed_kinematics::*;
 pub use leg_synchronizer::*;
 pub use realtime_kinematics::*;
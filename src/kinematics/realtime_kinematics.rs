@@ -1,8 +1,10 @@
 use crate::{ ui::Window, robot_configuration::RobotConfig };
-use super::{ RobotSkeleton, LegSynchronizer };
+use super::{ RobotSkeleton, LegSynchronizer, GaitTrajectory };
+use std::time::Instant;
 
 
 static mut REALTIME_KINEMATICS_ACTIVE:bool = false;
+static mut REALTIME_KINEMATICS_START:Option<Instant> = None;
 
 /// Check if the realtime kinematics are active.
 pub fn realtime_kinematics_active() -> bool {
@@ -13,6 +15,7 @@ pub fn realtime_kinematics_active() -> bool {
 pub fn activate_realtime_kinematics() {
 	if unsafe { REALTIME_KINEMATICS_ACTIVE } { return; }
 	unsafe { REALTIME_KINEMATICS_ACTIVE = true; }
+	unsafe { REALTIME_KINEMATICS_START = Some(Instant::now()); }
 
 	RobotSkeleton::create();
 	LegSynchronizer::create();
@@ -32,11 +35,9 @@ pub fn deactivate_realtime_kinematics() {
 	override_step_sizes(None);
 
 	// Reset motors to default rotation.
-	for leg in RobotConfig::get_mut().legs_mut() {
-		for joint in leg {
-			if let Some(motor) = joint.motor_mut() {
-				*motor.current_rotation_mut() = *motor.default_rotation();
-			}
+	for part in RobotConfig::get_mut().parts_mut() {
+		if let Some(motor) = part.motor_mut() {
+			*motor.current_rotation_mut() = *motor.default_rotation();
 		}
 	}
 
@@ -45,7 +46,6 @@ pub fn deactivate_realtime_kinematics() {
 
 /// Update the realtime kinematics. Will call the function again on each frame.
 fn update_realtime_kinematics() {
-	use glass_panel::tridimensional::model::VertexMath;
 	use crate::kinematics::calculate_leg_rotations;
 
 	// Fetch mainly used variables.
@@ -62,16 +62,15 @@ fn update_realtime_kinematics() {
 	// Update motor rotations according to target positions.
 	for (leg_index, target_offset) in &LegSynchronizer::get().realtime_target_offsets() {
 		if let Some(leg) = &skeleton.legs()[*leg_index] {
-			let step_position:[f32; 3] = *leg.step_position();
-			let target_position:[f32; 3] = step_position.displaced(&target_offset.rotated(&[0.0, 0.0, strafe_angle], &None));
+			let target_position:[f32; 3] = GaitTrajectory::target_position(leg, target_offset, strafe_angle);
 
-			// Find attached joint.
+			// Find attached part.
 			if let Some(rotations) = calculate_leg_rotations(leg, target_position) {
-				for (joint_index, target_rotation) in rotations {
-					if let Some(motor_joint) = robot_config.get_joint_mut(*leg_index, joint_index) {
-						if let Some(motor) = motor_joint.motor_mut() {
+				for (part_index, target_rotation) in rotations {
+					if let Some(motor_part) = robot_config.part_mut(part_index) {
+						if let Some(motor) = motor_part.motor_mut() {
 
-							// Rotate joint.
+							// Rotate part.
 							*motor.current_rotation_mut() = target_rotation;
 						}
 					}
@@ -80,6 +79,10 @@ fn update_realtime_kinematics() {
 		}
 	}
 
+	// Move manipulators along their path.
+	let time:f32 = unsafe { REALTIME_KINEMATICS_START }.map(|start| start.elapsed().as_secs_f32()).unwrap_or(0.0);
+	update_manipulator_rotations(time);
+
 	// Update leg rotations according to target rotations.
 	ui.update_scene_motors();
 
@@ -87,13 +90,45 @@ fn update_realtime_kinematics() {
 	ui.execute_post_listener(&update_realtime_kinematics);
 }
 
+/// Rotate the motors of all manipulators so their end effector reaches the target at a specific amount of seconds into their path. Uses the skeleton in the static instance. Returns the indexes of the manipulators that could not reach their target.
+pub fn update_manipulator_rotations(time:f32) -> Vec<usize> {
+	use crate::kinematics::calculate_leg_rotations;
+	use crate::robot_configuration::ManipulatorConfig;
+
+	let robot_config:&mut RobotConfig = RobotConfig::get_mut();
+	let manipulators:Vec<Vec<usize>> = robot_config.manipulators();
+	let mut unreachable:Vec<usize> = Vec::new();
+	for (manipulator_index, skeleton_chain) in RobotSkeleton::get().manipulators().iter().enumerate() {
+		if let (Some(skeleton_chain), Some(chain)) = (skeleton_chain, manipulators.get(manipulator_index)) {
+
+			// Find the current target of the end effector.
+			let manipulator:Option<&ManipulatorConfig> = chain.last().and_then(|part_index| robot_config.parts()[*part_index].kinematics_config().as_ref()).and_then(|kinematics| kinematics.manipulator().as_ref());
+			let target_position:[f32; 3] = match manipulator {
+				Some(manipulator) => manipulator.target_at(time),
+				None => continue
+			};
+
+			// Rotate the motors using the same solver as the legs.
+			match calculate_leg_rotations(skeleton_chain, target_position) {
+				Some(rotations) => {
+					for (part_index, target_rotation) in rotations {
+						if let Some(motor) = robot_config.part_mut(part_index).and_then(|part| part.motor_mut().as_mut()) {
+							*motor.current_rotation_mut() = target_rotation;
+						}
+					}
+				},
+				None => unreachable.push(manipulator_index)
+			}
+		}
+	}
+	unreachable
+}
+
 /// Set the step size of all kinematic steps.
 fn override_step_sizes(size:Option<f32>) {
-	for leg in RobotConfig::get_mut().legs_mut() {
-		for joint in leg {
-			if let Some(kinematics) = joint.kinematics_config_mut() {
-				*kinematics.step_distance_override() = size;
-			}
+	for part in RobotConfig::get_mut().parts_mut() {
+		if let Some(kinematics) = part.kinematics_config_mut() {
+			*kinematics.step_distance_override() = size;
 		}
 	}
 }
\ No newline at end of file
@@ -1,77 +1,78 @@
 use glass_panel::tridimensional::model::{ VertexMath, Mesh };
-use crate::robot_configuration::RobotConfig;
+use crate::robot_configuration::{ RobotConfig, PartConfig };
 
 
 
 static mut STATIC_SKELETON_INSTANCE:Option<RobotSkeleton> = None;
 pub struct RobotSkeleton {
-	legs:Vec<Option<RobotSkeletonLeg>>
+	legs:Vec<Option<RobotSkeletonLeg>>,
+	manipulators:Vec<Option<RobotSkeletonLeg>>
 }
 impl RobotSkeleton {
 
 	/* CONSTRUCTOR METHODS */
 
-	/// Create a skeleton from the given robot config and store it in the static instance. This function might get complicated, prioritize code clarity over efficiency.
+	/// Create a skeleton from the current robot config and store it in the static instance.
 	pub fn create() {
+		let skeleton:RobotSkeleton = RobotSkeleton::from_config(RobotConfig::get());
+		unsafe { STATIC_SKELETON_INSTANCE = Some(skeleton) }
+	}
 
-		// Get current config.
-		let robot_config:&RobotConfig = RobotConfig::get();
-
-		// Loop through legs.
-		let mut skeleton_legs:Vec<Option<RobotSkeletonLeg>> = Vec::new();
-		for (leg_index, leg) in robot_config.legs().iter().enumerate() {
-			let mut skeleton_leg:Option<RobotSkeletonLeg> = None;
-			
-			// Loop through joints.
-			let mut position:[f32; 3] = [0.0; 3];
-			let mut segment_start_joint:usize = 0;
-			let mut segment_axis:u8 = 0;
-			for (joint_index, joint) in leg.iter().enumerate() {
-
-				// Modify position.
-				position.displace(joint.position());
-
-				// If joint has a motor, build a new segment from the start joint to this joint.
-				if let Some(motor) = joint.motor() {
-					match &mut skeleton_leg {
-						None => skeleton_leg = Some(RobotSkeletonLeg::new(position.displaced(motor.position()))),
-						Some(skeleton_leg) => skeleton_leg.add_segment(RobotSkeletonSegment::new(segment_axis, [segment_start_joint, joint_index], position.displaced(motor.position())))
-					}
-
-					// Mark this the start of the new segment.
-					segment_start_joint = joint_index;
-					position = motor.position().negative(); // The motor's position is relative to the mesh in the main structure, but not in the skeleton.
-					segment_axis = *motor.rotation_axis();
-				}
+	/// Create a skeleton from the given robot config.
+	pub fn from_config(robot_config:&RobotConfig) -> RobotSkeleton {
+		RobotSkeleton {
+			legs: robot_config.legs().iter().map(|chain| Self::chain_from_config(robot_config, chain)).collect::<Vec<Option<RobotSkeletonLeg>>>(),
+			manipulators: robot_config.manipulators().iter().map(|chain| Self::chain_from_config(robot_config, chain)).collect::<Vec<Option<RobotSkeletonLeg>>>()
+		}
+	}
 
-				// If this joint has kinematics configuration, add final changes to the leg.
-				if let Some(kinematics_config) = joint.kinematics_config() {
+	/// Create the skeleton of a single chain of parts. Legs and manipulators share the same skeleton so both can be solved by the same inverse kinematics. This function might get complicated, prioritize code clarity over efficiency.
+	fn chain_from_config(robot_config:&RobotConfig, chain:&[usize]) -> Option<RobotSkeletonLeg> {
+		let mut skeleton_leg:Option<RobotSkeletonLeg> = None;
+
+		// Loop through the parts from the root to the endpoint of the chain.
+		let mut position:[f32; 3] = [0.0; 3];
+		let mut segment_start_part:usize = 0;
+		let mut segment_axis:u8 = 0;
+		for part_index in chain {
+			let part_index:usize = *part_index;
+			let part:&PartConfig = &robot_config.parts()[part_index];
+
+			// Modify position.
+			position.displace(part.position());
+
+			// If part has a motor, build a new segment from the start part to this part.
+			if let Some(motor) = part.motor() {
+				match &mut skeleton_leg {
+					None => skeleton_leg = Some(RobotSkeletonLeg::new(position.displaced(motor.position()))),
+					Some(skeleton_leg) => skeleton_leg.add_segment(RobotSkeletonSegment::new(segment_axis, [segment_start_part, part_index], position.displaced(motor.position())))
+				}
 
-					// Add last segment to leg.
-					if let Some(skeleton_leg) = &mut skeleton_leg {
-						let motor_offset:[f32; 3] = joint.motor().as_ref().map(|m| *m.position()).unwrap_or_default();
-						let endpoint_offset:[f32; 3] = kinematics_config.leg_endpoint().displaced(&motor_offset);
-						skeleton_leg.add_segment(RobotSkeletonSegment::new(segment_axis, [segment_start_joint, joint_index], position.displaced(&endpoint_offset)));
-					}
+				// Mark this the start of the new segment.
+				segment_start_part = part_index;
+				position = motor.position().negative(); // The motor's position is relative to the mesh in the main structure, but not in the skeleton.
+				segment_axis = *motor.rotation_axis();
+			}
 
-					// Add endpoint to leg.
-					if let Some(skeleton_leg) = &mut skeleton_leg {
-						skeleton_leg.set_step_matrix(*kinematics_config.step_position(), *kinematics_config.step_distance(), *kinematics_config.step_height());
-					}
+			// If this part has kinematics configuration, add final changes to the chain.
+			if let Some(kinematics_config) = part.kinematics_config() {
 
-					// Add the leg to the list. If no endpoint is found, it is not useful and should not be added.
-					skeleton_legs.push(skeleton_leg);
-					break;
+				// Add last segment to chain.
+				if let Some(skeleton_leg) = &mut skeleton_leg {
+					let motor_offset:[f32; 3] = part.motor().as_ref().map(|m| *m.position()).unwrap_or_default();
+					let endpoint_offset:[f32; 3] = kinematics_config.leg_endpoint().displaced(&motor_offset);
+					skeleton_leg.add_segment(RobotSkeletonSegment::new(segment_axis, [segment_start_part, part_index], position.displaced(&endpoint_offset)));
 				}
-			}
 
-			// Add empty space if no leg could be created.
-			if skeleton_legs.len() <= leg_index {
-				skeleton_legs.push(None);
+				// Add endpoint to chain.
+				if let Some(skeleton_leg) = &mut skeleton_leg {
+					skeleton_leg.set_step_matrix(*kinematics_config.step_position(), *kinematics_config.step_distance(), *kinematics_config.step_height());
+				}
 			}
 		}
 
-		unsafe { STATIC_SKELETON_INSTANCE = Some(RobotSkeleton { legs: skeleton_legs }) }
+		// If no motor is found, the chain is not useful and stays empty.
+		skeleton_leg
 	}
 
 	/// Get the static config.
@@ -94,6 +95,11 @@ impl RobotSkeleton {
 		&self.legs
 	}
 
+	/// Get the manipulators. These use the same skeleton as legs, but are not part of the gait.
+	pub fn manipulators(&self) -> &Vec<Option<RobotSkeletonLeg>> {
+		&self.manipulators
+	}
+
 	/// Get an indexed list of legs.
 	pub fn legs_indexed(&self) -> Vec<(usize, &RobotSkeletonLeg)> {
 		let mut list:Vec<(usize, &RobotSkeletonLeg)> = Vec::new();
@@ -242,7 +248,7 @@ impl RobotSkeletonSegment {
 		&self.axis
 	}
 
-	/// Return a reference to the joint_range.
+	/// Return a reference to the joint_range. Contains the indexes of the part with the motor the segment rotates around and the part it ends at.
 	pub fn joint_range(&self) -> &[usize; 2] {
 		&self.joint_range
 	}
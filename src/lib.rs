@@ -1,6 +1,13 @@
-// auto-publib exclude=[deps] no-use pub-mod
+mod _unit_testing;
+
+// auto-publib exclude=[deps,_unit_testing] no-use pub-mod
+pub mod cli;
+pub mod exporting;
+pub mod importing;
 pub mod kinematics;
 pub mod robot_configuration;
 pub mod ui;
 
+pub mod autosave;
+pub mod project_archive;
 pub mod storage;
\ No newline at end of file
@@ -1,28 +1,72 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-	use kinematic_flow::{ storage, ui::Window, robot_configuration::RobotConfig };
-	use std::{ thread::sleep, time::{ Duration, Instant } };
+	use kinematic_flow::{ autosave, storage, ui::Window, robot_configuration::RobotConfig };
+	use std::{ thread::{ sleep, spawn, JoinHandle }, time::{ Duration, Instant } };
 	use dynamic_data_storage::StorageManager;
 
+	// Allow projects stored by older versions to be read.
+	RobotConfig::register_migrations();
+
+	// Execute a command without opening the editor when arguments are given.
+	let arguments:Vec<String> = match kinematic_flow::cli::apply_global_options(&std::env::args().skip(1).collect::<Vec<String>>()) {
+		Ok(arguments) => arguments,
+		Err(error) => {
+			eprintln!("{error}\n\n{}", kinematic_flow::cli::usage());
+			std::process::exit(kinematic_flow::cli::EXIT_CODE_USAGE);
+		}
+	};
+	if !arguments.is_empty() {
+		std::process::exit(kinematic_flow::cli::execute(&arguments));
+	}
+
 	// Prepare project storage.
 	if let Err(error) = storage::init_storage() {
 		panic!("Could not initialize storage: {error}");
 	}
-	let strg:&mut StorageManager = StorageManager::get_mut();
+	let (fps, window_size, autosave_interval, autosave_slots):(f32, [u16; 2], Duration, usize) = {
+		let strg:&mut StorageManager = &mut StorageManager::get_mut();
+		let autosave_delay:f32 = strg.quick("storage_autosave_delay", 2.0);
+		strg.set_autosave_delay(Some(Duration::from_secs_f32(autosave_delay)));
+		let autosave_interval:f32 = strg.quick("project_autosave_interval", 60.0);
+		(strg.quick("main_window_framerate", 60.0), [strg.quick("main_window_width", 800), strg.quick("main_window_height", 600)], Duration::from_secs_f32(autosave_interval), strg.quick("project_autosave_slots", 10))
+	};
+
+	// Detect if the previous session ended unexpectedly, its autosaves can be restored from the project loading tooltip.
+	if let Err(error) = autosave::begin_session() {
+		eprintln!("Could not start session: {error}");
+	}
 
 	// Create the main window.
-	let fps:f32 = strg.quick("main_window_framerate", 60.0);
-	let window_size:[u16; 2] = [strg.quick("main_window_width", 800), strg.quick("main_window_height", 600)];
 	let window:&mut Window = Window::create([window_size[0] as usize, window_size[1] as usize], fps);
 	window.show_project_loading_tooltip();
 
 	// Main loop.
 	let interval:Duration = Duration::from_millis((1000.0 / fps).floor() as u64);
+	let mut last_autosave:Instant = Instant::now();
+	let mut autosave_thread:Option<JoinHandle<()>> = None;
  	while window.exists() {
 		let frame_start:Instant = Instant::now();
 
 		// Update UI window.
 		window.display();
 
+		// Store changes once they settled.
+		if let Err(error) = StorageManager::get_mut().autosave_if_due() {
+			eprintln!("Could not store changes: {error}");
+		}
+
+		// Keep a copy of the project in case the editor does not close cleanly. The snapshot is taken here, but written in the background. Wait for the previous write to finish before taking the next one.
+		if last_autosave.elapsed() >= autosave_interval && autosave_thread.as_ref().is_none_or(|thread| thread.is_finished()) {
+			last_autosave = Instant::now();
+			match autosave::AutosaveSnapshot::from_current_project() {
+				Ok(snapshot) => autosave_thread = Some(spawn(move || {
+					if let Err(error) = snapshot.write(autosave_slots) {
+						eprintln!("Could not autosave project: {error}");
+					}
+				})),
+				Err(error) => eprintln!("Could not autosave project: {error}")
+			}
+		}
+
 		// Await interval.
 		let processing_time:Duration = Instant::now() - frame_start;
 		if processing_time < interval {
@@ -31,8 +75,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 	}
 
 	// Save progress to auto-save.
+	if let Some(thread) = autosave_thread {
+		let _ = thread.join();
+	}
 	let _ = storage::store_project_at(storage::AUTO_SAVE_PROJECT_NAME);
 	RobotConfig::save();
+	if let Err(error) = autosave::end_session() {
+		eprintln!("Could not end session: {error}");
+	}
 
 	Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,147 @@
+use crate::storage;
+use dynamic_data_storage::{ GeneralDataType, StorageManager };
+use std::{ error::Error, fs, path::{ Component, Path } };
+
+pub static PROJECT_ARCHIVE_EXTENSION:&str = "kflow";
+static ARCHIVE_RECOGNITION_TAG:&str = "<<KinematicFlowProject>>";
+static ARCHIVE_FORMAT_VERSION:u16 = 1;
+static ARCHIVE_SKIPPED_EXTENSIONS:[&str; 2] = ["bak", "tmp"];
+
+
+
+// A project stored as a single file, containing its storage files, meshes and thumbnail. Used to share projects between machines.
+#[derive(GeneralDataType)]
+pub struct ProjectArchive {
+	name:String,
+	files:Vec<ArchiveFile>
+}
+impl ProjectArchive {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create an archive of the current project. The meshes of the project are copied into its assets dir first, so the archive contains everything required to open it elsewhere. Packing saves the project, which also updates its thumbnail.
+	pub fn from_current_project() -> Result<ProjectArchive, Box<dyn Error>> {
+		for mesh in storage::pack_project()? {
+			eprintln!("Warning: Could not find mesh '{mesh}', it is not included in the archive.");
+		}
+		StorageManager::get_mut().flush()?;
+
+		let mut files:Vec<ArchiveFile> = Vec::new();
+		Self::collect_files(&storage::project_dir(), "", &mut files)?;
+		Ok(ProjectArchive { name: storage::project_name().to_string(), files })
+	}
+
+	/// Add all files in a dir and its sub-dirs to the list, with their path relative to the project dir. Backups and temporary files of the storage are skipped.
+	fn collect_files(project_dir:&str, relative_dir:&str, files:&mut Vec<ArchiveFile>) -> Result<(), Box<dyn Error>> {
+		let mut entries:Vec<fs::DirEntry> = fs::read_dir(format!("{project_dir}/{relative_dir}"))?.flatten().collect::<Vec<fs::DirEntry>>();
+		entries.sort_by_key(|entry| entry.file_name());
+		for entry in entries {
+			let relative_path:String = format!("{relative_dir}{}", entry.file_name().to_string_lossy());
+			if entry.file_type()?.is_dir() {
+				Self::collect_files(project_dir, &(relative_path + "/"), files)?;
+			} else if !entry.path().extension().is_some_and(|extension| ARCHIVE_SKIPPED_EXTENSIONS.contains(&extension.to_string_lossy().as_ref())) {
+				files.push(ArchiveFile { path: relative_path, bytes: fs::read(entry.path())? });
+			}
+		}
+		Ok(())
+	}
+
+	/// Read an archive from a file.
+	pub fn read(path:&str) -> Result<ProjectArchive, Box<dyn Error>> {
+		let bytes:Vec<u8> = fs::read(path).map_err(|error| format!("Could not read '{path}': {error}"))?;
+
+		// Validate header.
+		let header_length:usize = ARCHIVE_RECOGNITION_TAG.len() + 2;
+		if bytes.get(..ARCHIVE_RECOGNITION_TAG.len()) != Some(ARCHIVE_RECOGNITION_TAG.as_bytes()) || bytes.len() < header_length {
+			return Err(format!("Could not read '{path}', file is not recognized as project archive.").into());
+		}
+		let version:u16 = u16::from_bytes(&bytes[ARCHIVE_RECOGNITION_TAG.len()..header_length])?;
+		if version > ARCHIVE_FORMAT_VERSION {
+			return Err(format!("Could not read '{path}', archive format version {version} is newer than the supported version {ARCHIVE_FORMAT_VERSION}.").into());
+		}
+
+		// Parse contents.
+		ProjectArchive::from_bytes(&bytes[header_length..]).map_err(|error| format!("Could not read '{path}': {error}").into())
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Return a reference to the name of the archived project.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Write the archive to a file.
+	pub fn write(&self, path:&str) -> Result<(), Box<dyn Error>> {
+		let bytes:Vec<u8> = [ARCHIVE_RECOGNITION_TAG.as_bytes().to_vec(), ARCHIVE_FORMAT_VERSION.to_bytes(), self.to_bytes()].concat();
+		fs::write(path, bytes).map_err(|error| format!("Could not write '{path}': {error}"))?;
+		Ok(())
+	}
+
+	/// Extract the archive as a new project with the given name and open it. Fails when a project with that name already exists.
+	pub fn import_as(&self, project_name:&str) -> Result<(), Box<dyn Error>> {
+
+		// Validate the project name and the paths of all files before anything is written.
+		if project_name.is_empty() || project_name.contains(['/', '\\']) || project_name == storage::AUTO_SAVE_PROJECT_NAME {
+			return Err(format!("'{project_name}' can not be used as project name.").into());
+		}
+		let project_dir:String = storage::projects_dir() + "/" + project_name;
+		if Path::new(&project_dir).exists() || storage::find_project_dir(project_name).is_some() {
+			return Err(format!("A project named '{project_name}' already exists.").into());
+		}
+		if let Some(file) = self.files.iter().find(|file| !Path::new(&file.path).components().all(|component| matches!(component, Component::Normal(_)))) {
+			return Err(format!("Could not import archive, it contains the invalid path '{}'.", file.path).into());
+		}
+
+		// Write files and open the project.
+		for file in &self.files {
+			let target_file:String = format!("{project_dir}/{}", file.path);
+			if let Some(parent) = Path::new(&target_file).parent() {
+				fs::create_dir_all(parent)?;
+			}
+			fs::write(&target_file, &file.bytes)?;
+		}
+		storage::select_project(project_name)
+	}
+}
+
+
+
+// A single file of an archived project.
+#[derive(GeneralDataType)]
+struct ArchiveFile {
+	path:String,
+	bytes:Vec<u8>
+}
+
+
+
+/* HELPER METHODS */
+
+/// Export the current project to an archive file.
+pub fn export_project_archive(path:&str) -> Result<(), Box<dyn Error>> {
+	project_archive_for_file(path)?.write(path)
+}
+
+/// Create an archive of the current project to write to a file. The last session has no name of its own, so it is named after the file. Creating the archive saves the project and renders its thumbnail, so it should happen on the UI thread, writing it to the file does not have to.
+pub fn project_archive_for_file(path:&str) -> Result<ProjectArchive, Box<dyn Error>> {
+	let mut archive:ProjectArchive = ProjectArchive::from_current_project()?;
+	if archive.name == storage::AUTO_SAVE_PROJECT_NAME {
+		archive.name = Path::new(path).file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or(String::from("Project"));
+	}
+	Ok(archive)
+}
+
+/// Import an archive file as a new project and open it. Without a name, the name of the archived project is used. Returns the name of the new project.
+pub fn import_project_archive(path:&str, project_name:Option<&str>) -> Result<String, Box<dyn Error>> {
+	let archive:ProjectArchive = ProjectArchive::read(path)?;
+	let project_name:String = project_name.unwrap_or(archive.name()).to_string();
+	archive.import_as(&project_name)?;
+	Ok(project_name)
+}
\ No newline at end of file
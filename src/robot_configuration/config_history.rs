@@ -0,0 +1,121 @@
+use dynamic_data_storage::GeneralDataType;
+use std::{ sync::{ Mutex, MutexGuard, PoisonError }, time::{ Duration, Instant } };
+use super::RobotConfig;
+
+static STATIC_HISTORY_INSTANCE:Mutex<ConfigHistory> = Mutex::new(ConfigHistory::new());
+const HISTORY_MAX_LENGTH:usize = 100;
+const COALESCE_TIMEOUT:Duration = Duration::from_secs(1);
+
+
+
+// The undo and redo history of the robot config. Every edit is stored as the config before and after the edit, so undoing or redoing an edit restores one of them.
+pub struct ConfigHistory {
+	undo_stack:Vec<ConfigEdit>,
+	redo_stack:Vec<ConfigEdit>,
+	last_coalesce_key:Option<(String, Instant)>
+}
+impl ConfigHistory {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create an empty history.
+	const fn new() -> ConfigHistory {
+		ConfigHistory {
+			undo_stack: Vec::new(),
+			redo_stack: Vec::new(),
+			last_coalesce_key: None
+		}
+	}
+
+	/// Lock the static history.
+	fn get() -> MutexGuard<'static, ConfigHistory> {
+		STATIC_HISTORY_INSTANCE.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	/// Remove all edits, for example when another project is loaded.
+	pub fn clear() {
+		*ConfigHistory::get() = ConfigHistory::new();
+	}
+
+
+
+	/* EDIT METHODS */
+
+	/// Make a change to the robot config that can be undone. Changes that do not modify the config are not stored. Consecutive changes with the same coalesce key, like dragging a single input, are combined into one edit.
+	pub fn edit<T>(coalesce_key:Option<&str>, change:impl FnOnce() -> T) -> T {
+		let before:Vec<u8> = RobotConfig::get().to_bytes();
+		let result:T = change();
+		let after:Vec<u8> = RobotConfig::get().to_bytes();
+		if before != after {
+			ConfigHistory::get().push(before, after, coalesce_key);
+		}
+		result
+	}
+
+	/// Add an edit to the history, or extend the last edit when it has the same coalesce key.
+	fn push(&mut self, before:Vec<u8>, after:Vec<u8>, coalesce_key:Option<&str>) {
+		self.redo_stack.clear();
+
+		// Extend the last edit while the same input keeps changing.
+		let coalesces:bool = matches!((&self.last_coalesce_key, coalesce_key), (Some((last_key, last_change)), Some(key)) if last_key == key && last_change.elapsed() < COALESCE_TIMEOUT);
+		self.last_coalesce_key = coalesce_key.map(|key| (key.to_string(), Instant::now()));
+		if let (true, Some(last_edit)) = (coalesces, self.undo_stack.last_mut()) {
+			last_edit.after = after;
+			return;
+		}
+
+		// Add a new edit, forgetting the oldest one when the history is full.
+		self.undo_stack.push(ConfigEdit { before, after });
+		if self.undo_stack.len() > HISTORY_MAX_LENGTH {
+			self.undo_stack.remove(0);
+		}
+	}
+
+	/// Check if there is an edit to undo.
+	pub fn can_undo() -> bool {
+		!ConfigHistory::get().undo_stack.is_empty()
+	}
+
+	/// Check if there is an undone edit to redo.
+	pub fn can_redo() -> bool {
+		!ConfigHistory::get().redo_stack.is_empty()
+	}
+
+	/// Restore the config from before the last edit. Returns false if there was nothing to undo.
+	pub fn undo() -> bool {
+		let mut history:MutexGuard<ConfigHistory> = ConfigHistory::get();
+		history.last_coalesce_key = None;
+		match history.undo_stack.pop() {
+			Some(edit) => {
+				let config:Option<RobotConfig> = RobotConfig::from_bytes(&edit.before).ok();
+				history.redo_stack.push(edit);
+				drop(history);
+				config.map(RobotConfig::replace).is_some()
+			},
+			None => false
+		}
+	}
+
+	/// Restore the config from after the last undone edit. Returns false if there was nothing to redo.
+	pub fn redo() -> bool {
+		let mut history:MutexGuard<ConfigHistory> = ConfigHistory::get();
+		history.last_coalesce_key = None;
+		match history.redo_stack.pop() {
+			Some(edit) => {
+				let config:Option<RobotConfig> = RobotConfig::from_bytes(&edit.after).ok();
+				history.undo_stack.push(edit);
+				drop(history);
+				config.map(RobotConfig::replace).is_some()
+			},
+			None => false
+		}
+	}
+}
+
+
+
+// A single edit of the robot config.
+struct ConfigEdit {
+	before:Vec<u8>,
+	after:Vec<u8>
+}
\ No newline at end of file
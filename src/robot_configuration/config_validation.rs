@@ -0,0 +1,232 @@
+use crate::kinematics::{ calculate_leg_rotations, RobotSkeleton, RobotSkeletonLeg };
+use glass_panel::tridimensional::model::VertexMath;
+use super::{ RobotConfig, PartConfig };
+use std::{ fmt::{ self, Display, Formatter }, path::Path };
+
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DiagnosticSeverity {
+	Warning,
+	Error
+}
+impl Display for DiagnosticSeverity {
+	fn fmt(&self, f:&mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", match self { DiagnosticSeverity::Warning => "warning", DiagnosticSeverity::Error => "error" })
+	}
+}
+
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConfigDiagnostic {
+	severity:DiagnosticSeverity,
+	part_index:Option<usize>,
+	message:String
+}
+impl ConfigDiagnostic {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new diagnostic. Without part index, the diagnostic is about the robot as a whole.
+	pub fn new(severity:DiagnosticSeverity, part_index:Option<usize>, message:&str) -> ConfigDiagnostic {
+		ConfigDiagnostic {
+			severity,
+			part_index,
+			message: message.to_string()
+		}
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Return a reference to the severity.
+	pub fn severity(&self) -> &DiagnosticSeverity {
+		&self.severity
+	}
+
+	/// Return a reference to the part_index.
+	pub fn part_index(&self) -> &Option<usize> {
+		&self.part_index
+	}
+
+	/// Return a reference to the message.
+	pub fn message(&self) -> &String {
+		&self.message
+	}
+}
+impl Display for ConfigDiagnostic {
+	fn fmt(&self, f:&mut Formatter<'_>) -> fmt::Result {
+		let location:String = match self.part_index {
+			None => "robot".to_string(),
+			Some(part_index) => format!("part {part_index}")
+		};
+		write!(f, "{} ({location}): {}", self.severity, self.message)
+	}
+}
+
+
+
+impl RobotConfig {
+
+	/* VALIDATION METHODS */
+
+	/// Walk through the config and its skeleton and list every problem that would prevent the robot from walking or its manipulators from reaching their targets.
+	pub fn validate(&self) -> Vec<ConfigDiagnostic> {
+		let mut diagnostics:Vec<ConfigDiagnostic> = Vec::new();
+		if self.parts().is_empty() {
+			diagnostics.push(ConfigDiagnostic::new(DiagnosticSeverity::Warning, None, "The robot has no parts."));
+		}
+
+		// Validate parts.
+		for (part_index, part) in self.parts().iter().enumerate() {
+			diagnostics.extend(Self::validate_part(part).into_iter().map(|(severity, message)| ConfigDiagnostic::new(severity, Some(part_index), &message)));
+		}
+
+		// Validate legs and manipulators.
+		let legs:Vec<Vec<usize>> = self.legs();
+		let manipulators:Vec<Vec<usize>> = self.manipulators();
+		let chains:Vec<Vec<usize>> = [legs.clone(), manipulators.clone()].concat();
+		for leg in &legs {
+			diagnostics.extend(self.validate_chain(leg, "Leg", &chains));
+		}
+		for manipulator in &manipulators {
+			diagnostics.extend(self.validate_chain(manipulator, "Manipulator", &chains));
+		}
+
+		// Validate the reach of each leg and manipulator.
+		let skeleton:RobotSkeleton = RobotSkeleton::from_config(self);
+		for (leg_index, skeleton_leg) in skeleton.legs_indexed() {
+			diagnostics.extend(self.validate_leg_reach(&legs[leg_index], skeleton_leg));
+		}
+		for (manipulator_index, skeleton_chain) in skeleton.manipulators().iter().enumerate() {
+			if let Some(skeleton_chain) = skeleton_chain {
+				diagnostics.extend(self.validate_manipulator_reach(&manipulators[manipulator_index], skeleton_chain));
+			}
+		}
+
+		diagnostics
+	}
+
+	/// Validate the settings of a single part.
+	fn validate_part(part:&PartConfig) -> Vec<(DiagnosticSeverity, String)> {
+		let mut diagnostics:Vec<(DiagnosticSeverity, String)> = Vec::new();
+
+		// Validate mesh.
+		if let Some(primitive) = part.primitive() {
+			if primitive.shape().dimensions().iter().any(|dimension| *dimension <= 0.0 || dimension.is_nan()) {
+				diagnostics.push((DiagnosticSeverity::Error, format!("{} primitive has dimensions that are not positive.", primitive.shape().name())));
+			}
+			if primitive.shape().axis().is_some_and(|axis| axis > 2) {
+				diagnostics.push((DiagnosticSeverity::Error, format!("{} primitive axis {} does not exist.", primitive.shape().name(), primitive.shape().axis().unwrap())));
+			}
+		} else if part.obj().is_empty() {
+			diagnostics.push((DiagnosticSeverity::Error, "Part has no mesh file.".to_string()));
+		} else if !Path::new(&crate::storage::asset_path(part.obj())).is_file() {
+			diagnostics.push((DiagnosticSeverity::Error, format!("Mesh file '{}' does not exist.", part.obj())));
+		}
+
+		// Validate motor.
+		if let Some(motor) = part.motor() {
+			let [range_min, range_max] = *motor.rotation_range();
+			if *motor.rotation_axis() > 2 {
+				diagnostics.push((DiagnosticSeverity::Error, format!("Motor rotation axis {} does not exist.", motor.rotation_axis())));
+			}
+			if range_min > range_max {
+				diagnostics.push((DiagnosticSeverity::Error, format!("Motor rotation range {range_min} to {range_max} is inverted.")));
+			} else if *motor.default_rotation() < range_min || *motor.default_rotation() > range_max {
+				diagnostics.push((DiagnosticSeverity::Warning, format!("Motor default rotation {} is outside its range of {range_min} to {range_max}.", motor.default_rotation())));
+			}
+		}
+
+		// Validate kinematics.
+		if let Some(kinematics_config) = part.kinematics_config() {
+			if kinematics_config.manipulator().is_none() && *kinematics_config.step_distance() <= 0.0 {
+				diagnostics.push((DiagnosticSeverity::Warning, "Step distance is zero, the leg will not move forward.".to_string()));
+			}
+		}
+
+		diagnostics
+	}
+
+	/// Validate the chain of parts of a single leg or manipulator. The chain kind is only used in the messages.
+	fn validate_chain(&self, chain:&[usize], chain_kind:&str, chains:&[Vec<usize>]) -> Vec<ConfigDiagnostic> {
+		let mut diagnostics:Vec<ConfigDiagnostic> = Vec::new();
+		let endpoint_index:usize = chain[chain.len() - 1];
+
+		// Validate the chain as a whole.
+		if !chain.iter().any(|part_index| self.parts()[*part_index].motor().is_some()) {
+			diagnostics.push(ConfigDiagnostic::new(DiagnosticSeverity::Warning, Some(endpoint_index), &format!("{chain_kind} has no motors and cannot move.")));
+		}
+
+		// Validate the parts in the chain.
+		for part_index in &chain[..chain.len() - 1] {
+			let part:&PartConfig = &self.parts()[*part_index];
+			if part.kinematics_config().is_some() {
+				diagnostics.push(ConfigDiagnostic::new(DiagnosticSeverity::Error, Some(*part_index), &format!("Kinematics config is only allowed on the last part of a {}, but '{}' is attached below this part.", chain_kind.to_lowercase(), self.parts()[endpoint_index].name())));
+			}
+			if part.motor().is_some() {
+				let sharing_endpoints:Vec<&str> = chains.iter().filter(|other_chain| other_chain[other_chain.len() - 1] < endpoint_index && other_chain.contains(part_index)).map(|other_chain| self.parts()[other_chain[other_chain.len() - 1]].name().as_str()).collect::<Vec<&str>>();
+				if !sharing_endpoints.is_empty() {
+					diagnostics.push(ConfigDiagnostic::new(DiagnosticSeverity::Warning, Some(*part_index), &format!("Motor is used by the chains ending in '{}' and '{}', they will fight over its rotation.", sharing_endpoints.join("', '"), self.parts()[endpoint_index].name())));
+				}
+			}
+		}
+
+		diagnostics
+	}
+
+	/// Validate that a leg can reach all corners of its step rectangle within the rotation ranges of its motors.
+	fn validate_leg_reach(&self, leg:&[usize], skeleton_leg:&RobotSkeletonLeg) -> Vec<ConfigDiagnostic> {
+
+		// The step moves the endpoint over the full step distance and lifts it halfway through the step height.
+		let step_distance:f32 = *skeleton_leg.step_distance();
+		let step_height:f32 = *skeleton_leg.step_height() * 0.5;
+		let step_corners:[[f32; 3]; 4] = [[0.0, 0.0, 0.0], [0.0, step_distance, 0.0], [0.0, 0.0, step_height], [0.0, step_distance, step_height]];
+		let positions:Vec<[f32; 3]> = step_corners.iter().map(|corner| skeleton_leg.step_position().displaced(corner)).collect::<Vec<[f32; 3]>>();
+
+		self.validate_chain_reach(leg, skeleton_leg, &positions, "Step position", "Stepping")
+	}
+
+	/// Validate that a manipulator can reach its target and all waypoints of its path within the rotation ranges of its motors.
+	fn validate_manipulator_reach(&self, manipulator:&[usize], skeleton_chain:&RobotSkeletonLeg) -> Vec<ConfigDiagnostic> {
+		match self.parts()[manipulator[manipulator.len() - 1]].kinematics_config().as_ref().and_then(|kinematics| kinematics.manipulator().as_ref()) {
+			Some(manipulator_config) => [
+				self.validate_chain_reach(manipulator, skeleton_chain, &[*manipulator_config.target()], "Target", "Reaching the target"),
+				self.validate_chain_reach(manipulator, skeleton_chain, manipulator_config.path(), "Waypoint", "Following the path")
+			].concat(),
+			None => Vec::new()
+		}
+	}
+
+	/// Validate that the endpoint of a chain can reach each of the given positions within the rotation ranges of its motors. Only the first unreachable position is reported.
+	fn validate_chain_reach(&self, chain:&[usize], skeleton_chain:&RobotSkeletonLeg, positions:&[[f32; 3]], position_name:&str, action_name:&str) -> Vec<ConfigDiagnostic> {
+		let mut diagnostics:Vec<ConfigDiagnostic> = Vec::new();
+		let endpoint_index:usize = chain[chain.len() - 1];
+
+		// Check each position.
+		let mut reported_parts:Vec<usize> = Vec::new();
+		for position in positions {
+			match calculate_leg_rotations(skeleton_chain, *position) {
+				None => {
+					diagnostics.push(ConfigDiagnostic::new(DiagnosticSeverity::Error, Some(endpoint_index), &format!("{position_name} {position:?} is out of reach.")));
+					break;
+				},
+				Some(rotations) => {
+					for (part_index, rotation) in rotations {
+						if let Some(motor) = self.part(part_index).and_then(|part| part.motor().as_ref()) {
+							let [range_min, range_max] = *motor.rotation_range();
+							if (rotation < range_min || rotation > range_max) && !reported_parts.contains(&part_index) {
+								diagnostics.push(ConfigDiagnostic::new(DiagnosticSeverity::Warning, Some(part_index), &format!("{action_name} requires a rotation of {rotation:.1}, outside the motor range of {range_min} to {range_max}.")));
+								reported_parts.push(part_index);
+							}
+						}
+					}
+				}
+			}
+		}
+
+		diagnostics
+	}
+}
\ No newline at end of file
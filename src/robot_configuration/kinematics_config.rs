@@ -1,13 +1,15 @@
 use dynamic_data_storage::GeneralDataType;
-use std::error::Error;
+use super::ManipulatorConfig;
 
-#[derive(Clone)]
+#[derive(Clone, GeneralDataType)]
 pub struct KinematicsConfig {
 	leg_endpoint:[f32; 3],
 	step_position:[f32; 3],
 	step_distance:f32,
+	#[general_data_type(skip)]
 	step_distance_override:Option<f32>,
-	step_height:f32
+	step_height:f32,
+	manipulator:Option<ManipulatorConfig>
 }
 impl KinematicsConfig {
 	
@@ -20,7 +22,8 @@ impl KinematicsConfig {
 			step_position,
 			step_distance,
 			step_distance_override: None,
-			step_height
+			step_height,
+			manipulator: None
 		}
 	}
 
@@ -33,7 +36,7 @@ impl KinematicsConfig {
 
 	/* PROPERTY GETTER METHODS */
 
-	/// Return a reference to the leg_endpoint.
+	/// Return a reference to the leg_endpoint. For manipulators, this is the tool tip.
 	pub fn leg_endpoint(&self) -> &[f32; 3] {
 		&self.leg_endpoint
 	}
@@ -80,49 +83,14 @@ impl KinematicsConfig {
 	pub fn step_height_mut(&mut self) -> &mut f32 {
 		&mut self.step_height
 	}
-}
-
-impl GeneralDataType for KinematicsConfig {
-
-	/// Create a value of the implemented type from these bytes while removing the bytes required from the bytes list. Useful for parsing more advances structs.
-	fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, Box<dyn Error>> {
-		Ok(KinematicsConfig::new(
-			[
-				f32::from_bytes(&bytes.drain(..4).collect::<Vec<u8>>())?,
-				f32::from_bytes(&bytes.drain(..4).collect::<Vec<u8>>())?,
-				f32::from_bytes(&bytes.drain(..4).collect::<Vec<u8>>())?
-			],
-			[
-				f32::from_bytes(&bytes.drain(..4).collect::<Vec<u8>>())?,
-				f32::from_bytes(&bytes.drain(..4).collect::<Vec<u8>>())?,
-				f32::from_bytes(&bytes.drain(..4).collect::<Vec<u8>>())?
-			],
-			f32::from_bytes(&bytes.drain(..4).collect::<Vec<u8>>())?,
-			f32::from_bytes(&bytes.drain(..4).collect::<Vec<u8>>())?
-		))
-	}
-
-	/// Create a value of the implemented type from these bytes.
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> {
-		Self::from_bytes_consume(&mut bytes.to_vec())
-	}
 
-	/// Create a list of bytes from the value.
-	fn to_bytes(&self) -> Vec<u8> {
-		let mut bytes:Vec<u8> = Vec::new();
-		bytes.extend_from_slice(&self.leg_endpoint[0].to_bytes());
-		bytes.extend_from_slice(&self.leg_endpoint[1].to_bytes());
-		bytes.extend_from_slice(&self.leg_endpoint[2].to_bytes());
-		bytes.extend_from_slice(&self.step_position[0].to_bytes());
-		bytes.extend_from_slice(&self.step_position[1].to_bytes());
-		bytes.extend_from_slice(&self.step_position[2].to_bytes());
-		bytes.extend_from_slice(&self.step_distance.to_bytes());
-		bytes.extend_from_slice(&self.step_height.to_bytes());
-		bytes
+	/// Return a reference to the manipulator config. Chains with a manipulator config are driven by a target instead of the gait, their step configuration is unused.
+	pub fn manipulator(&self) -> &Option<ManipulatorConfig> {
+		&self.manipulator
 	}
 
-	/// Get the byte size of this type.
-	fn byte_size() -> usize {
-		32
+	/// Return a mutable reference to the manipulator config.
+	pub fn manipulator_mut(&mut self) -> &mut Option<ManipulatorConfig> {
+		&mut self.manipulator
 	}
 }
\ No newline at end of file
@@ -0,0 +1,85 @@
+use dynamic_data_storage::GeneralDataType;
+
+#[derive(Clone, GeneralDataType)]
+pub struct ManipulatorConfig {
+	target:[f32; 3],
+	path:Vec<[f32; 3]>,
+	path_duration:f32
+}
+impl ManipulatorConfig {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new instance.
+	pub fn new(target:[f32; 3], path:Vec<[f32; 3]>, path_duration:f32) -> ManipulatorConfig {
+		ManipulatorConfig {
+			target,
+			path,
+			path_duration
+		}
+	}
+
+	/// Create an empty instance.
+	pub fn empty() -> ManipulatorConfig {
+		ManipulatorConfig::new([0.0; 3], Vec::new(), 4.0)
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Get the position the end effector should be at after a specific amount of seconds. Without a path, this is always the target. With a path, the end effector moves through all waypoints and loops back to the first one.
+	pub fn target_at(&self, time:f32) -> [f32; 3] {
+		if self.path.is_empty() || self.path_duration <= 0.0 {
+			return self.target;
+		}
+
+		// Find the waypoints to interpolate between.
+		let progress:f32 = (time / self.path_duration).rem_euclid(1.0) * self.path.len() as f32;
+		let start_index:usize = (progress.floor() as usize).min(self.path.len() - 1);
+		let start:[f32; 3] = self.path[start_index];
+		let end:[f32; 3] = self.path[(start_index + 1) % self.path.len()];
+		let factor:f32 = progress - start_index as f32;
+
+		// Interpolate.
+		[
+			start[0] + (end[0] - start[0]) * factor,
+			start[1] + (end[1] - start[1]) * factor,
+			start[2] + (end[2] - start[2]) * factor
+		]
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Return a reference to the target, relative to the first motor of the chain.
+	pub fn target(&self) -> &[f32; 3] {
+		&self.target
+	}
+
+	/// Return a mutable reference to the target.
+	pub fn target_mut(&mut self) -> &mut [f32; 3] {
+		&mut self.target
+	}
+
+	/// Return a reference to the waypoints of the path, relative to the first motor of the chain.
+	pub fn path(&self) -> &Vec<[f32; 3]> {
+		&self.path
+	}
+
+	/// Return a mutable reference to the waypoints of the path.
+	pub fn path_mut(&mut self) -> &mut Vec<[f32; 3]> {
+		&mut self.path
+	}
+
+	/// Return a reference to the amount of seconds it takes to run through the full path.
+	pub fn path_duration(&self) -> &f32 {
+		&self.path_duration
+	}
+
+	/// Return a mutable reference to the path_duration.
+	pub fn path_duration_mut(&mut self) -> &mut f32 {
+		&mut self.path_duration
+	}
+}
\ No newline at end of file
@@ -2,12 +2,24 @@ pub static OBJ_PATH_MAX_LEN:usize = 256;
 
 // auto-publib 
 
+mod config_history;
+mod config_validation;
 mod kinematics_config;
-mod leg_config;
+mod manipulator_config;
 mod motor_config;
+mod part_config;
+mod part_symmetry;
+mod primitive_config;
 mod robot_config;
+mod robot_template;
 
+pub use config_history::*;
+pub use config_validation::*;
 pub use kinematics_config::*;
-pub use leg_config::*;
+pub use manipulator_config::*;
 pub use motor_config::*;
-pub use robot_config::*;
\ No newline at end of file
+pub use part_config::*;
+pub use part_symmetry::*;
+pub use primitive_config::*;
+pub use robot_config::*;
+pub use robot_template::*;
\ No newline at end of file
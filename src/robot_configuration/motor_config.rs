@@ -1,7 +1,6 @@
 use dynamic_data_storage::GeneralDataType;
-use std::error::Error;
 
-#[derive(Clone)]
+#[derive(Clone, GeneralDataType)]
 pub struct MotorConfig {
 	position:[f32; 3],
 	rotation_axis:u8,
@@ -82,44 +81,4 @@ impl MotorConfig {
 	pub fn current_rotation_mut(&mut self) -> &mut f32 {
 		&mut self.current_rotation
 	}
-}
-
-impl GeneralDataType for MotorConfig {
-
-	/// Create a value of the implemented type from these bytes.
-	fn from_bytes_inner(bytes:&[u8]) -> Result<Self, Box<dyn Error>> {
-		Ok(MotorConfig {
-			position: [
-				f32::from_bytes(&bytes[0..4])?,
-				f32::from_bytes(&bytes[4..8])?,
-				f32::from_bytes(&bytes[8..12])?
-			],
-			rotation_axis: u8::from_bytes(&bytes[12..13])?,
-			default_rotation: f32::from_bytes(&bytes[13..17])?,
-			rotation_range: [
-				f32::from_bytes(&bytes[17..21])?,
-				f32::from_bytes(&bytes[21..25])?
-			],
-			current_rotation: f32::from_bytes(&bytes[25..29])?
-		})
-	}
-
-	/// Create a list of bytes from the value.
-	fn to_bytes(&self) -> Vec<u8> {
-		let mut bytes:Vec<u8> = Vec::new();
-		bytes.extend_from_slice(&self.position[0].to_bytes());
-		bytes.extend_from_slice(&self.position[1].to_bytes());
-		bytes.extend_from_slice(&self.position[2].to_bytes());
-		bytes.extend_from_slice(&(self.rotation_axis).to_bytes());
-		bytes.extend_from_slice(&self.default_rotation.to_bytes());
-		bytes.extend_from_slice(&self.rotation_range[0].to_bytes());
-		bytes.extend_from_slice(&self.rotation_range[1].to_bytes());
-		bytes.extend_from_slice(&self.current_rotation.to_bytes());
-		bytes
-	}
-
-	/// Get the byte size of this type.
-	fn byte_size() -> usize {
-		29
-	}
 }
\ No newline at end of file
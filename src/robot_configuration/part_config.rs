@@ -0,0 +1,143 @@
+use super::{ MotorConfig, KinematicsConfig, PrimitiveConfig };
+use dynamic_data_storage::{ GeneralDataType, StorageError, consume_bytes, string_from_legacy_bytes };
+
+#[derive(Clone, GeneralDataType)]
+pub struct PartConfig {
+	name:String,
+	parent:Option<usize>,
+	obj:String,
+	position:[f32; 3],
+	rotation:[f32; 3],
+	motor:Option<MotorConfig>,
+	kinematics_config:Option<KinematicsConfig>,
+	primitive:Option<PrimitiveConfig>
+}
+impl PartConfig {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new instance.
+	pub fn new(name:&str, parent:Option<usize>, obj:&str) -> PartConfig {
+		PartConfig {
+			name: name.to_string(),
+			parent,
+			obj: obj.to_string(),
+			position: [0.0; 3],
+			rotation: [0.0; 3],
+			motor: None,
+			kinematics_config: None,
+			primitive: None
+		}
+	}
+
+	/// Create an empty instance.
+	pub fn empty() -> PartConfig {
+		PartConfig::new("", None, "")
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Return a reference to the name.
+	pub fn name(&self) -> &String {
+		&self.name
+	}
+
+	/// Return a mutable reference to the name.
+	pub fn name_mut(&mut self) -> &mut String {
+		&mut self.name
+	}
+
+	/// Return a reference to the index of the parent part.
+	pub fn parent(&self) -> &Option<usize> {
+		&self.parent
+	}
+
+	/// Return a mutable reference to the index of the parent part.
+	pub fn parent_mut(&mut self) -> &mut Option<usize> {
+		&mut self.parent
+	}
+
+	/// Return a reference to the obj.
+	pub fn obj(&self) -> &String {
+		&self.obj
+	}
+
+	/// Return a mutable reference to the obj.
+	pub fn obj_mut(&mut self) -> &mut String {
+		&mut self.obj
+	}
+
+	/// Return a reference to the position.
+	pub fn position(&self) -> &[f32; 3] {
+		&self.position
+	}
+
+	/// Return a mutable reference to the position.
+	pub fn position_mut(&mut self) -> &mut [f32; 3] {
+		&mut self.position
+	}
+
+	/// Return a reference to the rotation.
+	pub fn rotation(&self) -> &[f32; 3] {
+		&self.rotation
+	}
+
+	/// Return a mutable reference to the rotation.
+	pub fn rotation_mut(&mut self) -> &mut [f32; 3] {
+		&mut self.rotation
+	}
+
+	/// Return a reference to the motor.
+	pub fn motor(&self) -> &Option<MotorConfig> {
+		&self.motor
+	}
+
+	/// Return a mutable reference to the motor.
+	pub fn motor_mut(&mut self) -> &mut Option<MotorConfig> {
+		&mut self.motor
+	}
+
+	/// Return a reference to the kinematics_config. Parts with a kinematics config are the endpoints of legs.
+	pub fn kinematics_config(&self) -> &Option<KinematicsConfig> {
+		&self.kinematics_config
+	}
+
+	/// Return a mutable reference to the kinematics_config.
+	pub fn kinematics_config_mut(&mut self) -> &mut Option<KinematicsConfig> {
+		&mut self.kinematics_config
+	}
+
+	/// Return a reference to the primitive. A part with a primitive uses a generated mesh instead of its obj.
+	pub fn primitive(&self) -> &Option<PrimitiveConfig> {
+		&self.primitive
+	}
+
+	/// Return a mutable reference to the primitive.
+	pub fn primitive_mut(&mut self) -> &mut Option<PrimitiveConfig> {
+		&mut self.primitive
+	}
+
+
+
+	/* LEGACY PARSING METHODS */
+
+	/// Parse a joint stored in the layout used before parts were stored as a tree. That layout has no name and parent, those should be set afterwards.
+	pub(crate) fn from_legacy_joint_bytes_consume(bytes:&mut Vec<u8>) -> Result<PartConfig, StorageError> {
+		let mut part:PartConfig = PartConfig::empty();
+		part.obj = string_from_legacy_bytes(&consume_bytes::<String>(bytes, OBJ_LEN + 1)?);
+		part.position = <[f32; 3]>::from_bytes_consume(bytes)?;
+		part.rotation = <[f32; 3]>::from_bytes_consume(bytes)?;
+		part.motor = if u8::from_bytes_consume(bytes)? == 0 { None } else { Some(MotorConfig::from_bytes_consume(bytes)?) };
+		part.kinematics_config = if u8::from_bytes_consume(bytes)? == 0 { None } else {
+			let [leg_endpoint, step_position]:[[f32; 3]; 2] = <[[f32; 3]; 2]>::from_bytes_consume(bytes)?;
+			Some(KinematicsConfig::new(leg_endpoint, step_position, f32::from_bytes_consume(bytes)?, f32::from_bytes_consume(bytes)?))
+		};
+		Ok(part)
+	}
+}
+
+
+
+use super::OBJ_PATH_MAX_LEN as OBJ_LEN;
\ No newline at end of file
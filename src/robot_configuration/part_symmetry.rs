@@ -0,0 +1,109 @@
+use glass_panel::tridimensional::model::VertexMath;
+use super::{ PartConfig, RobotConfig };
+
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MirrorPlane {
+	XZ,
+	YZ
+}
+impl MirrorPlane {
+
+	/// Get the index of the axis that gets negated when mirroring across this plane.
+	pub fn normal_axis(&self) -> usize {
+		match self {
+			MirrorPlane::XZ => 1,
+			MirrorPlane::YZ => 0
+		}
+	}
+
+	/// Mirror a position across the plane.
+	fn mirror_position(&self, position:&mut [f32; 3]) {
+		position[self.normal_axis()] = negated(position[self.normal_axis()]);
+	}
+
+	/// Mirror a rotation across the plane. Rotations around the normal of the plane stay the same, rotations around the other axes are reversed.
+	fn mirror_rotation(&self, rotation:&mut [f32; 3]) {
+		for (axis, value) in rotation.iter_mut().enumerate() {
+			if axis != self.normal_axis() {
+				*value = negated(*value);
+			}
+		}
+	}
+}
+
+/// Negate a value without turning zero into negative zero, which would show up as '-0' in the UI.
+fn negated(value:f32) -> f32 {
+	0.0 - value
+}
+
+
+
+impl RobotConfig {
+
+	/* SYMMETRY METHODS */
+
+	/// Copy a part and all parts below it to the other side of a plane through the origin of its parent. Returns the index of the mirrored copy of the part.
+	pub fn mirror_part(&mut self, part_index:usize, plane:MirrorPlane) -> Option<usize> {
+		let copy_index:usize = self.duplicate_part(part_index)?;
+		for mirrored_index in self.subtree(copy_index) {
+			self.parts_mut()[mirrored_index].mirror(plane);
+		}
+		self.update_ui();
+		Some(copy_index)
+	}
+
+	/// Copy a part and all parts below it around the vertical axis of its parent, so that the given amount of evenly spaced instances exist including the original. Returns the indexes of the copies of the part.
+	pub fn duplicate_part_radially(&mut self, part_index:usize, count:usize) -> Vec<usize> {
+		let mut copies:Vec<usize> = Vec::new();
+		for instance in 1..count {
+			if let Some(copy_index) = self.duplicate_part(part_index) {
+				let angle:f32 = 360.0 / count as f32 * instance as f32;
+				let copy:&mut PartConfig = &mut self.parts_mut()[copy_index];
+				*copy.position_mut() = copy.position().rotated(&[0.0, 0.0, angle.to_radians()], &None);
+				copy.rotation_mut()[2] = (copy.rotation()[2] + angle) % 360.0;
+				copies.push(copy_index);
+			}
+		}
+		self.update_ui();
+		copies
+	}
+}
+
+
+
+impl PartConfig {
+
+	/// Mirror the placement of the part and the settings of its motor and kinematics across a plane. The mesh itself is not mirrored, a primitive is symmetric so only its center is.
+	fn mirror(&mut self, plane:MirrorPlane) {
+		plane.mirror_position(self.position_mut());
+		plane.mirror_rotation(self.rotation_mut());
+		if let Some(primitive) = self.primitive_mut() {
+			plane.mirror_position(primitive.center_mut());
+		}
+
+		// Mirror the motor. A motor that does not rotate around the normal of the plane turns the other way, so its rotations and range are negated.
+		if let Some(motor) = self.motor_mut() {
+			plane.mirror_position(motor.position_mut());
+			if *motor.rotation_axis() as usize != plane.normal_axis() {
+				let [range_min, range_max] = *motor.rotation_range();
+				*motor.rotation_range_mut() = [negated(range_max), negated(range_min)];
+				*motor.default_rotation_mut() = negated(*motor.default_rotation());
+				*motor.current_rotation_mut() = negated(*motor.current_rotation());
+			}
+		}
+
+		// Mirror the kinematics.
+		if let Some(kinematics) = self.kinematics_config_mut() {
+			plane.mirror_position(kinematics.leg_endpoint_mut());
+			plane.mirror_position(kinematics.step_position_mut());
+			if let Some(manipulator) = kinematics.manipulator_mut() {
+				plane.mirror_position(manipulator.target_mut());
+				for waypoint in manipulator.path_mut() {
+					plane.mirror_position(waypoint);
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,162 @@
+use dynamic_data_storage::GeneralDataType;
+use glass_panel::tridimensional::model::{ Mesh, Vertex };
+
+pub const PRIMITIVE_SEGMENTS:usize = 24;
+const DEFAULT_PRIMITIVE_SIZE:f32 = 20.0;
+
+#[derive(Clone, Copy, PartialEq, Debug, GeneralDataType)]
+pub enum PrimitiveShape {
+	Box { size:[f32; 3] },
+	Cylinder { radius:f32, length:f32, axis:u8 },
+	Capsule { radius:f32, length:f32, axis:u8 },
+	Sphere { radius:f32 }
+}
+impl PrimitiveShape {
+
+	/// Return a list of all shapes with default dimensions.
+	pub fn all() -> [PrimitiveShape; 4] {
+		[
+			PrimitiveShape::Box { size: [DEFAULT_PRIMITIVE_SIZE; 3] },
+			PrimitiveShape::Cylinder { radius: DEFAULT_PRIMITIVE_SIZE * 0.5, length: DEFAULT_PRIMITIVE_SIZE, axis: 2 },
+			PrimitiveShape::Capsule { radius: DEFAULT_PRIMITIVE_SIZE * 0.5, length: DEFAULT_PRIMITIVE_SIZE, axis: 2 },
+			PrimitiveShape::Sphere { radius: DEFAULT_PRIMITIVE_SIZE * 0.5 }
+		]
+	}
+
+	/// Return the display name of the shape.
+	pub fn name(&self) -> &'static str {
+		match self {
+			PrimitiveShape::Box { .. } => "Box",
+			PrimitiveShape::Cylinder { .. } => "Cylinder",
+			PrimitiveShape::Capsule { .. } => "Capsule",
+			PrimitiveShape::Sphere { .. } => "Sphere"
+		}
+	}
+
+	/// Return all dimensions of the shape, used to validate them.
+	pub fn dimensions(&self) -> Vec<f32> {
+		match self {
+			PrimitiveShape::Box { size } => size.to_vec(),
+			PrimitiveShape::Cylinder { radius, length, .. } => vec![*radius, *length],
+			PrimitiveShape::Capsule { radius, length, .. } => vec![*radius, *length],
+			PrimitiveShape::Sphere { radius } => vec![*radius]
+		}
+	}
+
+	/// Return a copy of the shape with all dimensions multiplied by the scale.
+	pub fn scaled(&self, scale:f32) -> PrimitiveShape {
+		match *self {
+			PrimitiveShape::Box { size } => PrimitiveShape::Box { size: size.map(|value| value * scale) },
+			PrimitiveShape::Cylinder { radius, length, axis } => PrimitiveShape::Cylinder { radius: radius * scale, length: length * scale, axis },
+			PrimitiveShape::Capsule { radius, length, axis } => PrimitiveShape::Capsule { radius: radius * scale, length: length * scale, axis },
+			PrimitiveShape::Sphere { radius } => PrimitiveShape::Sphere { radius: radius * scale }
+		}
+	}
+
+	/// Return the axis the shape is aligned with. Boxes and spheres are not aligned with an axis.
+	pub fn axis(&self) -> Option<u8> {
+		match self {
+			PrimitiveShape::Cylinder { axis, .. } | PrimitiveShape::Capsule { axis, .. } => Some(*axis),
+			_ => None
+		}
+	}
+
+	/// Return a mutable reference to the radius of the shape, if it has one.
+	pub fn radius_mut(&mut self) -> Option<&mut f32> {
+		match self {
+			PrimitiveShape::Cylinder { radius, .. } | PrimitiveShape::Capsule { radius, .. } | PrimitiveShape::Sphere { radius } => Some(radius),
+			PrimitiveShape::Box { .. } => None
+		}
+	}
+
+	/// Return a mutable reference to the length of the shape, if it has one.
+	pub fn length_mut(&mut self) -> Option<&mut f32> {
+		match self {
+			PrimitiveShape::Cylinder { length, .. } | PrimitiveShape::Capsule { length, .. } => Some(length),
+			_ => None
+		}
+	}
+
+	/// Return a mutable reference to the axis of the shape, if it has one.
+	pub fn axis_mut(&mut self) -> Option<&mut u8> {
+		match self {
+			PrimitiveShape::Cylinder { axis, .. } | PrimitiveShape::Capsule { axis, .. } => Some(axis),
+			_ => None
+		}
+	}
+
+	/// Return a mutable reference to the size of the shape, if it has one.
+	pub fn size_mut(&mut self) -> Option<&mut [f32; 3]> {
+		match self {
+			PrimitiveShape::Box { size } => Some(size),
+			_ => None
+		}
+	}
+}
+
+
+
+#[derive(Clone, PartialEq, Debug, GeneralDataType)]
+pub struct PrimitiveConfig {
+	shape:PrimitiveShape,
+	center:[f32; 3]
+}
+impl PrimitiveConfig {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new instance.
+	pub fn new(shape:PrimitiveShape, center:[f32; 3]) -> PrimitiveConfig {
+		PrimitiveConfig {
+			shape,
+			center
+		}
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Generate the mesh of the primitive. Cylinders and capsules are generated around the Z axis, so their vertices are cycled to align them with their own axis.
+	pub fn mesh(&self) -> Mesh {
+		let mesh:Mesh = match self.shape {
+			PrimitiveShape::Box { size } => Mesh::cuboid(&size),
+			PrimitiveShape::Cylinder { radius, length, .. } => Mesh::cylinder(radius, length, PRIMITIVE_SEGMENTS),
+			PrimitiveShape::Capsule { radius, length, .. } => Mesh::capsule(radius, length, PRIMITIVE_SEGMENTS),
+			PrimitiveShape::Sphere { radius } => Mesh::sphere(radius, PRIMITIVE_SEGMENTS)
+		};
+		let axis:usize = self.shape.axis().unwrap_or(2) as usize % 3;
+		let vertices:Vec<Vertex> = mesh.vertices().iter().map(|vertex| {
+			let mut aligned:Vertex = [0.0; 3];
+			for source_axis in 0..3 {
+				aligned[(axis + 1 + source_axis) % 3] = vertex[source_axis] + self.center[(axis + 1 + source_axis) % 3];
+			}
+			aligned
+		}).collect::<Vec<Vertex>>();
+		Mesh::raw(vertices, mesh.faces().iter().map(|face| *face.vertex_indexes()).collect::<Vec<[usize; 3]>>())
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Return a reference to the shape.
+	pub fn shape(&self) -> &PrimitiveShape {
+		&self.shape
+	}
+
+	/// Return a mutable reference to the shape.
+	pub fn shape_mut(&mut self) -> &mut PrimitiveShape {
+		&mut self.shape
+	}
+
+	/// Return a reference to the center, relative to the origin of the part.
+	pub fn center(&self) -> &[f32; 3] {
+		&self.center
+	}
+
+	/// Return a mutable reference to the center.
+	pub fn center_mut(&mut self) -> &mut [f32; 3] {
+		&mut self.center
+	}
+}
\ No newline at end of file
@@ -1,13 +1,14 @@
-use dynamic_data_storage::GeneralDataType;
-use std::error::Error;
-use super::LegConfig;
+use dynamic_data_storage::{ GeneralDataType, StorageError, StorageManager, consume_bytes, string_from_legacy_bytes };
+use std::path::Path;
+use super::{ ConfigHistory, PartConfig, PrimitiveConfig };
 
 
 
 static mut STATIC_CONFIG_INSTANCE:Option<RobotConfig> = None;
+#[derive(GeneralDataType)]
+#[general_data_type(schema_version = 1)]
 pub struct RobotConfig {
-	body:Option<String>,
-	legs:Vec<Vec<LegConfig>>
+	parts:Vec<PartConfig>
 }
 impl RobotConfig {
 
@@ -15,25 +16,67 @@ impl RobotConfig {
 
 	/// Create an empty config and store it in the static instance.
 	pub fn create() {
-		unsafe { STATIC_CONFIG_INSTANCE = Some(RobotConfig { body: None, legs: Vec::new() }) }
+		unsafe { STATIC_CONFIG_INSTANCE = Some(RobotConfig { parts: Vec::new() }) }
+		ConfigHistory::clear();
 	}
 
-	/// Create a config from the current project files.
-	pub fn load() {
-		unsafe {
-			STATIC_CONFIG_INSTANCE = Some(
-				match dynamic_data_storage::StorageManager::get_mut().get_value::<RobotConfig>("project_robot_config", "robot_config") {
-					Ok(config) => config,
-					Err(_) => RobotConfig { body: None, legs: Vec::new() }
-				}
-			);
-			RobotConfig::get().update_ui();
-		}
+	/// Create a config from the current project files. A project without config gets an empty config. When the stored config cannot be read, an empty config is used as well, but the error is returned.
+	pub fn load() -> Result<(), StorageError> {
+		let stored_config:Result<RobotConfig, StorageError> = StorageManager::get().get_value::<RobotConfig>("project_robot_config", "robot_config");
+		let (config, result) = match stored_config {
+			Ok(config) => (config, Ok(())),
+			Err(error) if error.is_not_found() => (RobotConfig { parts: Vec::new() }, Ok(())),
+			Err(error) => (RobotConfig { parts: Vec::new() }, Err(error))
+		};
+		unsafe { STATIC_CONFIG_INSTANCE = Some(config); }
+		ConfigHistory::clear();
+		RobotConfig::get().update_ui();
+		result
 	}
 
-	/// Save the robot config to the assigned file.
+	/// Save the robot config to the assigned file and update the thumbnail of the project. Also stores any other pending changes, as saving is an explicit request of the user.
 	pub fn save() {
-		let _ = dynamic_data_storage::StorageManager::get_mut().set_value::<RobotConfig>("project_robot_config", "robot_config", RobotConfig::get());
+		let _ = StorageManager::get_mut().set_value::<RobotConfig>("project_robot_config", "robot_config", RobotConfig::get());
+		if let Err(error) = crate::storage::update_project_thumbnail() {
+			eprintln!("Could not render project thumbnail: {error}");
+		}
+		if let Err(error) = StorageManager::get_mut().flush() {
+			eprintln!("Could not save robot config: {error}");
+		}
+	}
+
+	/// Keep the config in sync with the storage. When the stored config is changed by anything other than saving, like importing a project, the config is reloaded and the UI is updated. Changes can be made on any thread, so with a window the reload is handed to the UI thread.
+	pub fn watch_storage() {
+		use crate::ui::Window;
+
+		StorageManager::get_mut().subscribe("project_robot_config", Some("robot_config"), |_| {
+			if Window::instance_available() {
+				Window::execute_on_ui_thread(RobotConfig::reload_if_changed);
+			} else {
+				RobotConfig::reload_if_changed();
+			}
+		});
+	}
+
+	/// Replace the config by the one in storage if they differ.
+	fn reload_if_changed() {
+		let stored_config:Option<RobotConfig> = StorageManager::get().get_value::<RobotConfig>("project_robot_config", "robot_config").ok();
+		if let Some(config) = stored_config {
+			if config.to_bytes() != RobotConfig::get().to_bytes() {
+				RobotConfig::replace(config);
+			}
+		}
+	}
+
+	/// Replace the static config by another config, like an earlier version of itself, and update the UI.
+	pub fn replace(config:RobotConfig) {
+		unsafe { STATIC_CONFIG_INSTANCE = Some(config); }
+		RobotConfig::get().update_ui();
+	}
+
+	/// Create a config from a list of parts without storing it in the static instance, like a config created by an importer.
+	pub fn from_parts(parts:Vec<PartConfig>) -> RobotConfig {
+		RobotConfig { parts }
 	}
 
 
@@ -62,96 +105,242 @@ impl RobotConfig {
 		}
 	}
 
-	
+
 
 	/* PROPERTY GETTER METHODS */
 
-	/// Get the body configuration.
-	pub fn body(&self) -> &Option<String> {
-		&self.body
+	/// Get all parts of the robot.
+	pub fn parts(&self) -> &Vec<PartConfig> {
+		&self.parts
 	}
 
-	/// Get the body configuration.
-	pub fn body_mut(&mut self) -> &mut Option<String> {
-		&mut self.body
+	/// Get all parts of the robot mutable.
+	pub fn parts_mut(&mut self) -> &mut Vec<PartConfig> {
+		&mut self.parts
 	}
 
-	/// Get the legs' configuration.
-	pub fn legs(&self) -> &Vec<Vec<LegConfig>> {
-		&self.legs
+	/// Get a part.
+	pub fn part(&self, part_index:usize) -> Option<&PartConfig> {
+		self.parts.get(part_index)
 	}
 
-	/// Get the legs' configuration mutable.
-	pub fn legs_mut(&mut self) -> &mut Vec<Vec<LegConfig>> {
-		&mut self.legs
+	/// Get a part mutable.
+	pub fn part_mut(&mut self, part_index:usize) -> Option<&mut PartConfig> {
+		self.parts.get_mut(part_index)
 	}
 
-	/// Get a joint.
-	pub fn get_joint(&self, leg_index:usize, joint_index:usize) -> Option<&LegConfig> {
-		if leg_index < self.legs.len() && joint_index < self.legs[leg_index].len() {
-			Some(&self.legs[leg_index][joint_index])
-		} else {
-			None
+	/// Get the index of a part by its name.
+	pub fn part_index_by_name(&self, name:&str) -> Option<usize> {
+		self.parts.iter().position(|part| part.name() == name)
+	}
+
+
+
+	/* TREE STRUCTURE METHODS */
+
+	/// Get the indexes of all parts without a parent.
+	pub fn roots(&self) -> Vec<usize> {
+		(0..self.parts.len()).filter(|part_index| self.parts[*part_index].parent().is_none()).collect::<Vec<usize>>()
+	}
+
+	/// Get the indexes of all direct children of a part.
+	pub fn children(&self, part_index:usize) -> Vec<usize> {
+		(0..self.parts.len()).filter(|child_index| self.parts[*child_index].parent() == &Some(part_index)).collect::<Vec<usize>>()
+	}
+
+	/// Get the indexes of a part and all parts below it.
+	pub fn subtree(&self, part_index:usize) -> Vec<usize> {
+		let mut subtree:Vec<usize> = vec![part_index];
+		let mut cursor:usize = 0;
+		while cursor < subtree.len() {
+			for child_index in self.children(subtree[cursor]) {
+				if !subtree.contains(&child_index) {
+					subtree.push(child_index);
+				}
+			}
+			cursor += 1;
 		}
+		subtree
 	}
 
-	/// Get a joint mutable.
-	pub fn get_joint_mut(&mut self, leg_index:usize, joint_index:usize) -> Option<&mut LegConfig> {
-		if leg_index < self.legs.len() && joint_index < self.legs[leg_index].len() {
-			Some(&mut self.legs[leg_index][joint_index])
-		} else {
-			None
+	/// Get the chain of part indexes from the root of the tree down to the given part.
+	pub fn chain(&self, part_index:usize) -> Vec<usize> {
+		let mut chain:Vec<usize> = Vec::new();
+		let mut cursor:Option<usize> = Some(part_index).filter(|part_index| *part_index < self.parts.len());
+		while let Some(current_index) = cursor {
+			if chain.contains(&current_index) {
+				break;
+			}
+			chain.insert(0, current_index);
+			cursor = *self.parts[current_index].parent();
 		}
+		chain
+	}
+
+	/// Get the depth of a part in the tree. Root parts have a depth of 0.
+	pub fn depth(&self, part_index:usize) -> usize {
+		self.chain(part_index).len().max(1) - 1
+	}
+
+	/// Get all parts in the order they appear in the tree, with their depth.
+	pub fn tree_order(&self) -> Vec<(usize, usize)> {
+		let mut order:Vec<(usize, usize)> = Vec::new();
+		let mut stack:Vec<(usize, usize)> = self.roots().iter().rev().map(|root| (*root, 0)).collect::<Vec<(usize, usize)>>();
+		while let Some((part_index, depth)) = stack.pop() {
+			order.push((part_index, depth));
+			stack.extend(self.children(part_index).iter().rev().map(|child| (*child, depth + 1)));
+		}
+		order
+	}
+
+	/// Get the legs of the robot. A leg is the chain of parts from the root to a part with a kinematics config that is not a manipulator.
+	pub fn legs(&self) -> Vec<Vec<usize>> {
+		(0..self.parts.len()).filter(|part_index| self.parts[*part_index].kinematics_config().as_ref().is_some_and(|kinematics| kinematics.manipulator().is_none())).map(|part_index| self.chain(part_index)).collect::<Vec<Vec<usize>>>()
+	}
+
+	/// Get the manipulators of the robot. A manipulator is the chain of parts from the root to a part with a manipulator config, it does not take part in the gait.
+	pub fn manipulators(&self) -> Vec<Vec<usize>> {
+		(0..self.parts.len()).filter(|part_index| self.parts[*part_index].kinematics_config().as_ref().is_some_and(|kinematics| kinematics.manipulator().is_some())).map(|part_index| self.chain(part_index)).collect::<Vec<Vec<usize>>>()
+	}
+
+
+
+	/* MESH METHODS */
+
+	/// Get the distinct meshes used by parts that could not be found.
+	pub fn missing_meshes(&self) -> Vec<String> {
+		let mut missing_meshes:Vec<String> = Vec::new();
+		for part in &self.parts {
+			if !part.obj().is_empty() && !missing_meshes.iter().any(|mesh| mesh == part.obj()) && !Path::new(&crate::storage::asset_path(part.obj())).is_file() {
+				missing_meshes.push(part.obj().to_string());
+			}
+		}
+		missing_meshes
+	}
+
+	/// Replace a missing mesh by another file for all parts using it. Other missing meshes with a file of the same name in the directory of the new file are relinked as well. Returns the amount of relinked meshes.
+	pub fn relink_mesh(&mut self, missing_mesh:&str, path:&str) -> usize {
+		let search_dir:Option<&Path> = Path::new(path).parent();
+		let mut relinked_meshes:Vec<(String, String)> = vec![(missing_mesh.to_string(), path.to_string())];
+		for mesh in self.missing_meshes() {
+			if mesh == missing_mesh {
+				continue;
+			}
+			let candidate:Option<String> = Path::new(&mesh).file_name().zip(search_dir).map(|(file_name, dir)| dir.join(file_name).display().to_string().replace('\\', "/"));
+			if let Some(candidate) = candidate.filter(|candidate| Path::new(candidate).is_file()) {
+				relinked_meshes.push((mesh, candidate));
+			}
+		}
+		for part in &mut self.parts {
+			if let Some((_, new_mesh)) = relinked_meshes.iter().find(|(mesh, _)| mesh == part.obj()) {
+				*part.obj_mut() = new_mesh.to_string();
+			}
+		}
+		self.update_ui();
+		relinked_meshes.len()
 	}
 
 
 
 	/* MODIFIER METHODS */
 
-	/// Add a leg.
-	pub fn add_leg(&mut self, path:&str) {
-		self.add_joint(self.legs.len(), path);
+	/// Add a part with the given mesh as a child of the given parent. Returns the index of the new part.
+	pub fn add_part(&mut self, parent:Option<usize>, path:&str) -> usize {
+		let name:String = self.unique_part_name(Path::new(path).file_stem().map(|stem| stem.to_string_lossy().to_string()).as_deref().unwrap_or("Part"));
+		self.parts.push(PartConfig::new(&name, parent.filter(|parent| *parent < self.parts.len()), path));
+		self.update_ui();
+		self.parts.len() - 1
 	}
 
-	/// Add a joint.
-	pub fn add_joint(&mut self, leg_index:usize, path:&str) {
-		while leg_index >= self.legs.len() {
-			self.legs.push(Vec::new());
+	/// Set the mesh of a part. Replaces the primitive of the part, if it had one.
+	pub fn set_part_obj(&mut self, part_index:usize, path:&str) {
+		if let Some(part) = self.parts.get_mut(part_index) {
+			*part.obj_mut() = path.to_string();
+			*part.primitive_mut() = None;
 		}
-		self.set_joint(leg_index, self.legs[leg_index].len(), path);
+		self.update_ui();
 	}
 
-	/// Set a joint.
-	pub fn set_joint(&mut self, leg_index:usize, joint_index:usize, path:&str) {
-		while leg_index >= self.legs.len() {
-			self.legs.push(Vec::new());
+	/// Set the primitive of a part. A primitive replaces the mesh file of the part, removing the primitive leaves the part without mesh.
+	pub fn set_part_primitive(&mut self, part_index:usize, primitive:Option<PrimitiveConfig>) {
+		if let Some(part) = self.parts.get_mut(part_index) {
+			if primitive.is_some() {
+				part.obj_mut().clear();
+			}
+			*part.primitive_mut() = primitive;
 		}
-		while joint_index >= self.legs[leg_index].len() {
-			self.legs[leg_index].push(LegConfig::empty());
+		self.update_ui();
+	}
+
+	/// Attach a part to a new parent. Returns false if this would make the part a child of itself.
+	pub fn set_parent(&mut self, part_index:usize, parent:Option<usize>) -> bool {
+		if part_index >= self.parts.len() || parent.map(|parent| parent >= self.parts.len() || self.subtree(part_index).contains(&parent)).unwrap_or(false) {
+			return false;
 		}
-		*self.legs[leg_index][joint_index].obj_mut() = path.to_string();
+		*self.parts[part_index].parent_mut() = parent;
 		self.update_ui();
+		true
 	}
 
-	/// Remove a leg.
-	pub fn remove_leg(&mut self, leg_index:usize) {
-		if self.legs.len() > leg_index {
-			self.legs.remove(leg_index);
+	/// Duplicate a part and all parts below it, attached to the same parent. Returns the index of the new copy of the part.
+	pub fn duplicate_part(&mut self, part_index:usize) -> Option<usize> {
+		if part_index >= self.parts.len() {
+			return None;
+		}
+
+		// Copy parts, remapping parents within the subtree to the copies.
+		let subtree:Vec<usize> = self.subtree(part_index);
+		let first_new_index:usize = self.parts.len();
+		for original_index in &subtree {
+			let mut copy:PartConfig = self.parts[*original_index].clone();
+			*copy.name_mut() = self.unique_part_name(copy.name());
+			if *original_index != part_index {
+				*copy.parent_mut() = copy.parent().and_then(|parent| subtree.iter().position(|index| *index == parent)).map(|position| first_new_index + position);
+			}
+			self.parts.push(copy);
 		}
 		self.update_ui();
+		Some(first_new_index)
 	}
 
-	/// Remove a leg.
-	pub fn remove_joint(&mut self, leg_index:usize, joint_index:usize) {
-		if self.legs.len() > leg_index && self.legs[leg_index].len() > joint_index {
-			self.legs[leg_index].remove(joint_index);
-			if self.legs[leg_index].is_empty() {
-				self.legs.remove(leg_index);
+	/// Remove a part and all parts below it.
+	pub fn remove_part(&mut self, part_index:usize) {
+		if part_index < self.parts.len() {
+			let removed:Vec<usize> = self.subtree(part_index);
+
+			// Map old indexes to new indexes.
+			let mut new_indexes:Vec<Option<usize>> = Vec::new();
+			let mut kept_count:usize = 0;
+			for index in 0..self.parts.len() {
+				if removed.contains(&index) {
+					new_indexes.push(None);
+				} else {
+					new_indexes.push(Some(kept_count));
+					kept_count += 1;
+				}
+			}
+
+			// Remove parts and remap parents.
+			let mut index:usize = 0;
+			self.parts.retain(|_| { index += 1; new_indexes[index - 1].is_some() });
+			for part in &mut self.parts {
+				*part.parent_mut() = part.parent().and_then(|parent| new_indexes[parent]);
 			}
 		}
 		self.update_ui();
 	}
 
+	/// Create a name based on the given name that is not yet used by any part.
+	pub fn unique_part_name(&self, name:&str) -> String {
+		let mut unique_name:String = name.to_string();
+		let mut counter:usize = 2;
+		while self.part_index_by_name(&unique_name).is_some() {
+			unique_name = format!("{name} {counter}");
+			counter += 1;
+		}
+		unique_name
+	}
+
 
 
 	/* UI UPDATING METHODS */
@@ -160,68 +349,53 @@ impl RobotConfig {
 	pub fn update_ui(&self) {
 
 		// TODO: This does not really belong here.
-		crate::ui::Window::get().update_robot_config_synchronized();
+		if crate::ui::Window::instance_available() {
+			crate::ui::Window::get().update_robot_config_synchronized();
+		}
 	}
-}
 
 
 
-use super::OBJ_PATH_MAX_LEN as OBJ_LEN;
+	/* MIGRATION METHODS */
 
-impl GeneralDataType for RobotConfig {
+	/// Register the parsers that read robot configs stored with an older schema version. Should be called once at startup, before any project is loaded.
+	pub fn register_migrations() {
+		dynamic_data_storage::MigrationRegistry::get_mut().register_parser::<RobotConfig>(0, RobotConfig::from_legacy_bytes_consume);
+	}
+
+	/// Parse a config stored in the layout used before parts were stored as a tree. That layout has an optional body with a list of serial joint chains as legs.
+	fn from_legacy_bytes_consume(bytes:&mut Vec<u8>) -> Result<RobotConfig, StorageError> {
+		let mut parts:Vec<PartConfig> = Vec::new();
+
+		// Parse body.
+		let body_index:Option<usize> = if u8::from_bytes_consume(bytes)? == LEGACY_LAYOUT_NO_BODY {
+			None
+		} else {
+			parts.push(PartConfig::new("Body", None, &string_from_legacy_bytes(&consume_bytes::<String>(bytes, OBJ_LEN + 1)?)));
+			Some(0)
+		};
 
-	/// Create a value of the implemented type from these bytes while removing the bytes required from the bytes list. Useful for parsing more advances structs.
-	fn from_bytes_consume(bytes:&mut Vec<u8>) -> Result<Self, Box<dyn Error>> {
-		let body:Option<String> = if bytes.remove(0) == 0 { None } else { Some(String::from_bytes(&bytes.drain(..OBJ_LEN + 1).collect::<Vec<u8>>())?) };
-		let mut legs:Vec<Vec<LegConfig>> = Vec::new();
-		let leg_count:usize = u16::from_bytes(&bytes.drain(..2).collect::<Vec<u8>>())? as usize;
+		// Parse legs as chains of parts attached to the body.
+		let leg_count:usize = u16::from_bytes_consume(bytes)? as usize;
 		for leg_index in 0..leg_count {
-			legs.push(Vec::new());
-			let joint_count:usize = u16::from_bytes(&bytes.drain(..2).collect::<Vec<u8>>())? as usize;
-			for _ in 0..joint_count {
-				legs[leg_index].push(LegConfig::from_bytes_consume(bytes)?);
+			let joint_count:usize = u16::from_bytes_consume(bytes)? as usize;
+			let mut parent:Option<usize> = body_index;
+			for joint_index in 0..joint_count {
+				let mut joint:PartConfig = PartConfig::from_legacy_joint_bytes_consume(bytes)?;
+				*joint.name_mut() = format!("Leg{leg_index} Joint{joint_index}");
+				*joint.parent_mut() = parent;
+				parent = Some(parts.len());
+				parts.push(joint);
 			}
 		}
-		Ok(RobotConfig {
-			body,
-			legs
-		})
-	}
-
-	/// Create a value of the implemented type from these bytes.
-	fn from_bytes(bytes:&[u8]) -> Result<Self, Box<dyn Error>> {
-		Self::from_bytes_consume(&mut bytes.to_vec())
-	}
 
-	/// Create a value of the implemented type from these bytes.
-	fn from_bytes_inner(_:&[u8]) -> Result<Self, Box<dyn Error>> {
-		panic!("Should not get LegConfig from bytes due to not having a set byte size, please use from_bytes_consume");
+		// This layout was never checked for unused bytes, so ignore them like before.
+		bytes.clear();
+		Ok(RobotConfig { parts })
 	}
+}
 
-	/// Create a list of bytes from the value.
-	fn to_bytes(&self) -> Vec<u8> {
 
-		// Validate obj path len.
-		let obj_len:usize = self.body.as_ref().map(|body| body.len()).unwrap_or(0);
-		if obj_len > OBJ_LEN {
-			panic!("Could not store leg config, name '{}' is too long.", self.body.as_ref().unwrap());
-		}
 
-		// Create bytes.
-		let mut bytes:Vec<u8> = Vec::new();
-		bytes.extend_from_slice(&match &self.body() { Some(body) => [vec![1], body.to_bytes().to_vec(), vec![0u8; OBJ_LEN - obj_len]].iter().flatten().copied().collect::<Vec<u8>>(), None => vec![0] });
-		bytes.extend_from_slice(&(self.legs.len() as u16).to_bytes());
-		for leg in &self.legs {
-			bytes.extend_from_slice(&(leg.len() as u16).to_bytes());
-			for joint in leg {
-				bytes.extend_from_slice(&joint.to_bytes());
-			}
-		}
-		bytes
-	}
-
-	/// Get the byte size of this type.
-	fn byte_size() -> usize {
-		0
-	}
-}
\ No newline at end of file
+use super::OBJ_PATH_MAX_LEN as OBJ_LEN;
+const LEGACY_LAYOUT_NO_BODY:u8 = 0;
\ No newline at end of file
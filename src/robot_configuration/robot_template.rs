@@ -0,0 +1,223 @@
+use std::{ error::Error, f32::consts::PI };
+use super::{ KinematicsConfig, MotorConfig, PartConfig, PrimitiveConfig, PrimitiveShape, RobotConfig };
+
+const TEMPLATE_BODY_THICKNESS:f32 = 20.0;
+const TEMPLATE_SEGMENT_THICKNESS:f32 = 10.0;
+const TEMPLATE_HIP_RANGE:[f32; 2] = [-135.0, 135.0];
+const TEMPLATE_JOINT_RANGE:[f32; 2] = [-150.0, 150.0];
+
+
+
+// A common robot layout that can be generated with a few parameters, so a new project does not have to be built part by part.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RobotTemplate {
+	Hexapod,
+	Quadruped,
+	TwoJointLeg,
+	ThreeJointLeg
+}
+impl RobotTemplate {
+
+	/* PROPERTY GETTER METHODS */
+
+	/// All templates.
+	pub fn all() -> [RobotTemplate; 4] {
+		[RobotTemplate::Hexapod, RobotTemplate::Quadruped, RobotTemplate::TwoJointLeg, RobotTemplate::ThreeJointLeg]
+	}
+
+	/// Find a template by its identifier.
+	pub fn from_id(id:&str) -> Option<RobotTemplate> {
+		RobotTemplate::all().into_iter().find(|template| template.id() == id)
+	}
+
+	/// The identifier of the template, used on the command-line.
+	pub fn id(&self) -> &'static str {
+		match self {
+			RobotTemplate::Hexapod => "hexapod",
+			RobotTemplate::Quadruped => "quadruped",
+			RobotTemplate::TwoJointLeg => "leg-2dof",
+			RobotTemplate::ThreeJointLeg => "leg-3dof"
+		}
+	}
+
+	/// The name of the template as shown to the user.
+	pub fn name(&self) -> &'static str {
+		match self {
+			RobotTemplate::Hexapod => "Hexapod",
+			RobotTemplate::Quadruped => "Quadruped",
+			RobotTemplate::TwoJointLeg => "2-DOF leg",
+			RobotTemplate::ThreeJointLeg => "3-DOF leg"
+		}
+	}
+
+	/// The amount of motors in each leg. Legs with two motors swing forwards and backwards, legs with three motors also have a hip turning sideways.
+	pub fn leg_motor_count(&self) -> usize {
+		if *self == RobotTemplate::TwoJointLeg { 2 } else { 3 }
+	}
+
+	/// Get the parameters the template is generated with by default.
+	pub fn default_parameters(&self) -> TemplateParameters {
+		match self {
+			RobotTemplate::Hexapod => TemplateParameters::new(6, vec![30.0, 60.0, 80.0], 60.0),
+			RobotTemplate::Quadruped => TemplateParameters::new(4, vec![30.0, 70.0, 90.0], 80.0),
+			RobotTemplate::TwoJointLeg => TemplateParameters::new(1, vec![70.0, 70.0], 30.0),
+			RobotTemplate::ThreeJointLeg => TemplateParameters::new(1, vec![30.0, 60.0, 80.0], 30.0)
+		}
+	}
+
+
+
+	/* GENERATION METHODS */
+
+	/// Create a new project with a robot generated from the template and open it. The name of the template is used when no name is given. Returns the name of the new project.
+	pub fn create_project(&self, name:Option<&str>, parameters:&TemplateParameters) -> Result<String, Box<dyn Error>> {
+		use crate::storage;
+
+		let robot_config:RobotConfig = self.generate(parameters)?;
+		let project_name:String = storage::create_project(name.unwrap_or(self.name()))?;
+		RobotConfig::replace(robot_config);
+		RobotConfig::save();
+		Ok(project_name)
+	}
+
+	/// Generate a robot from the template. All parts are primitives, so the robot can be replaced by real meshes part by part. The robot walks forwards over the Y axis, with Z pointing up.
+	pub fn generate(&self, parameters:&TemplateParameters) -> Result<RobotConfig, Box<dyn Error>> {
+		parameters.validate(self.leg_motor_count())?;
+		let leg_count:usize = parameters.leg_count;
+		let hip_radius:f32 = parameters.hip_radius;
+
+		// Find the position of each hip and the direction its leg points in.
+		let hips:Vec<([f32; 3], [f32; 2])> = match self {
+			RobotTemplate::Quadruped => {
+
+				// Legs are spread over both sides of a body twice as long as it is wide. The hips in the corners are at the hip radius from the center.
+				let half_width:f32 = hip_radius / 5.0_f32.sqrt();
+				let legs_per_side:usize = leg_count.div_ceil(2);
+				(0..leg_count).map(|leg_index| {
+					let side:f32 = if leg_index % 2 == 0 { 1.0 } else { -1.0 };
+					let progress:f32 = if legs_per_side > 1 { (leg_index / 2) as f32 / (legs_per_side - 1) as f32 } else { 0.5 };
+					([side * half_width, (1.0 - progress * 2.0) * half_width * 2.0, 0.0], [side, 0.0])
+				}).collect()
+			},
+			_ => (0..leg_count).map(|leg_index| {
+				let angle:f32 = PI * 2.0 / leg_count as f32 * leg_index as f32;
+				([angle.cos() * hip_radius, angle.sin() * hip_radius, 0.0], [angle.cos(), angle.sin()])
+			}).collect()
+		};
+
+		// Create the body.
+		let body_shape:PrimitiveShape = match self {
+			RobotTemplate::Quadruped => PrimitiveShape::Box { size: [hip_radius / 5.0_f32.sqrt() * 2.0, hip_radius / 5.0_f32.sqrt() * 4.0, TEMPLATE_BODY_THICKNESS] },
+			_ => PrimitiveShape::Cylinder { radius: hip_radius, length: TEMPLATE_BODY_THICKNESS, axis: 2 }
+		};
+		let mut body:PartConfig = PartConfig::new(if matches!(self, RobotTemplate::Hexapod | RobotTemplate::Quadruped) { "Body" } else { "Mount" }, None, "");
+		*body.primitive_mut() = Some(PrimitiveConfig::new(body_shape, [0.0; 3]));
+		let mut parts:Vec<PartConfig> = vec![body];
+
+		// Create the legs.
+		for (leg_index, (hip_position, outward)) in hips.into_iter().enumerate() {
+			let leg_name:String = format!("Leg {}", leg_index + 1);
+			let lengths:&Vec<f32> = &parameters.segment_lengths;
+
+			// Legs point forwards or backwards, the hip motor turns them outwards. Legs without hip motor swing in the direction of walking.
+			let direction:f32 = if self.leg_motor_count() == 3 && outward[1] < -0.001 { -1.0 } else { 1.0 };
+			let (segment_names, step_position) = if self.leg_motor_count() == 3 {
+				let reach:f32 = lengths[0] + lengths[1] * 0.8;
+				(vec!["hip", "femur", "tibia"], [outward[0] * reach, outward[1] * reach - lengths[1] * 0.25, -lengths[2] * 0.8])
+			} else {
+				let total_length:f32 = lengths[0] + lengths[1];
+				(vec!["thigh", "shin"], [0.0, total_length * 0.05, -total_length * 0.7])
+			};
+			let step_distance:f32 = if self.leg_motor_count() == 3 { lengths[1] * 0.5 } else { (lengths[0] + lengths[1]) * 0.3 };
+			let step_height:f32 = lengths[lengths.len() - 1] * 0.3;
+
+			// Create a part per segment, each holding the motor at its start.
+			for (segment_index, length) in lengths.iter().enumerate() {
+				let parent:usize = if segment_index == 0 { 0 } else { parts.len() - 1 };
+				let mut part:PartConfig = PartConfig::new(&format!("{leg_name} {}", segment_names[segment_index]), Some(parent), "");
+				let segment_shape:PrimitiveShape = PrimitiveShape::Box { size: [TEMPLATE_SEGMENT_THICKNESS, length + TEMPLATE_SEGMENT_THICKNESS, TEMPLATE_SEGMENT_THICKNESS] };
+				*part.primitive_mut() = Some(PrimitiveConfig::new(segment_shape, [0.0, length * 0.5 * direction, 0.0]));
+				*part.position_mut() = if segment_index == 0 { hip_position } else { [0.0, lengths[segment_index - 1] * direction, 0.0] };
+				let is_hip:bool = self.leg_motor_count() == 3 && segment_index == 0;
+				*part.motor_mut() = Some(MotorConfig::new([0.0; 3], if is_hip { 2 } else { 0 }, 0.0, if is_hip { TEMPLATE_HIP_RANGE } else { TEMPLATE_JOINT_RANGE }));
+				if segment_index == lengths.len() - 1 {
+					*part.kinematics_config_mut() = Some(KinematicsConfig::new([0.0, length * direction, 0.0], step_position, step_distance, step_height));
+				}
+				parts.push(part);
+			}
+		}
+
+		Ok(RobotConfig::from_parts(parts))
+	}
+}
+
+
+
+// The dimensions a template is generated with. Lengths are in millimeters.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TemplateParameters {
+	leg_count:usize,
+	segment_lengths:Vec<f32>,
+	hip_radius:f32
+}
+impl TemplateParameters {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new instance. The segment lengths are listed from the body to the foot.
+	pub fn new(leg_count:usize, segment_lengths:Vec<f32>, hip_radius:f32) -> TemplateParameters {
+		TemplateParameters {
+			leg_count,
+			segment_lengths,
+			hip_radius
+		}
+	}
+
+	/// Check if a template with legs of the given amount of motors can be generated with these parameters.
+	fn validate(&self, leg_motor_count:usize) -> Result<(), Box<dyn Error>> {
+		if self.leg_count == 0 {
+			return Err("A robot needs at least one leg.".into());
+		}
+		if self.segment_lengths.len() != leg_motor_count {
+			return Err(format!("Legs of this template have {leg_motor_count} segments, but {} segment lengths were given.", self.segment_lengths.len()).into());
+		}
+		if self.segment_lengths.iter().chain([&self.hip_radius]).any(|length| *length <= 0.0 || !length.is_finite()) {
+			return Err("Segment lengths and hip radius should be larger than 0.".into());
+		}
+		Ok(())
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Return a reference to the leg_count.
+	pub fn leg_count(&self) -> &usize {
+		&self.leg_count
+	}
+
+	/// Return a mutable reference to the leg_count.
+	pub fn leg_count_mut(&mut self) -> &mut usize {
+		&mut self.leg_count
+	}
+
+	/// Return a reference to the segment_lengths.
+	pub fn segment_lengths(&self) -> &Vec<f32> {
+		&self.segment_lengths
+	}
+
+	/// Return a mutable reference to the segment_lengths.
+	pub fn segment_lengths_mut(&mut self) -> &mut Vec<f32> {
+		&mut self.segment_lengths
+	}
+
+	/// Return a reference to the hip_radius.
+	pub fn hip_radius(&self) -> &f32 {
+		&self.hip_radius
+	}
+
+	/// Return a mutable reference to the hip_radius.
+	pub fn hip_radius_mut(&mut self) -> &mut f32 {
+		&mut self.hip_radius
+	}
+}
\ No newline at end of file
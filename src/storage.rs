@@ -1,6 +1,6 @@
-use crate::robot_configuration::RobotConfig;
-use dynamic_data_storage::StorageManager;
-use std::error::Error;
+use crate::{ robot_configuration::RobotConfig, ui::Window };
+use dynamic_data_storage::{ GeneralDataType, StorageError, StorageManager, TextFormat, TextValue };
+use std::{ error::Error, sync::Mutex };
 
 
 
@@ -9,29 +9,59 @@ use std::error::Error;
 /// Create and set the initial storage of the project.
 pub fn init_storage() -> Result<(), Box<dyn Error>> {
 	set_project_name_and_path(AUTO_SAVE_PROJECT_NAME);
+	StorageManager::get_mut().flush()?;
 	StorageManager::hard_reset();
 	set_storage_manager_catagories()?;
 	RobotConfig::create();
+	RobotConfig::watch_storage();
 	Ok(())
 }
 
 /// Select a project based on its name or path.
 pub fn select_project(project_source:&str) -> Result<(), Box<dyn Error>> {
 	set_project_name_and_path(project_source);
+	StorageManager::get_mut().flush()?;
 	StorageManager::hard_reset();
 	set_storage_manager_catagories()?;
-	RobotConfig::load();
+	RobotConfig::watch_storage();
+	RobotConfig::load()?;
+
+	// Report meshes that could not be found, the editor lets the user relink them.
+	let missing_meshes:Vec<String> = RobotConfig::get().missing_meshes();
+	for mesh in &missing_meshes {
+		eprintln!("Warning: Could not find mesh '{mesh}'.");
+	}
+	if !missing_meshes.is_empty() && Window::instance_available() {
+		Window::get().execute_post_listener(&|| Window::get().show_mesh_relink_tooltip());
+	}
 	Ok(())
 }
 
-/// Store the current project at a new name/path.
+/// Store the current project at a new name/path. The meshes of the project are copied along, so the new project is self-contained.
 pub fn store_project_at(project_source:&str) -> Result<(), Box<dyn Error>> {
+	let previous_project_dir:String = project_dir();
 	set_project_name_and_path(project_source);
 	set_storage_manager_catagories()?;
+	for mesh in pack_assets(&previous_project_dir)? {
+		eprintln!("Warning: Could not find mesh '{mesh}', it was not copied into the project.");
+	}
 	RobotConfig::save();
 	Ok(())
 }
 
+/// Create a new empty project and open it. A number is added to the name when it is already used by another project. Returns the name of the new project.
+pub fn create_project(name:&str) -> Result<String, Box<dyn Error>> {
+	use std::fs::create_dir_all;
+
+	if name.is_empty() || name.contains(['/', '\\']) {
+		return Err(format!("'{name}' can not be used as project name.").into());
+	}
+	let project_name:String = unique_project_name(name);
+	create_dir_all(format!("{}/{project_name}", projects_dir()))?;
+	select_project(&project_name)?;
+	Ok(project_name)
+}
+
 /// Set the new project name and path based on a name/path.
 fn set_project_name_and_path(source:&str) {
 	unsafe {
@@ -40,27 +70,224 @@ fn set_project_name_and_path(source:&str) {
 			PROJECT_PATH = source.to_string();
 		} else {
 			PROJECT_NAME = source.to_string();
-			PROJECT_PATH = projects_dir() + "/" + source;
+			PROJECT_PATH = find_project_dir(source).unwrap_or(projects_dir() + "/" + source);
 		}
 	}
 }
 
 /// Set the categories for the storage manager.
-fn set_storage_manager_catagories() -> Result<(), Box<dyn Error>> {
-	StorageManager::get_mut().set_categories(vec![
+fn set_storage_manager_catagories() -> Result<(), StorageError> {
+	let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+	storage_manager.set_categories(vec![
 		("user_settings", &user_settings_file()),
 		("project_settings", &project_settings_file()),
-		("project_robot_config", &project_robot_config_file())
-	])
+		("project_robot_config", &project_robot_config_file()),
+		("project_cache", &project_cache_file())
+	])?;
+
+	// Report files that were damaged and replaced by their backup.
+	for (category, reason) in storage_manager.take_restored_categories() {
+		eprintln!("{reason} Restored '{category}' from its backup.");
+	}
+	Ok(())
+}
+
+
+
+/* PROJECT TEXT METHODS */
+
+/// The storage categories that make up a project when stored as text.
+static PROJECT_TEXT_CATEGORIES:[&str; 2] = ["project_settings", "project_robot_config"];
+
+/// Create a readable text version of the current project, for example to keep it in version control.
+pub fn project_to_text(format:TextFormat) -> Result<String, Box<dyn Error>> {
+	RobotConfig::save();
+	Ok(StorageManager::get().categories_to_text(&PROJECT_TEXT_CATEGORIES, format)?)
+}
+
+/// Export the current project to a .json or .toml file.
+pub fn export_project_text(path:&str) -> Result<(), Box<dyn Error>> {
+	RobotConfig::save();
+	Ok(StorageManager::get().export_categories(&PROJECT_TEXT_CATEGORIES, path)?)
+}
+
+/// Import a .json or .toml file exported by `export_project_text` into the current project.
+pub fn import_project_text(path:&str) -> Result<(), Box<dyn Error>> {
+	let format:TextFormat = TextFormat::from_path(path)?;
+	let text:String = std::fs::read_to_string(path).map_err(|error| format!("Could not read '{path}': {error}"))?;
+
+	// Only allow project categories, user settings should not be overwritten by a project.
+	let document:TextValue = format.parse(&text)?;
+	if let Some((category, _)) = document.map()?.iter().find(|(category, _)| !PROJECT_TEXT_CATEGORIES.contains(&category.as_str())) {
+		return Err(format!("'{path}' contains category '{category}', which is not part of a project.").into());
+	}
+
+	// Make sure the robot config can be read before anything is imported.
+	let robot_config_text:&TextValue = document.field("project_robot_config").field("robot_config");
+	if robot_config_text.has_field("value") {
+		RobotConfig::from_text_value(robot_config_text.field("value")).map_err(|error| format!("Could not read the robot config in '{path}': {error}"))?;
+	}
+
+	// Import, the robot config reloads itself as it watches the storage.
+	StorageManager::get_mut().import_categories_from_text(&text, format)?;
+	Ok(StorageManager::get_mut().flush()?)
+}
+
+
+
+/* PROJECT ASSET METHODS */
+
+/// Copy all meshes used by the current project into its assets dir and let the parts refer to the copies. Returns the meshes that could not be found.
+pub fn pack_project() -> Result<Vec<String>, Box<dyn Error>> {
+	let missing_meshes:Vec<String> = pack_assets(&project_dir())?;
+	RobotConfig::save();
+	Ok(missing_meshes)
+}
+
+/// Copy all meshes used by the robot config into the assets dir of the current project. Project-relative mesh paths are resolved from the given project dir, which differs from the current one when storing a project at a new location. Returns the meshes that could not be found.
+fn pack_assets(source_project_dir:&str) -> Result<Vec<String>, Box<dyn Error>> {
+	use std::{ fs::{ copy, create_dir_all }, path::Path };
+
+	let robot_config:&mut RobotConfig = RobotConfig::get_mut();
+	let mut packed_meshes:Vec<(String, String)> = Vec::new();
+	let mut missing_meshes:Vec<String> = Vec::new();
+	for part_index in 0..robot_config.parts().len() {
+		let mesh:String = robot_config.parts()[part_index].obj().to_string();
+		if mesh.is_empty() || missing_meshes.contains(&mesh) {
+			continue;
+		}
+
+		// Copy each mesh only once, even when used by multiple parts.
+		let asset:String = match packed_meshes.iter().find(|(packed_mesh, _)| packed_mesh == &mesh) {
+			Some((_, asset)) => asset.to_string(),
+			None => {
+				let source_file:String = asset_path_in(source_project_dir, &mesh);
+				if !Path::new(&source_file).is_file() {
+					missing_meshes.push(mesh);
+					continue;
+				}
+				let asset:String = unique_asset_path(&source_file)?;
+				let target_file:String = asset_path(&asset);
+				if target_file != source_file {
+					create_dir_all(project_assets_dir())?;
+					copy(&source_file, &target_file).map_err(|error| format!("Could not copy mesh '{mesh}' into the project: {error}"))?;
+				}
+				packed_meshes.push((mesh, asset.to_string()));
+				asset
+			}
+		};
+		*robot_config.parts_mut()[part_index].obj_mut() = asset;
+	}
+	robot_config.update_ui();
+	Ok(missing_meshes)
+}
+
+/// Find the project-relative path to copy a mesh file to. A file with the same name and contents is reused, other files with the same name get a numbered name.
+fn unique_asset_path(source_file:&str) -> Result<String, Box<dyn Error>> {
+	use std::{ fs::read, path::Path };
+
+	let source_path:&Path = Path::new(source_file);
+	let stem:String = source_path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or(String::from("mesh"));
+	let extension:String = source_path.extension().map(|extension| format!(".{}", extension.to_string_lossy())).unwrap_or_default();
+	let source_bytes:Vec<u8> = read(source_file)?;
+	let mut index:usize = 0;
+	loop {
+		let file_name:String = if index == 0 { format!("{stem}{extension}") } else { format!("{stem}_{index}{extension}") };
+		let asset:String = format!("{PROJECT_ASSETS_DIR}/{file_name}");
+		let target_file:String = asset_path(&asset);
+		if target_file == source_file || !Path::new(&target_file).exists() || read(&target_file)? == source_bytes {
+			return Ok(asset);
+		}
+		index += 1;
+	}
+}
+
+/// Get the path to read a mesh of the current project from. Paths inside the assets dir are stored relative to the project, others are used as they are.
+pub fn asset_path(mesh:&str) -> String {
+	asset_path_in(&project_dir(), mesh)
+}
+
+/// Get the path to read a mesh from for a project in a specific dir.
+fn asset_path_in(project_dir:&str, mesh:&str) -> String {
+	if mesh.starts_with(&format!("{PROJECT_ASSETS_DIR}/")) { format!("{project_dir}/{mesh}") } else { mesh.to_string() }
+}
+
+
+
+/* PROJECT THUMBNAIL METHODS */
+
+const PROJECT_THUMBNAIL_SIZE:usize = 256;
+
+/// Render the robot of the current project to its thumbnail. Only renders when the thumbnail does not exist or the robot config changed since it was rendered.
+pub fn update_project_thumbnail() -> Result<(), Box<dyn Error>> {
+	use std::{ fs::create_dir_all, hash::{ DefaultHasher, Hasher }, path::Path };
+
+	// Compare the config to the one the thumbnail was rendered from.
+	let mut hasher:DefaultHasher = DefaultHasher::new();
+	hasher.write(&RobotConfig::get().to_bytes());
+	let config_checksum:u64 = hasher.finish();
+	if Path::new(&project_thumbnail_file()).exists() && StorageManager::get().get_value::<u64>("project_cache", "thumbnail_config_checksum").is_ok_and(|checksum| checksum == config_checksum) {
+		return Ok(());
+	}
+
+	// Render the thumbnail.
+	create_dir_all(project_dir())?;
+	Window::render_robot_png(&project_thumbnail_file(), [PROJECT_THUMBNAIL_SIZE, PROJECT_THUMBNAIL_SIZE])?;
+	StorageManager::get_mut().set_value::<u64>("project_cache", "thumbnail_config_checksum", &config_checksum)?;
+	Ok(())
 }
 
 
 
 /* MAIN PATH BUILDER METHODS */
 
-/// The root directory to store user settings and configuration.
+pub static USER_STORAGE_DIR_ENV_VAR:&str = "KINEMATIC_FLOW_DATA_DIR";
+static APPLICATION_DIR_NAME:&str = "kinematic_flow";
+static LEGACY_USER_STORAGE_DIR:&str = "user_data";
+static USER_STORAGE_DIR_OVERRIDE:Mutex<Option<String>> = Mutex::new(None);
+
+/// Use a specific root directory to store user settings and projects, overriding the environment variable and default location.
+pub fn set_user_storage_dir(dir:&str) {
+	*USER_STORAGE_DIR_OVERRIDE.lock().unwrap() = Some(dir.trim_end_matches('/').to_string());
+}
+
+/// The root directory to store user settings and configuration. Can be set using `set_user_storage_dir` or the KINEMATIC_FLOW_DATA_DIR environment variable, defaults to the data dir of the platform. A 'user_data' dir in the working directory created by earlier versions is still used when the default dir does not exist yet.
 pub fn user_storage_dir() -> String {
-	"user_data".to_string()
+	use std::path::Path;
+
+	if let Some(dir) = USER_STORAGE_DIR_OVERRIDE.lock().unwrap().clone() {
+		return dir;
+	}
+	if let Some(dir) = std::env::var(USER_STORAGE_DIR_ENV_VAR).ok().filter(|dir| !dir.is_empty()) {
+		return dir.replace('\\', "/").trim_end_matches('/').to_string();
+	}
+	match platform_data_dir().map(|dir| format!("{dir}/{APPLICATION_DIR_NAME}")) {
+		Some(dir) if Path::new(&dir).exists() || !Path::new(LEGACY_USER_STORAGE_DIR).is_dir() => dir,
+		_ => LEGACY_USER_STORAGE_DIR.to_string()
+	}
+}
+
+/// The dir the platform stores application data in.
+#[cfg(target_os = "windows")]
+fn platform_data_dir() -> Option<String> {
+	std::env::var("APPDATA").ok().filter(|dir| !dir.is_empty()).map(|dir| dir.replace('\\', "/"))
+}
+
+/// The dir the platform stores application data in.
+#[cfg(target_os = "macos")]
+fn platform_data_dir() -> Option<String> {
+	std::env::var("HOME").ok().filter(|home| !home.is_empty()).map(|home| format!("{home}/Library/Application Support"))
+}
+
+/// The dir the platform stores application data in, following the XDG base directory specification.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn platform_data_dir() -> Option<String> {
+	use std::path::Path;
+
+	match std::env::var("XDG_DATA_HOME") {
+		Ok(dir) if Path::new(&dir).is_absolute() => Some(dir),
+		_ => std::env::var("HOME").ok().filter(|home| !home.is_empty()).map(|home| format!("{home}/.local/share"))
+	}
 }
 
 /// The file to store user settings globally used across all projects.
@@ -73,16 +300,127 @@ fn default_projects_dir() -> String {
 	format!("{}/projects", user_storage_dir())
 }
 
-/// Get the active projects dir.
+/// Get the active projects dir, new projects are created here.
 pub fn projects_dir() -> String {
 	default_projects_dir()
 }
 
 
 
+/* PROJECT ROOT METHODS */
+
+/// Get all dirs containing projects. The active projects dir comes first, followed by the additional roots listed in the user settings.
+pub fn project_roots() -> Vec<String> {
+	let mut roots:Vec<String> = vec![projects_dir()];
+	for root in StorageManager::get().get_value::<Vec<String>>("user_settings", "project_roots").unwrap_or_default() {
+		if !roots.contains(&root) {
+			roots.push(root);
+		}
+	}
+	roots
+}
+
+/// Add a dir containing projects to the user settings.
+pub fn add_project_root(dir:&str) -> Result<(), Box<dyn Error>> {
+	let dir:String = dir.replace('\\', "/").trim_end_matches('/').to_string();
+	if !std::path::Path::new(&dir).is_dir() {
+		return Err(format!("'{dir}' is not a directory.").into());
+	}
+	let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+	let mut roots:Vec<String> = storage_manager.get_value::<Vec<String>>("user_settings", "project_roots").unwrap_or_default();
+	if !roots.contains(&dir) {
+		roots.push(dir);
+		storage_manager.set_value::<Vec<String>>("user_settings", "project_roots", &roots)?;
+	}
+	Ok(())
+}
+
+/// Remove a dir containing projects from the user settings. Returns false if it was not listed.
+pub fn remove_project_root(dir:&str) -> Result<bool, Box<dyn Error>> {
+	let dir:String = dir.replace('\\', "/").trim_end_matches('/').to_string();
+	let storage_manager:&mut StorageManager = &mut StorageManager::get_mut();
+	let mut roots:Vec<String> = storage_manager.get_value::<Vec<String>>("user_settings", "project_roots").unwrap_or_default();
+	let root_count:usize = roots.len();
+	roots.retain(|root| root != &dir);
+	if roots.len() == root_count {
+		return Ok(false);
+	}
+	storage_manager.set_value::<Vec<String>>("user_settings", "project_roots", &roots)?;
+	Ok(true)
+}
+
+/// Find the dir of an existing project by its name in any of the project roots.
+pub fn find_project_dir(project_name:&str) -> Option<String> {
+	use std::path::Path;
+
+	project_roots().into_iter().map(|root| format!("{root}/{project_name}")).find(|dir| Path::new(dir).is_dir())
+}
+
+
+
+/* PROJECT LISTING METHODS */
+
+/// Create a list of available projects in all project roots, sorted by modification date from newest to oldest changes.
+pub fn latest_modified_projects() -> Vec<String> {
+	use std::{ fs::{ read_dir, metadata }, time::SystemTime };
+
+	// Loop through project dirs.
+	let mut latest_projects:Vec<(String, SystemTime)> = Vec::new();
+	for projects_dir in project_roots() {
+		if let Ok(sub_dir_entries) = read_dir(projects_dir) {
+			for sub_dir in sub_dir_entries.flatten() {
+				if sub_dir.file_name() == AUTO_SAVE_PROJECT_NAME { continue; }
+				if let Ok(sub_dir_meta) = metadata(sub_dir.path()) {
+					if !sub_dir_meta.is_dir() { continue; }
+
+					// Loop through files in the directory.
+					let mut latest_modified:SystemTime = sub_dir_meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+					if let Ok(file_entries) = read_dir(sub_dir.path()) {
+						for file in file_entries.flatten() {
+							if let Ok(file_meta) = metadata(file.path()) {
+								if file_meta.is_dir() { continue; }
+
+								// Keep track of the last modified file.
+								if let Ok(modification_time) = file_meta.modified() {
+									if modification_time > latest_modified {
+										latest_modified = modification_time;
+									}
+								}
+							}
+						}
+					}
+
+					// Keep list of available projects.
+					latest_projects.push((sub_dir.path().display().to_string(), latest_modified));
+				}
+			}
+		}
+	}
+
+	// Sort projects by date.
+	latest_projects.sort_by(|(_, time1), (_, time2)| time2.cmp(time1));
+
+	latest_projects.iter().map(|project| project.0.replace('\\', "/")).collect::<Vec<String>>()
+}
+
+/// Find a name for a new project that is not used by any existing project in any of the project roots, based on the given name.
+pub fn unique_project_name(name:&str) -> String {
+	let mut index:usize = 1;
+	let mut project_name:String = name.to_string();
+	while project_name == AUTO_SAVE_PROJECT_NAME || find_project_dir(&project_name).is_some() {
+		index += 1;
+		project_name = format!("{name} ({index})");
+	}
+	project_name
+}
+
+
+
 /* PROJECT PATH BUILDER METHODS */
 
 pub static AUTO_SAVE_PROJECT_NAME:&str = "_AutoSave";
+pub static PROJECT_ASSETS_DIR:&str = "assets";
+pub static PROJECT_THUMBNAIL_FILE_NAME:&str = "thumbnail.png";
 static mut PROJECT_NAME:String = String::new();
 static mut PROJECT_PATH:String = String::new();
 
@@ -104,4 +442,19 @@ pub fn project_settings_file() -> String {
 /// The file for the configuration of the robot.
 pub fn project_robot_config_file() -> String {
 	format!("{}/robot", project_dir())
+}
+
+/// The file for data derived from the project, which is not part of its text version.
+pub fn project_cache_file() -> String {
+	format!("{}/cache", project_dir())
+}
+
+/// The image shown for the project when opening projects.
+pub fn project_thumbnail_file() -> String {
+	format!("{}/{PROJECT_THUMBNAIL_FILE_NAME}", project_dir())
+}
+
+/// The directory to copy the meshes of the project to.
+pub fn project_assets_dir() -> String {
+	format!("{}/{PROJECT_ASSETS_DIR}", project_dir())
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use glass_panel::{ Drawable, DrawableData, ListenerType, elements::{ Centered, Class, Col, Rectangle, Row, Text, Border } };
+use crate::robot_configuration::ConfigHistory;
 use super::super::Window;
 use std::rc::Rc;
 
@@ -51,7 +52,7 @@ impl Window {
 		]);
 
 		// Create listener.
-		button.add_listener("InputButtonClickEvent", ListenerType::LeftDown, Rc::new(|_,_,_,_| click_event()));
+		button.add_listener("InputButtonClickEvent", ListenerType::LeftDown, Rc::new(move |_,_,_,_| ConfigHistory::edit(None, click_event)));
 
 		// Return button
 		Class::new("input_field", vec![&button])
@@ -81,7 +82,7 @@ impl Window {
 		// Add value box listeners.
 		inner_box.add_listener("InputFieldModify", ListenerType::LeftDown, Rc::new(|_,_,_, element_data| {
 			let enabled:bool = !value_getter();
-			value_setter(enabled);
+			ConfigHistory::edit(None, || value_setter(enabled));
 			let inner_element:&mut dyn Drawable = element_data.children_mut()[0].child_by_name_mut(".checkbox_inner rectangle").unwrap();
 			let color:u32 = Window::get().setting::<u32>(if enabled { "color_detail" } else { "color_foreground" });
 			inner_element.data_set_mut().set_setting_value::<u32>("color", color);
@@ -120,7 +121,7 @@ impl Window {
 			value_box.add_listener("SelectOption", ListenerType::LeftDown, Rc::new(|_, _, _, element_data:&mut DrawableData| {
 				let option_index:usize = element_data.get_setting_value::<usize>("option_index").unwrap();
 				element_data.children_mut()[0].child_by_name_mut("text").unwrap().data_set_mut().set_setting_value::<u32>("color", Window::get().setting("color_detail"));
-				value_setter(option_index);
+				ConfigHistory::edit(None, || value_setter(option_index));
 			}));
 
 			// Return element.
@@ -153,12 +154,14 @@ impl Window {
 		let mut value_box:Rectangle = self.value_box(&value_getter().to_string());
 
 		// Add value box listeners.
+		value_box.data_set_mut().set_setting_value::<String>("input_name", name.to_string());
 		value_box.add_listener("InputFieldModify", ListenerType::DragLeft, Rc::new(|position_shift,_,_, element_data| {
-			value_setter(value_getter() - position_shift[1] as f32);
+			let value:f32 = value_getter() - position_shift[1] as f32;
+			ConfigHistory::edit(Some(&Self::input_coalesce_key(element_data)), || value_setter(value));
 			Self::change_value_box_value(element_data, &value_getter().to_string());
 		}));
 		value_box.add_listener("InputFieldReset", ListenerType::RightDown, Rc::new(|_,_,_, element_data| {
-			value_setter(0.0);
+			ConfigHistory::edit(None, || value_setter(0.0));
 			Self::change_value_box_value(element_data, &value_getter().to_string());
 		}));
 
@@ -185,16 +188,17 @@ impl Window {
 			value_box.child_by_name_mut("centered").unwrap().data_set_mut().set_setting_value::<usize>("width", value_box_inner_width);
 
 			// Add listeners.
+			value_box.data_set_mut().set_setting_value::<String>("input_name", name.to_string());
 			value_box.data_set_mut().set_setting_value::<usize>("input_index", index);
 			value_box.add_listener("InputFieldModify", ListenerType::DragLeft, Rc::new(|position_shift,_,_, element_data| {
 				let index:usize = element_data.get_setting_value::<usize>("input_index").unwrap();
 				let value:f32 = value_getter()[index] - position_shift[1] as f32;
-				value_setter(index, value);
+				ConfigHistory::edit(Some(&Self::input_coalesce_key(element_data)), || value_setter(index, value));
 				Self::change_value_box_value(element_data, &value_getter()[index].to_string());
 			}));
 			value_box.add_listener("InputFieldReset", ListenerType::RightDown, Rc::new(|_,_,_, element_data| {
 				let index:usize = element_data.get_setting_value::<usize>("input_index").unwrap();
-				value_setter(index, 0.0);
+				ConfigHistory::edit(None, || value_setter(index, 0.0));
 				Self::change_value_box_value(element_data, &value_getter()[index].to_string());
 			}));
 
@@ -245,6 +249,14 @@ impl Window {
 		])
 	}
 
+	/// Identify the dragged value box, so consecutive changes of the same value of the same part are combined into a single edit in the config history.
+	fn input_coalesce_key(element_data:&DrawableData) -> String {
+		let ui:&Window = Window::get();
+		let input_name:String = element_data.get_setting_value::<String>("input_name").unwrap_or_default();
+		let input_index:usize = element_data.get_setting_value::<usize>("input_index").unwrap_or(0);
+		format!("{}/{input_name}/{input_index}/{:?}", ui.active_tab_name(), ui.selected_part())
+	}
+
 	/// Update the value text of a value box based on the element data. Mainly used in drag listeners.
 	fn change_value_box_value(element_data:&mut DrawableData, value:&str) {
 		element_data.children_mut()[0].child_by_name_mut(".ValText text").unwrap().data_set_mut().set_setting_value::<String>("text", value.to_string());
@@ -0,0 +1,91 @@
+use glass_panel::{ Drawable, ListenerType, elements::{ Rectangle, Col, Text, Id, VisibilityToggler, Centered, Border } };
+use crate::robot_configuration::{ ConfigHistory, RobotConfig };
+use super::super::Window;
+use std::rc::Rc;
+
+impl Window {
+
+	/// Create the menu to relink meshes that could not be found.
+	pub(crate) fn create_mesh_relink_tooltip(&self) -> Id {
+		let window_size:[usize; 2] = [self.setting::<usize>("window_width"), self.setting::<usize>("window_width")];
+
+		Id::new("mesh_relink_tooltip", vec![
+			&VisibilityToggler::new(false, vec![
+				&Rectangle::new(window_size[0], window_size[1], self.setting::<u32>("color_tooltip_shadow"), vec![
+					&Centered::new(true, true, window_size[0], window_size[1], vec![
+						&Id::new("mesh_relink_tooltip_contents", vec![])
+					])
+				])
+			])
+		])
+	}
+
+	/// Show the menu listing all meshes of the robot config that could not be found. Clicking a mesh lets the user select the file to use instead.
+	pub fn show_mesh_relink_tooltip(&mut self) {
+		use std::thread;
+
+		// Suppress main window listeners.
+		self.window_mut().source_mut().child_by_name_mut("#main_ui").unwrap().suppress_listeners();
+
+		// Create buttons.
+		let missing_meshes:Vec<String> = RobotConfig::get().missing_meshes();
+		let button_color:u32 = self.setting("color_highlight");
+		let mut button_texts:Vec<Text> = vec![self.default_text("Missing meshes, click one to relink it:")];
+		button_texts.extend(missing_meshes.iter().map(|mesh| self.default_text(mesh)));
+		button_texts.push(self.default_text("Close"));
+		let button_sizes:Vec<[usize; 4]> = button_texts.iter().map(|button| button.position()).collect::<Vec<[usize; 4]>>();
+		let button_shared_width:usize = button_sizes.iter().map(|size| size[2]).reduce(|a, b| a.max(b)).unwrap();
+		let button_shared_height:usize = button_sizes.iter().map(|size| size[3]).reduce(|a, b| a + b).unwrap();
+		let mut buttons:Vec<Rectangle> = (0..button_texts.len()).map(|index|
+			Rectangle::new(button_shared_width, button_sizes[index][3], button_color, vec![&button_texts[index]])
+		).collect::<Vec<Rectangle>>();
+
+		// Add listeners to buttons.
+		for (mesh_index, mesh) in missing_meshes.iter().enumerate() {
+			let button:&mut Rectangle = &mut buttons[mesh_index + 1];
+			button.data_set_mut().set_setting_value::<String>("mesh", mesh.to_string());
+			button.add_listener("MeshRelinkToolTipRelinkButtonClick", ListenerType::LeftDown, Rc::new(|_, _, _, element_data| {
+				let mesh:String = element_data.get_setting_value::<String>("mesh").unwrap();
+				thread::spawn(move || {
+					if let Some(path) = Window::select_obj(None) {
+						Window::execute_on_ui_thread(move || {
+							ConfigHistory::edit(None, || RobotConfig::get_mut().relink_mesh(&mesh, &path));
+							if RobotConfig::get().missing_meshes().is_empty() {
+								Window::get().hide_mesh_relink_tooltip();
+							} else {
+								Window::get().execute_post_listener(&|| Window::get().show_mesh_relink_tooltip());
+							}
+						});
+					}
+				});
+			}));
+		}
+		buttons.last_mut().unwrap().add_listener("MeshRelinkToolTipCloseButtonClick", ListenerType::LeftDown, Rc::new(|_, _, _, _| Window::get().hide_mesh_relink_tooltip()));
+
+		// Create full tooltip.
+		let tooltip:Border = Border::new(1, self.setting("color_detail"), vec![
+			&Rectangle::new(button_shared_width, button_shared_height, button_color, vec![
+				&Col::new(
+					buttons.iter().map(|button| button as &dyn Drawable).collect::<Vec<&dyn Drawable>>()
+				)
+			])
+		]);
+
+		// Set tooltip in window.
+		self.window_mut().source_mut().child_by_name_mut("#mesh_relink_tooltip visibility_toggler").unwrap().data_set_mut().set_setting_value::<bool>("visible", true);
+		self.window_mut().source_mut().child_by_name_mut("#mesh_relink_tooltip_contents").unwrap().set_children(vec![&tooltip]);
+		self.window_mut().source_mut().child_by_name_mut("#mesh_relink_tooltip").unwrap().unsuppress_listeners();
+	}
+
+	/// Hide the mesh relink tooltip.
+	pub(crate) fn hide_mesh_relink_tooltip(&mut self) {
+
+		// Schedule UI update.
+		Window::get().execute_post_listener(&|| {
+			let ui:&mut Window = Window::get();
+			ui.window_mut().source_mut().child_by_name_mut("#mesh_relink_tooltip").unwrap().suppress_listeners();
+			ui.window_mut().source_mut().child_by_name_mut("#mesh_relink_tooltip visibility_toggler").unwrap().data_set_mut().set_setting_value::<bool>("visible", false);
+			ui.window_mut().source_mut().child_by_name_mut("#main_ui").unwrap().unsuppress_listeners();
+		});
+	}
+}
\ No newline at end of file
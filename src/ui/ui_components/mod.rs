@@ -2,7 +2,9 @@
 
 mod file_selector;
 mod inputs;
+mod mesh_relink_tooltip;
 mod mesh_selector;
+mod new_project_tooltip;
 mod project_loading_tooltip;
 mod properties_menu;
 mod properties_menu_listener_handlers;
@@ -0,0 +1,123 @@
+use glass_panel::{ Drawable, ListenerType, elements::{ Rectangle, Col, Text, Id, VisibilityToggler, Centered, Border, Class } };
+use crate::{ robot_configuration::RobotTemplate, storage };
+use super::super::Window;
+use std::rc::Rc;
+
+const NEW_PROJECT_EMPTY_NAME:&str = "New project";
+const NEW_PROJECT_MAX_LEG_COUNT:usize = 16;
+const NEW_PROJECT_MIN_LENGTH:f32 = 1.0;
+
+impl Window {
+
+	/// Create the menu to create a new project.
+	pub(crate) fn create_new_project_tooltip(&self) -> Id {
+		let window_size:[usize; 2] = [self.setting::<usize>("window_width"), self.setting::<usize>("window_width")];
+
+		Id::new("new_project_tooltip", vec![
+			&VisibilityToggler::new(false, vec![
+				&Rectangle::new(window_size[0], window_size[1], self.setting::<u32>("color_tooltip_shadow"), vec![
+					&Centered::new(true, true, window_size[0], window_size[1], vec![
+						&Id::new("new_project_tooltip_contents", vec![])
+					])
+				])
+			])
+		])
+	}
+
+	/// Show the menu to create a new project, either empty or with a robot generated from a template with adjustable dimensions.
+	pub fn show_new_project_tooltip(&mut self) {
+
+		// Suppress main window listeners.
+		self.window_mut().source_mut().child_by_name_mut("#main_ui").unwrap().suppress_listeners();
+
+		// Create template buttons, the selected template is highlighted.
+		let templates:Vec<Option<RobotTemplate>> = [None].into_iter().chain(RobotTemplate::all().map(Some)).collect::<Vec<Option<RobotTemplate>>>();
+		let button_color:u32 = self.setting("color_highlight");
+		let mut button_texts:Vec<Text> = vec![self.default_text("New project from template:")];
+		button_texts.extend(templates.iter().map(|template| {
+			let mut text:Text = self.default_text(template.map(|template| template.name()).unwrap_or("Empty"));
+			if template == self.new_project_template() {
+				text.data_set_mut().set_setting_value::<u32>("color", self.setting("color_detail"));
+			}
+			text
+		}));
+		button_texts.extend([self.default_text("Create"), self.default_text("Cancel")]);
+
+		// Create parameter inputs.
+		let parameter_inputs:Vec<Class> = if self.new_project_template().is_some() {
+			vec![
+				self.create_property_input_float("Legs", &|| *Window::get().new_project_parameters().leg_count() as f32, &|value| *Window::get().new_project_parameters_mut().leg_count_mut() = value.round().clamp(1.0, NEW_PROJECT_MAX_LEG_COUNT as f32) as usize),
+				self.create_property_input_float_vec("Segments", &|| Window::get().new_project_parameters().segment_lengths().clone(), &|index, value| Window::get().new_project_parameters_mut().segment_lengths_mut()[index] = value.max(NEW_PROJECT_MIN_LENGTH)),
+				self.create_property_input_float("Hip radius", &|| *Window::get().new_project_parameters().hip_radius(), &|value| *Window::get().new_project_parameters_mut().hip_radius_mut() = value.max(NEW_PROJECT_MIN_LENGTH))
+			]
+		} else {
+			Vec::new()
+		};
+
+		// Size all buttons to the widest element.
+		let button_sizes:Vec<[usize; 4]> = button_texts.iter().map(|button| button.position()).collect::<Vec<[usize; 4]>>();
+		let input_width:usize = parameter_inputs.iter().map(|input| input.position()[2]).max().unwrap_or(0);
+		let button_shared_width:usize = button_sizes.iter().map(|size| size[2]).max().unwrap().max(input_width);
+		let mut buttons:Vec<Rectangle> = (0..button_texts.len()).map(|index|
+			Rectangle::new(button_shared_width, button_sizes[index][3], button_color, vec![&button_texts[index]])
+		).collect::<Vec<Rectangle>>();
+
+		// Add listeners to buttons.
+		for template_index in 0..templates.len() {
+			let button:&mut Rectangle = &mut buttons[template_index + 1];
+			button.data_set_mut().set_setting_value::<usize>("template_index", template_index);
+			button.add_listener("NewProjectToolTipTemplateButtonClick", ListenerType::LeftDown, Rc::new(|_, _, _, element_data| {
+				let template_index:usize = element_data.get_setting_value::<usize>("template_index").unwrap();
+				let template:Option<RobotTemplate> = template_index.checked_sub(1).map(|index| RobotTemplate::all()[index]);
+				let ui:&mut Window = Window::get();
+				*ui.new_project_template_mut() = template;
+				if let Some(template) = template {
+					*ui.new_project_parameters_mut() = template.default_parameters();
+				}
+				ui.execute_post_listener(&|| Window::get().show_new_project_tooltip());
+			}));
+		}
+		let button_count:usize = buttons.len();
+		buttons[button_count - 2].add_listener("NewProjectToolTipCreateButtonClick", ListenerType::LeftDown, Rc::new(|_, _, _, _| {
+			Window::get().hide_new_project_tooltip();
+			Window::get().execute_post_listener(&|| {
+				let result:Result<String, Box<dyn std::error::Error>> = match Window::get().new_project_template() {
+					Some(template) => template.create_project(None, Window::get().new_project_parameters()),
+					None => storage::create_project(NEW_PROJECT_EMPTY_NAME)
+				};
+				if let Err(error) = result {
+					eprintln!("Could not create project: {error}");
+				}
+			});
+		}));
+		buttons[button_count - 1].add_listener("NewProjectToolTipCloseButtonClick", ListenerType::LeftDown, Rc::new(|_, _, _, _| Window::get().hide_new_project_tooltip()));
+
+		// Create full tooltip, with the parameter inputs between the templates and the create button.
+		let mut elements:Vec<&dyn Drawable> = buttons[..button_count - 2].iter().map(|button| button as &dyn Drawable).collect::<Vec<&dyn Drawable>>();
+		elements.extend(parameter_inputs.iter().map(|input| input as &dyn Drawable));
+		elements.extend(buttons[button_count - 2..].iter().map(|button| button as &dyn Drawable));
+		let tooltip_height:usize = elements.iter().map(|element| element.position()[3]).sum::<usize>();
+		let tooltip:Border = Border::new(1, self.setting("color_detail"), vec![
+			&Rectangle::new(button_shared_width, tooltip_height, button_color, vec![
+				&Col::new(elements)
+			])
+		]);
+
+		// Set tooltip in window.
+		self.window_mut().source_mut().child_by_name_mut("#new_project_tooltip visibility_toggler").unwrap().data_set_mut().set_setting_value::<bool>("visible", true);
+		self.window_mut().source_mut().child_by_name_mut("#new_project_tooltip_contents").unwrap().set_children(vec![&tooltip]);
+		self.window_mut().source_mut().child_by_name_mut("#new_project_tooltip").unwrap().unsuppress_listeners();
+	}
+
+	/// Hide the new project tooltip.
+	pub(crate) fn hide_new_project_tooltip(&mut self) {
+
+		// Schedule UI update.
+		Window::get().execute_post_listener(&|| {
+			let ui:&mut Window = Window::get();
+			ui.window_mut().source_mut().child_by_name_mut("#new_project_tooltip").unwrap().suppress_listeners();
+			ui.window_mut().source_mut().child_by_name_mut("#new_project_tooltip visibility_toggler").unwrap().data_set_mut().set_setting_value::<bool>("visible", false);
+			ui.window_mut().source_mut().child_by_name_mut("#main_ui").unwrap().unsuppress_listeners();
+		});
+	}
+}
\ No newline at end of file
@@ -1,7 +1,7 @@
-use glass_panel::{ Drawable, ListenerType, elements::{ Rectangle, Col, Row, Id, VisibilityToggler, Centered, Border } };
-use std::{rc::Rc, time::SystemTime};
+use glass_panel::{ Drawable, ListenerType, elements::{ Rectangle, Col, Row, Id, VisibilityToggler, Centered, Border, Png, Resized } };
+use std::rc::Rc;
 use super::super::Window;
-use crate::storage;
+use crate::{ autosave, storage };
 
 impl Window {
 	
@@ -28,7 +28,7 @@ impl Window {
 		self.window_mut().source_mut().child_by_name_mut("#main_ui").unwrap().suppress_listeners();
 
 		// Find latest modified projects.
-		let latest_projects:Vec<String> = Self::latest_modified_projects();
+		let latest_projects:Vec<String> = storage::latest_modified_projects();
 
 		// Calculate sizes.
 		let vmin:usize = self.setting::<usize>("window_width").min(self.setting::<usize>("window_width"));
@@ -37,13 +37,23 @@ impl Window {
 		let tile_bounds_size:usize = tile_size + tile_border_size * 2;
 
 		// Create tiles.
-		let auto_save:Rectangle = self.project_tile(tile_bounds_size * 2, tile_border_size, "Last session", None);
+		let auto_save_thumbnail:String = format!("{}/{}/{}", storage::projects_dir(), storage::AUTO_SAVE_PROJECT_NAME, storage::PROJECT_THUMBNAIL_FILE_NAME);
+		let auto_save:Rectangle = match autosave::recoverable_autosave() {
+			Some(_) => self.project_tile(tile_bounds_size * 2, tile_border_size, "Restore autosave", None), // The last session ended unexpectedly, so its newest autosave is more recent than the stored last session.
+			None => self.project_tile(tile_bounds_size * 2, tile_border_size, "Last session", Some(auto_save_thumbnail))
+		};
 		let new:Rectangle = self.project_tile(tile_bounds_size, tile_border_size, "New", None);
 		let open:Rectangle = self.project_tile(tile_bounds_size, tile_border_size, "Open", None);
-		let latest:Vec<Rectangle> = (0..4).map(|index| {
+		let import:Rectangle = self.project_tile(tile_bounds_size, tile_border_size, "Import", None);
+		let latest:Vec<Rectangle> = (0..3).map(|index| {
 			if latest_projects.len() > index {
 				let title:String = latest_projects[index].split('/').last().unwrap().replace(&format!(".{}", STORAGE_FILE_EXTENSION), "");
-				self.project_tile(tile_bounds_size, tile_border_size, &title, None)
+				let thumbnail:String = format!("{}/{}", latest_projects[index], storage::PROJECT_THUMBNAIL_FILE_NAME);
+				let mut tile:Rectangle = self.project_tile(tile_bounds_size, tile_border_size, &title, Some(thumbnail));
+
+				// Projects can be in any of the project roots, open them by path.
+				tile.data_set_mut().set_setting_value::<String>("project_path", latest_projects[index].to_string());
+				tile
 			} else {
 				Rectangle::new(0, 0, 0, vec![])
 			}
@@ -53,8 +63,8 @@ impl Window {
 		let tiles = Row::new(vec![
 			&auto_save,
 			&Col::new(vec![
-				&Row::new(vec![&new, &open, &latest[0]]),
-				&Row::new(vec![&latest[1], &latest[2], &latest[3]])
+				&Row::new(vec![&new, &open, &import]),
+				&Row::new(vec![&latest[0], &latest[1], &latest[2]])
 			])
 		]);
 		let tiles_size = &tiles.position()[2..];
@@ -83,9 +93,10 @@ impl Window {
 		});
 	}
 
-	/// Create a single project tile.
+	/// Create a single project tile. The thumbnail is scaled to fill the tile, when it cannot be read only the title is shown.
 	fn project_tile(&self, size:usize, border_size:usize, title:&str, thumbnail:Option<String>) -> Rectangle {
-		use super::file_selector::select_dir;
+		use super::file_selector::{ select_dir, select_file };
+		use crate::project_archive::{ ProjectArchive, PROJECT_ARCHIVE_EXTENSION };
 		use std::thread;
 
 		// Size settings.
@@ -94,17 +105,20 @@ impl Window {
 		let color_border:u32 = self.setting("color_highlight");
 		let contents_size:usize = size - 2 * border_size;
 
+		// Tile contents, the title is drawn over the thumbnail.
+		let thumbnail:Option<Resized> = thumbnail.and_then(|thumbnail| Png::new(&thumbnail, vec![]).ok()).map(|thumbnail| Resized::new(contents_size, contents_size, vec![&thumbnail]));
+		let title_text:Centered = Centered::new(thumbnail.is_none(), thumbnail.is_none(), contents_size, contents_size, vec![&self.default_text(title)]);
+		let mut tile_contents:Vec<&dyn Drawable> = Vec::new();
+		if let Some(thumbnail) = &thumbnail {
+			tile_contents.push(thumbnail);
+		}
+		tile_contents.push(&title_text);
+
 		// Create tile.
 		let mut tile:Rectangle = Rectangle::new(size, size, color_background, vec![
 			&Border::new(border_size - 1, 0, vec![
 				&Border::new(1, color_border, vec![
-					&Rectangle::new(contents_size, contents_size, color_tile, vec![
-						&Centered::new(thumbnail.is_none(), thumbnail.is_none(), contents_size, contents_size, vec![
-							
-							// Tile contents.
-							&self.default_text(title)
-						])
-					])
+					&Rectangle::new(contents_size, contents_size, color_tile, tile_contents)
 				])
 			])
 		]);
@@ -113,65 +127,49 @@ impl Window {
 		tile.data_set_mut().set_setting_value::<String>("project_name", title.to_string());
 		tile.add_listener("OpenProject", ListenerType::LeftDown, Rc::new(|_, _, _, element_data| {
 			let clicked_tile_title:String = element_data.get_setting_value::<String>("project_name").unwrap();
+			let clicked_tile_path:Option<String> = element_data.get_setting_value::<String>("project_path");
 			match clicked_tile_title.as_str() {
-				"New" => {}, // Automatically open fresh AutoSave project.
+				"New" => Window::get().execute_post_listener(&|| Window::get().show_new_project_tooltip()),
+				"Restore autosave" => {
+					if let Some(Err(error)) = autosave::recoverable_autosave().map(|slot| autosave::restore_autosave(&slot)) {
+						eprintln!("Could not restore autosave: {error}");
+					}
+				},
 				"Open" => {
 					thread::spawn(||{
 						if let Some(project) = select_dir(Some(&storage::projects_dir())) {
-							storage::select_project(&project).expect("Could not open project");
+							if let Err(error) = storage::select_project(&project) {
+								eprintln!("Could not open project: {error}");
+							}
 						}
 					});
 				},
-				title => {
-					let title: &str = if title == "Last session" { storage::AUTO_SAVE_PROJECT_NAME } else { title };
-					storage::select_project(title).expect("Could not open project");
-				}
-			}
-		}));
-
-		// Return tile.
-		tile
-	}
+				"Import" => {
+					thread::spawn(||{
+						if let Some(file) = select_file(Some(&storage::projects_dir()), vec![("Project archive", &[PROJECT_ARCHIVE_EXTENSION])]) {
+							match ProjectArchive::read(&file) {
 
-	/// Create a list of available projects sorted by modification date from newest to oldest changes.
-	fn latest_modified_projects() -> Vec<String> {
-		use std::fs::{ read_dir, metadata };
-
-		// Loop through project dirs.
-		let projects_dir:String = storage::projects_dir();
-		let mut latest_projects:Vec<(String, SystemTime)> = Vec::new();
-		if let Ok(sub_dir_entries) = read_dir(projects_dir) {
-			for sub_dir in sub_dir_entries.flatten() {
-				if sub_dir.file_name() == "_AutoSave" { continue; }
-				if let Ok(sub_dir_meta) = metadata(sub_dir.path()) {
-					if !sub_dir_meta.is_dir() { continue; }
-
-					// Loop through files in the directory.
-					let mut latest_modified:SystemTime = sub_dir_meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
-					if let Ok(file_entries) = read_dir(sub_dir.path()) {
-						for file in file_entries.flatten() {
-							if let Ok(file_meta) = metadata(file.path()) {
-								if file_meta.is_dir() { continue; }
-
-								// Keep track of the last modified file.
-								if let Ok(modification_time) = file_meta.modified() {
-									if modification_time > latest_modified {
-										latest_modified = modification_time;
+								// Importing opens the project, which happens on the UI thread. Import under a new name when a project with the archived name already exists.
+								Ok(archive) => Window::execute_on_ui_thread(move || {
+									if let Err(error) = archive.import_as(&storage::unique_project_name(archive.name())) {
+										eprintln!("Could not import project archive: {error}");
 									}
-								}
+								}),
+								Err(error) => eprintln!("Could not import project archive: {error}")
 							}
 						}
+					});
+				},
+				title => {
+					let title: &str = if title == "Last session" { storage::AUTO_SAVE_PROJECT_NAME } else { title };
+					if let Err(error) = storage::select_project(clicked_tile_path.as_deref().unwrap_or(title)) {
+						eprintln!("Could not open project: {error}");
 					}
-
-					// Keep list of available projects.
-					latest_projects.push((sub_dir.path().display().to_string(), latest_modified));
 				}
 			}
-		}
-
-		// Sort projects by date.
-		latest_projects.sort_by(|(_, time1), (_, time2)| time2.cmp(time1));
+		}));
 
-		latest_projects.iter().map(|project| project.0.replace('\\', "/")).collect::<Vec<String>>()
+		// Return tile.
+		tile
 	}
 }
\ No newline at end of file
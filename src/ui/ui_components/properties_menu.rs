@@ -1,5 +1,5 @@
 use glass_panel::{ Drawable, ListenerType, elements::{ Rectangle, Row, Col, Id, Class, Border, Centered, Text } };
-use crate::robot_configuration::RobotConfig;
+use crate::robot_configuration::{ PrimitiveShape, RobotConfig };
 
 use super::super::Window;
 use std::rc::Rc;
@@ -105,9 +105,49 @@ impl Window {
 	pub(super) fn create_properties_tab_displacement(&self) -> Col {
 		use super::properties_menu_listener_handlers::*;
 
+		// Create the primitive inputs, the dimensions depend on the shape.
+		let primitive_shape:Option<PrimitiveShape> = self.selected_part().and_then(|part_index| RobotConfig::get().part(part_index)).and_then(|part| part.primitive().as_ref()).map(|primitive| *primitive.shape());
+		let primitive_input:Class = match primitive_shape {
+			Some(shape) => {
+				let mut primitive_inputs:Vec<Class> = vec![self.create_property_input_str_list("Shape", &get_primitive_shape, &set_primitive_shape)];
+				primitive_inputs.extend(match shape {
+					PrimitiveShape::Box { .. } => vec![
+						self.create_property_input_float_vec("Size", &|| get_primitive_size().to_vec(), &set_primitive_size)
+					],
+					PrimitiveShape::Cylinder { .. } | PrimitiveShape::Capsule { .. } => vec![
+						self.create_property_input_float("Radius", &get_primitive_radius, &set_primitive_radius),
+						self.create_property_input_float("Length", &get_primitive_length, &set_primitive_length),
+						self.create_property_input_str_list("Axis", &|| (get_primitive_axis() as usize, vec!["X".to_string(), "Y".to_string(), "Z".to_string()]), &|axis| set_primitive_axis(axis as u8))
+					],
+					PrimitiveShape::Sphere { .. } => vec![
+						self.create_property_input_float("Radius", &get_primitive_radius, &set_primitive_radius)
+					]
+				});
+				primitive_inputs.extend([
+					self.create_property_input_float_vec("Center", &|| get_primitive_center().to_vec(), &set_primitive_center),
+					self.create_property_input_button("Remove primitive", &remove_primitive)
+				]);
+				self.create_property_input_group("Primitive", primitive_inputs)
+			},
+			None => self.create_property_input_button("+Primitive", &add_primitive)
+		};
+
 		// Create input elements.
-		let input_elements:Vec<Class> = match &self.selected_joint() {
-			Some(_) => vec![self.create_property_input_float_vec("Position", &|| get_leg_position().to_vec(), &set_leg_position)],
+		let input_elements:Vec<Class> = match &self.selected_part() {
+			Some(_) => vec![
+				self.create_property_input_str_list("Parent", &get_part_parent, &set_part_parent),
+				self.create_property_input_float_vec("Position", &|| get_part_position().to_vec(), &set_part_position),
+				self.create_property_input_spacer(),
+				primitive_input,
+				self.create_property_input_spacer(),
+				self.create_property_input_group("Symmetry", vec![
+					self.create_property_input_button("Mirror across YZ", &mirror_part_yz),
+					self.create_property_input_button("Mirror across XZ", &mirror_part_xz),
+					self.create_property_input_spacer(),
+					self.create_property_input_float("Count", &get_radial_duplicate_count, &set_radial_duplicate_count),
+					self.create_property_input_button("Duplicate radially", &duplicate_part_radially)
+				])
+			],
 			None => Vec::new()
 		};
 
@@ -121,9 +161,9 @@ impl Window {
 
 		// Create input elements.
 		let mut input_elements:Vec<Class> = Vec::new();
-		if let Some(selected_joint_indexes) = &self.selected_joint() {
-			if let Some(selected_joint) = RobotConfig::get().get_joint(selected_joint_indexes[0], selected_joint_indexes[1]) {
-				if selected_joint.motor().is_some() {
+		if let Some(selected_part_index) = self.selected_part() {
+			if let Some(selected_part) = RobotConfig::get().part(*selected_part_index) {
+				if selected_part.motor().is_some() {
 					input_elements = vec![
 						self.create_property_input_group("Motor position", vec![
 							self.create_property_input_float_vec("Position", &|| handlers::get_motor_position().to_vec(), &handlers::set_motor_position)
@@ -157,20 +197,38 @@ impl Window {
 
 		// Create input elements.
 		let mut input_elements:Vec<Class> = Vec::new();
-		if let Some(selected_joint_indexes) = &self.selected_joint() {
-			if let Some(selected_joint) = RobotConfig::get().get_joint(selected_joint_indexes[0], selected_joint_indexes[1]) {
-				if selected_joint.kinematics_config().is_some() {
+		if let Some(selected_part_index) = self.selected_part() {
+			if let Some(selected_part) = RobotConfig::get().part(*selected_part_index) {
+				if let Some(kinematics_config) = selected_part.kinematics_config() {
 					input_elements = vec![
+						self.create_property_input_str_list("Mode", &handlers::get_kinematics_mode, &handlers::set_kinematics_mode),
+						self.create_property_input_spacer(),
 						self.create_property_input_group("Endpoint", vec![
 							self.create_property_input_float_vec("Position", &|| handlers::get_kinematics_endpoint().to_vec(), &handlers::set_kinematics_endpoint)
 						]),
-						self.create_property_input_spacer(),
-						self.create_property_input_group("Step configuration", vec![
+						self.create_property_input_spacer()
+					];
+
+					// Legs are configured by their step, manipulators by their target.
+					if kinematics_config.manipulator().is_some() {
+						input_elements.extend(vec![
+							self.create_property_input_group("Target", vec![
+								self.create_property_input_float_vec("Position", &|| handlers::get_manipulator_target().to_vec(), &handlers::set_manipulator_target)
+							]),
+							self.create_property_input_spacer(),
+							self.create_property_input_group("Path", vec![
+								self.create_property_input_float("Duration", &handlers::get_manipulator_path_duration, &handlers::set_manipulator_path_duration),
+								self.create_property_input_button("+Waypoint at target", &handlers::add_manipulator_waypoint),
+								self.create_property_input_button("Clear path", &handlers::clear_manipulator_path)
+							])
+						]);
+					} else {
+						input_elements.push(self.create_property_input_group("Step configuration", vec![
 							self.create_property_input_float_vec("Position", &|| handlers::get_kinematics_step_position().to_vec(), &handlers::set_kinematics_step_position),
 							self.create_property_input_float("Distance", &handlers::get_kinematics_step_distance, &handlers::set_kinematics_step_distance),
 							self.create_property_input_float("Height", &handlers::get_kinematics_step_height, &handlers::set_kinematics_step_height)
-						])
-					];
+						]));
+					}
 				} else {
 					input_elements = vec![
 						self.create_property_input_button("+Kinematics", &handlers::add_kinematics)
@@ -1,35 +1,209 @@
-use crate::{ kinematics::LegSynchronizer, robot_configuration::{ KinematicsConfig, LegConfig, MotorConfig, RobotConfig }, ui::Window };
+use crate::{ kinematics::LegSynchronizer, robot_configuration::{ KinematicsConfig, ManipulatorConfig, MirrorPlane, PartConfig, MotorConfig, PrimitiveConfig, PrimitiveShape, RobotConfig }, ui::Window };
+
+
+
+static mut RADIAL_DUPLICATE_COUNT:usize = 6;
+const PRIMITIVE_MIN_DIMENSION:f32 = 0.1;
 
 
 
 /* HELPER METHODS */
 
-/// Get a joint by its indexes.
-fn get_selected_joint_mut() -> Option<&'static mut LegConfig> {
+/// Get the selected part.
+fn get_selected_part_mut() -> Option<&'static mut PartConfig> {
 	let ui:&mut Window = Window::get();
-	let selected_joint_indexes:Option<[usize; 2]> = *ui.selected_joint();
+	let selected_part_index:Option<usize> = *ui.selected_part();
 	let robot_config:&mut RobotConfig = RobotConfig::get_mut();
-	selected_joint_indexes.and_then(|joint_indexes| robot_config.get_joint_mut(joint_indexes[0], joint_indexes[1]))
+	selected_part_index.and_then(|part_index| robot_config.part_mut(part_index))
 }
 
 
 
 /* DISPLACEMENT TAB METHODS */
 
-/// Get the position of a specific leg by its indexes.
-pub fn get_selected_joint_position_mut() -> Option<&'static mut [f32; 3]> {
-	get_selected_joint_mut().map(|joint| joint.position_mut())
+/// Get the position of the selected part.
+pub fn get_selected_part_position_mut() -> Option<&'static mut [f32; 3]> {
+	get_selected_part_mut().map(|part| part.position_mut())
 }
 
-/// Get the coordinates of the selected leg.
-pub fn get_leg_position() -> [f32; 3] {
-	get_selected_joint_position_mut().map(|position| *position).unwrap_or([0.0; 3])
+/// Get the coordinates of the selected part.
+pub fn get_part_position() -> [f32; 3] {
+	get_selected_part_position_mut().map(|position| *position).unwrap_or([0.0; 3])
 }
 
-/// Set the position of the selected leg.
-pub fn set_leg_position(index:usize, value:f32) {
-	if let Some(joint_position) = get_selected_joint_position_mut() {
-		joint_position[index] = value;
+/// Set the position of the selected part.
+pub fn set_part_position(index:usize, value:f32) {
+	if let Some(part_position) = get_selected_part_position_mut() {
+		part_position[index] = value;
+		Window::get().update_robot_config_in_scene_synchronized();
+	}
+}
+
+/// Mirror the selected part and all parts below it across the YZ plane.
+pub fn mirror_part_yz() {
+	mirror_selected_part(MirrorPlane::YZ);
+}
+
+/// Mirror the selected part and all parts below it across the XZ plane.
+pub fn mirror_part_xz() {
+	mirror_selected_part(MirrorPlane::XZ);
+}
+
+/// Mirror the selected part and all parts below it.
+fn mirror_selected_part(plane:MirrorPlane) {
+	if let Some(part_index) = *Window::get().selected_part() {
+		RobotConfig::get_mut().mirror_part(part_index, plane);
+	}
+}
+
+/// Get the amount of instances the radial duplication creates.
+pub fn get_radial_duplicate_count() -> f32 {
+	unsafe { RADIAL_DUPLICATE_COUNT as f32 }
+}
+
+/// Set the amount of instances the radial duplication creates.
+pub fn set_radial_duplicate_count(count:f32) {
+	unsafe { RADIAL_DUPLICATE_COUNT = count.round().max(2.0) as usize; }
+}
+
+/// Duplicate the selected part and all parts below it around the vertical axis.
+pub fn duplicate_part_radially() {
+	if let Some(part_index) = *Window::get().selected_part() {
+		RobotConfig::get_mut().duplicate_part_radially(part_index, unsafe { RADIAL_DUPLICATE_COUNT });
+	}
+}
+
+/// List the parts the selected part could be attached to. Parts below the selected part are excluded to prevent loops.
+fn get_parent_candidates() -> Vec<Option<usize>> {
+	let robot_config:&RobotConfig = RobotConfig::get();
+	match Window::get().selected_part() {
+		Some(part_index) => {
+			let subtree:Vec<usize> = robot_config.subtree(*part_index);
+			[vec![None], (0..robot_config.parts().len()).filter(|candidate| !subtree.contains(candidate)).map(Some).collect::<Vec<Option<usize>>>()].concat()
+		},
+		None => Vec::new()
+	}
+}
+
+/// Get the index of the current parent of the selected part in the list of candidates, along with the names of the candidates.
+pub fn get_part_parent() -> (usize, Vec<String>) {
+	let robot_config:&RobotConfig = RobotConfig::get();
+	let candidates:Vec<Option<usize>> = get_parent_candidates();
+	let current_parent:Option<usize> = Window::get().selected_part().and_then(|part_index| robot_config.part(part_index)).and_then(|part| *part.parent());
+	let names:Vec<String> = candidates.iter().map(|candidate| candidate.map(|part_index| robot_config.parts()[part_index].name().to_string()).unwrap_or("None".to_string())).collect::<Vec<String>>();
+	(candidates.iter().position(|candidate| *candidate == current_parent).unwrap_or(0), names)
+}
+
+/// Attach the selected part to a different parent.
+pub fn set_part_parent(candidate_index:usize) {
+	if let (Some(part_index), Some(parent)) = (*Window::get().selected_part(), get_parent_candidates().get(candidate_index)) {
+		RobotConfig::get_mut().set_parent(part_index, *parent);
+	}
+}
+
+
+
+/* PRIMITIVE METHODS */
+
+/// Replace the mesh of the selected part by a box primitive.
+pub fn add_primitive() {
+	if let Some(part_index) = *Window::get().selected_part() {
+		RobotConfig::get_mut().set_part_primitive(part_index, Some(PrimitiveConfig::new(PrimitiveShape::all()[0], [0.0; 3])));
+	}
+}
+
+/// Remove the primitive of the selected part, leaving it without mesh.
+pub fn remove_primitive() {
+	if let Some(part_index) = *Window::get().selected_part() {
+		RobotConfig::get_mut().set_part_primitive(part_index, None);
+	}
+}
+
+/// Get the primitive of the selected part.
+pub fn get_selected_part_primitive_mut() -> Option<&'static mut PrimitiveConfig> {
+	get_selected_part_mut().and_then(|part| part.primitive_mut().as_mut())
+}
+
+/// Get the index of the shape of the selected primitive, along with the names of all shapes.
+pub fn get_primitive_shape() -> (usize, Vec<String>) {
+	let shapes:[PrimitiveShape; 4] = PrimitiveShape::all();
+	let shape_name:&str = get_selected_part_primitive_mut().map(|primitive| primitive.shape().name()).unwrap_or_default();
+	(shapes.iter().position(|shape| shape.name() == shape_name).unwrap_or(0), shapes.iter().map(|shape| shape.name().to_string()).collect::<Vec<String>>())
+}
+
+/// Change the shape of the selected primitive. The new shape starts with default dimensions.
+pub fn set_primitive_shape(shape_index:usize) {
+	if let Some(primitive) = get_selected_part_primitive_mut() {
+		if primitive.shape().name() != PrimitiveShape::all()[shape_index].name() {
+			*primitive.shape_mut() = PrimitiveShape::all()[shape_index];
+			let ui:&mut Window = Window::get();
+			ui.update_robot_properties_menu_synchronized();
+			ui.update_robot_config_in_scene_synchronized();
+		}
+	}
+}
+
+/// Get the center of the selected primitive.
+pub fn get_primitive_center() -> [f32; 3] {
+	get_selected_part_primitive_mut().map(|primitive| *primitive.center()).unwrap_or([0.0; 3])
+}
+
+/// Set the center of the selected primitive.
+pub fn set_primitive_center(index:usize, value:f32) {
+	if let Some(primitive) = get_selected_part_primitive_mut() {
+		primitive.center_mut()[index] = value;
+		Window::get().update_robot_config_in_scene_synchronized();
+	}
+}
+
+/// Get the size of the selected box primitive.
+pub fn get_primitive_size() -> [f32; 3] {
+	get_selected_part_primitive_mut().and_then(|primitive| primitive.shape_mut().size_mut().map(|size| *size)).unwrap_or([0.0; 3])
+}
+
+/// Set the size of the selected box primitive.
+pub fn set_primitive_size(index:usize, value:f32) {
+	if let Some(size) = get_selected_part_primitive_mut().and_then(|primitive| primitive.shape_mut().size_mut()) {
+		size[index] = value.max(PRIMITIVE_MIN_DIMENSION);
+		Window::get().update_robot_config_in_scene_synchronized();
+	}
+}
+
+/// Get the radius of the selected primitive.
+pub fn get_primitive_radius() -> f32 {
+	get_selected_part_primitive_mut().and_then(|primitive| primitive.shape_mut().radius_mut().map(|radius| *radius)).unwrap_or(0.0)
+}
+
+/// Set the radius of the selected primitive.
+pub fn set_primitive_radius(value:f32) {
+	if let Some(radius) = get_selected_part_primitive_mut().and_then(|primitive| primitive.shape_mut().radius_mut()) {
+		*radius = value.max(PRIMITIVE_MIN_DIMENSION);
+		Window::get().update_robot_config_in_scene_synchronized();
+	}
+}
+
+/// Get the length of the selected primitive.
+pub fn get_primitive_length() -> f32 {
+	get_selected_part_primitive_mut().and_then(|primitive| primitive.shape_mut().length_mut().map(|length| *length)).unwrap_or(0.0)
+}
+
+/// Set the length of the selected primitive.
+pub fn set_primitive_length(value:f32) {
+	if let Some(length) = get_selected_part_primitive_mut().and_then(|primitive| primitive.shape_mut().length_mut()) {
+		*length = value.max(PRIMITIVE_MIN_DIMENSION);
+		Window::get().update_robot_config_in_scene_synchronized();
+	}
+}
+
+/// Get the axis of the selected primitive.
+pub fn get_primitive_axis() -> u8 {
+	get_selected_part_primitive_mut().and_then(|primitive| primitive.shape().axis()).unwrap_or(2)
+}
+
+/// Set the axis of the selected primitive.
+pub fn set_primitive_axis(axis:u8) {
+	if let Some(primitive_axis) = get_selected_part_primitive_mut().and_then(|primitive| primitive.shape_mut().axis_mut()) {
+		*primitive_axis = axis;
 		Window::get().update_robot_config_in_scene_synchronized();
 	}
 }
@@ -40,9 +214,9 @@ pub fn set_leg_position(index:usize, value:f32) {
 
 /// Add a motor at position 0.
 pub fn add_motor() {
-	if let Some(joint) = get_selected_joint_mut() {
-		if joint.motor().is_none() {
-			*joint.motor_mut() = Some(MotorConfig::empty());
+	if let Some(part) = get_selected_part_mut() {
+		if part.motor().is_none() {
+			*part.motor_mut() = Some(MotorConfig::empty());
 			let ui:&mut Window = Window::get();
 			ui.update_robot_properties_menu_synchronized();
 			ui.update_robot_config_in_scene_synchronized();
@@ -50,10 +224,10 @@ pub fn add_motor() {
 	}
 }
 
-/// Get the position of a specific leg by its indexes.
-pub fn get_selected_joint_motor_mut() -> Option<&'static mut MotorConfig> {
-	match get_selected_joint_mut(){
-		Some(joint) => match joint.motor_mut() {
+/// Get the motor of the selected part.
+pub fn get_selected_part_motor_mut() -> Option<&'static mut MotorConfig> {
+	match get_selected_part_mut(){
+		Some(part) => match part.motor_mut() {
 			Some(motor) => Some(motor),
 			None => None
 		},
@@ -63,12 +237,12 @@ pub fn get_selected_joint_motor_mut() -> Option<&'static mut MotorConfig> {
 
 /// Get the coordinates of the selected leg.
 pub fn get_motor_position() -> [f32; 3] {
-	get_selected_joint_motor_mut().map(|motor| *motor.position()).unwrap_or([0.0; 3])
+	get_selected_part_motor_mut().map(|motor| *motor.position()).unwrap_or([0.0; 3])
 }
 
 /// Set the position of the selected leg.
 pub fn set_motor_position(index:usize, value:f32) {
-	if let Some(motor) = get_selected_joint_motor_mut() {
+	if let Some(motor) = get_selected_part_motor_mut() {
 		motor.position_mut()[index] = value;
 		Window::get().update_robot_config_in_scene_synchronized();
 	}
@@ -76,12 +250,12 @@ pub fn set_motor_position(index:usize, value:f32) {
 
 /// Get the default rotation of the selected motor.
 pub fn get_motor_rotation_axis() -> u8 {
-	get_selected_joint_motor_mut().map(|motor| *motor.rotation_axis()).unwrap_or(0)
+	get_selected_part_motor_mut().map(|motor| *motor.rotation_axis()).unwrap_or(0)
 }
 
 /// Set the default rotation of the selected motor.
 pub fn set_motor_rotation_axis(axis:u8) {
-	if let Some(motor) = get_selected_joint_motor_mut() {
+	if let Some(motor) = get_selected_part_motor_mut() {
 		*motor.rotation_axis_mut() = axis;
 		Window::get().update_robot_config_in_scene_synchronized();
 	}
@@ -89,12 +263,12 @@ pub fn set_motor_rotation_axis(axis:u8) {
 
 /// Get the default rotation of the selected motor.
 pub fn get_motor_default_rotation() -> f32 {
-	get_selected_joint_motor_mut().map(|motor| *motor.default_rotation()).unwrap_or(0.0)
+	get_selected_part_motor_mut().map(|motor| *motor.default_rotation()).unwrap_or(0.0)
 }
 
 /// Set the default rotation of the selected motor.
 pub fn set_motor_default_rotation(rotation:f32) {
-	if let Some(motor) = get_selected_joint_motor_mut() {
+	if let Some(motor) = get_selected_part_motor_mut() {
 		let bounds:&[f32; 2] = motor.rotation_range();
 		*motor.default_rotation_mut() = rotation.max(bounds[0]).min(bounds[1]);
 		Window::get().update_robot_config_in_scene_synchronized();
@@ -103,12 +277,12 @@ pub fn set_motor_default_rotation(rotation:f32) {
 
 /// Get the rotation range of the selected motor.
 pub fn get_motor_rotation_range() -> [f32; 2] {
-	get_selected_joint_motor_mut().map(|motor| *motor.rotation_range()).unwrap_or([0.0; 2])
+	get_selected_part_motor_mut().map(|motor| *motor.rotation_range()).unwrap_or([0.0; 2])
 }
 
 /// Set the rotation range of the selected motor.
 pub fn set_motor_rotation_range(index:usize, value:f32) {
-	if let Some(motor) = get_selected_joint_motor_mut() {
+	if let Some(motor) = get_selected_part_motor_mut() {
 
 		// Get default and current rotation.
 		let default_rotation:f32 = *motor.default_rotation();
@@ -130,17 +304,17 @@ pub fn set_motor_rotation_range(index:usize, value:f32) {
 
 /// Get the current rotation of the selected motor.
 pub fn get_motor_current_rotation() -> f32 {
-	get_selected_joint_motor_mut().map(|motor| *motor.current_rotation()).unwrap_or(0.0)
+	get_selected_part_motor_mut().map(|motor| *motor.current_rotation()).unwrap_or(0.0)
 }
 
 /// Set the current rotation of the selected motor.
 pub fn set_motor_current_rotation(rotation:f32) {
-	if let Some(selected_joint_indexes) = Window::get().selected_joint() {
-		if let Some(selected_joint) = RobotConfig::get_mut().get_joint_mut(selected_joint_indexes[0], selected_joint_indexes[1]) {
-			if let Some(motor) = selected_joint.motor_mut() {
+	if let Some(selected_part_index) = *Window::get().selected_part() {
+		if let Some(selected_part) = RobotConfig::get_mut().part_mut(selected_part_index) {
+			if let Some(motor) = selected_part.motor_mut() {
 				let bounds:&[f32; 2] = motor.rotation_range();
 				*motor.current_rotation_mut() = rotation.max(bounds[0]).min(bounds[1]);
-				Window::get().update_scene_motor(selected_joint_indexes[0], selected_joint_indexes[1]);
+				Window::get().update_scene_motor(selected_part_index);
 			}
 		}
 	}
@@ -152,9 +326,9 @@ pub fn set_motor_current_rotation(rotation:f32) {
 
 /// Add a motor at position 0.
 pub fn add_kinematics() {
-	if let Some(joint) = get_selected_joint_mut() {
-		if joint.kinematics_config().is_none() {
-			*joint.kinematics_config_mut() = Some(KinematicsConfig::empty());
+	if let Some(part) = get_selected_part_mut() {
+		if part.kinematics_config().is_none() {
+			*part.kinematics_config_mut() = Some(KinematicsConfig::empty());
 			let ui:&mut Window = Window::get();
 			ui.update_robot_properties_menu_synchronized();
 			ui.update_robot_config_in_scene_synchronized();
@@ -163,9 +337,9 @@ pub fn add_kinematics() {
 }
 
 /// Get the position of the kinematics endpoint of a specific leg.
-pub fn get_selected_joint_kinematics_mut() -> Option<&'static mut KinematicsConfig> {
-	match get_selected_joint_mut(){
-		Some(joint) => match joint.kinematics_config_mut() {
+pub fn get_selected_part_kinematics_mut() -> Option<&'static mut KinematicsConfig> {
+	match get_selected_part_mut(){
+		Some(part) => match part.kinematics_config_mut() {
 			Some(kinematics) => Some(kinematics),
 			None => None
 		},
@@ -175,12 +349,12 @@ pub fn get_selected_joint_kinematics_mut() -> Option<&'static mut KinematicsConf
 
 /// Get the position of the kinematics endpoint of the selected leg.
 pub fn get_kinematics_endpoint() -> [f32; 3] {
-	get_selected_joint_kinematics_mut().map(|kinematics| *kinematics.leg_endpoint()).unwrap_or([0.0; 3])
+	get_selected_part_kinematics_mut().map(|kinematics| *kinematics.leg_endpoint()).unwrap_or([0.0; 3])
 }
 
 /// Set the position of the kinematics endpoint of the selected leg.
 pub fn set_kinematics_endpoint(index:usize, value:f32) {
-	if let Some(kinematics) = get_selected_joint_kinematics_mut() {
+	if let Some(kinematics) = get_selected_part_kinematics_mut() {
 		kinematics.leg_endpoint_mut()[index] = value;
 		Window::get().update_robot_config_in_scene_synchronized();
 	}
@@ -188,12 +362,12 @@ pub fn set_kinematics_endpoint(index:usize, value:f32) {
 
 /// Get the position of the kinematics step.
 pub fn get_kinematics_step_position() -> [f32; 3] {
-	get_selected_joint_kinematics_mut().map(|kinematics| *kinematics.step_position()).unwrap_or([0.0; 3])
+	get_selected_part_kinematics_mut().map(|kinematics| *kinematics.step_position()).unwrap_or([0.0; 3])
 }
 
 /// Set the position of the kinematics step.
 pub fn set_kinematics_step_position(index:usize, value:f32) {
-	if let Some(kinematics) = get_selected_joint_kinematics_mut() {
+	if let Some(kinematics) = get_selected_part_kinematics_mut() {
 		kinematics.step_position_mut()[index] = value;
 		Window::get().update_robot_config_in_scene_synchronized();
 	}
@@ -201,12 +375,12 @@ pub fn set_kinematics_step_position(index:usize, value:f32) {
 
 /// Get the distance of the kinematics step.
 pub fn get_kinematics_step_distance() -> f32 {
-	get_selected_joint_kinematics_mut().map(|kinematics| *kinematics.step_distance()).unwrap_or(0.0)
+	get_selected_part_kinematics_mut().map(|kinematics| *kinematics.step_distance()).unwrap_or(0.0)
 }
 
 /// Get the distance of the kinematics step.
 pub fn set_kinematics_step_distance(value:f32) {
-	if let Some(kinematics) = get_selected_joint_kinematics_mut() {
+	if let Some(kinematics) = get_selected_part_kinematics_mut() {
 		*kinematics.step_distance_mut() = value;
 		Window::get().update_robot_config_in_scene_synchronized();
 	}
@@ -214,12 +388,12 @@ pub fn set_kinematics_step_distance(value:f32) {
 
 /// Get the height of the kinematics step.
 pub fn get_kinematics_step_height() -> f32 {
-	get_selected_joint_kinematics_mut().map(|kinematics| *kinematics.step_height()).unwrap_or(0.0)
+	get_selected_part_kinematics_mut().map(|kinematics| *kinematics.step_height()).unwrap_or(0.0)
 }
 
 /// Get the height of the kinematics step.
 pub fn set_kinematics_step_height(value:f32) {
-	if let Some(kinematics) = get_selected_joint_kinematics_mut() {
+	if let Some(kinematics) = get_selected_part_kinematics_mut() {
 		*kinematics.step_height_mut() = value;
 		Window::get().update_robot_config_in_scene_synchronized();
 	}
@@ -227,6 +401,86 @@ pub fn set_kinematics_step_height(value:f32) {
 
 
 
+/* MANIPULATOR METHODS */
+
+/// Get the manipulator config of the selected part.
+pub fn get_selected_part_manipulator_mut() -> Option<&'static mut ManipulatorConfig> {
+	get_selected_part_kinematics_mut().and_then(|kinematics| kinematics.manipulator_mut().as_mut())
+}
+
+/// Get the kinematics mode of the selected part. Legs follow the gait, manipulators follow their target.
+pub fn get_kinematics_mode() -> (usize, Vec<String>) {
+	let mode:usize = if get_selected_part_manipulator_mut().is_some() { 1 } else { 0 };
+	(mode, vec!["Leg".to_string(), "Manipulator".to_string()])
+}
+
+/// Set the kinematics mode of the selected part.
+pub fn set_kinematics_mode(mode:usize) {
+	if let Some(kinematics) = get_selected_part_kinematics_mut() {
+		if (mode == 1) != kinematics.manipulator().is_some() {
+			*kinematics.manipulator_mut() = if mode == 1 { Some(ManipulatorConfig::empty()) } else { None };
+			let ui:&mut Window = Window::get();
+			ui.update_robot_properties_menu_synchronized();
+			ui.update_robot_config_in_scene_synchronized();
+		}
+	}
+}
+
+/// Get the target of the selected manipulator.
+pub fn get_manipulator_target() -> [f32; 3] {
+	get_selected_part_manipulator_mut().map(|manipulator| *manipulator.target()).unwrap_or([0.0; 3])
+}
+
+/// Set the target of the selected manipulator and move the manipulator towards it.
+pub fn set_manipulator_target(index:usize, value:f32) {
+	if let Some(manipulator) = get_selected_part_manipulator_mut() {
+		manipulator.target_mut()[index] = value;
+		move_manipulators_to_targets();
+		Window::get().update_robot_config_in_scene_synchronized();
+	}
+}
+
+/// Get the amount of seconds the selected manipulator takes to run through its path.
+pub fn get_manipulator_path_duration() -> f32 {
+	get_selected_part_manipulator_mut().map(|manipulator| *manipulator.path_duration()).unwrap_or(0.0)
+}
+
+/// Set the amount of seconds the selected manipulator takes to run through its path.
+pub fn set_manipulator_path_duration(duration:f32) {
+	if let Some(manipulator) = get_selected_part_manipulator_mut() {
+		*manipulator.path_duration_mut() = duration.max(0.0);
+	}
+}
+
+/// Add the current target of the selected manipulator as a waypoint to its path.
+pub fn add_manipulator_waypoint() {
+	if let Some(manipulator) = get_selected_part_manipulator_mut() {
+		let target:[f32; 3] = *manipulator.target();
+		manipulator.path_mut().push(target);
+		Window::get().update_robot_config_in_scene_synchronized();
+	}
+}
+
+/// Remove all waypoints from the path of the selected manipulator.
+pub fn clear_manipulator_path() {
+	if let Some(manipulator) = get_selected_part_manipulator_mut() {
+		manipulator.path_mut().clear();
+		Window::get().update_robot_config_in_scene_synchronized();
+	}
+}
+
+/// Move all manipulators to their target. While the realtime kinematics are active, they already take care of this.
+fn move_manipulators_to_targets() {
+	use crate::kinematics::{ realtime_kinematics_active, update_manipulator_rotations, RobotSkeleton };
+
+	if !realtime_kinematics_active() {
+		RobotSkeleton::create();
+		update_manipulator_rotations(0.0);
+	}
+}
+
+
+
 /* CONTROLLER TAB METHODS */
 
 /// Get a boolean indicating if the realtime kinematics are showing.
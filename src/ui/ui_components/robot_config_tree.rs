@@ -1,8 +1,10 @@
-use glass_panel::{ Drawable, ListenerType, ListenerCallback, elements::{ Rectangle, Positioned, Col, ScrollView, Id } };
-use crate::robot_configuration::{ RobotConfig, LegConfig };
+use glass_panel::{ Drawable, ListenerType, ListenerCallback, elements::{ Rectangle, Positioned, Col, ScrollView, Id, Text } };
+use crate::robot_configuration::{ RobotConfig, ConfigDiagnostic, DiagnosticSeverity };
 use glass_panel::DrawableData;
 use super::super::Window;
-use std::rc::Rc;
+use std::{ rc::Rc, time::{ Duration, Instant } };
+
+static CONFIG_DIAGNOSTICS_DELAY:Duration = Duration::from_millis(250); // Validating walks the skeleton of every leg, so edits made in quick succession, like dragging a value, share a single validation.
 
 impl Window {
 
@@ -20,11 +22,31 @@ impl Window {
 		self.update_robot_properties_menu_now();
 	}
 
+	/// Validate the config again shortly after it was modified. Further modifications in the meantime are covered by the same validation.
+	pub(crate) fn update_robot_config_diagnostics_delayed(&mut self) {
+		if self.config_diagnostics_due().is_none() {
+			*self.config_diagnostics_due_mut() = Some(Instant::now() + CONFIG_DIAGNOSTICS_DELAY);
+		}
+	}
+
+	/// Update the robot components tree only if the diagnostics of the config changed since the last validation. Keeps the tree untouched while dragging property values.
+	pub(crate) fn update_robot_config_diagnostics_if_due(&mut self) {
+		if self.config_diagnostics_due().is_some_and(|due| Instant::now() >= due) {
+			*self.config_diagnostics_due_mut() = None;
+			if &RobotConfig::get().validate() != self.config_diagnostics() {
+				self.update_robot_config_synchronized_tree_now();
+			}
+		}
+	}
+
 	/// Update the robot components tree.
-	fn update_robot_config_synchronized_tree_now(&mut self) {
+	pub(super) fn update_robot_config_synchronized_tree_now(&mut self) {
 		use super::robot_config_tree_listener_handlers::*;
 
 		let robot_config:&RobotConfig = RobotConfig::get();
+		let diagnostics:Vec<ConfigDiagnostic> = robot_config.validate();
+		*self.config_diagnostics_mut() = diagnostics.clone();
+		*self.config_diagnostics_due_mut() = None;
 
 		// Throughout this function, many threads will be created and removed shortly.
 		// This is not very efficient and should be replaced soon, but it is important to not remove UI elements from within a callback function stored in that UI element.
@@ -32,55 +54,32 @@ impl Window {
 		
 		// Create rows for tree.
 		let mut tree_row_entries:Vec<Id> = Vec::new();
-		if robot_config.body().is_none() {
-
-			// Add body button.
-			tree_row_entries.push(
-				self.create_ui_robot_tree_row(0, "+ body", vec![
-					("RobotTreeAddBody", ListenerType::LeftDown, Rc::new(|_,_,_,_| replace_body()))
-				])
-			);
-
-		} else {
+		for diagnostic in diagnostics.iter().filter(|diagnostic| diagnostic.part_index().is_none()) {
+			tree_row_entries.push(self.create_ui_robot_tree_diagnostic_row(0, diagnostic));
+		}
 
-			// Body entry.
-			tree_row_entries.push(
-				self.create_ui_robot_tree_row(0, "Body", vec![
-					("RobotTreeShowReplacementToolTip", ListenerType::LeftDown, Rc::new(|_, _, _, _| set_no_joints_selected())),
-					("RobotTreeShowReplacementToolTip", ListenerType::RightDown, Rc::new(|_, absolute_position, _, _| show_body_replacement_menu(absolute_position)))
-				])
-			);
-
-			// Loop through legs.
-			for leg_index in 0..robot_config.legs().len() {
-				let leg:&Vec<LegConfig> = &robot_config.legs()[leg_index];
-
-				// Joint entries.
-				for joint_index in 0..leg.len() {
-					let mut joint_entry:Id = self.create_ui_robot_tree_row(1 + joint_index, &format!("Leg{leg_index} Joint{joint_index}"), vec![
-						("RobotTreeSelectJoint", ListenerType::LeftDown, Rc::new(|_, _, _, element_data| select_clicked_joint(element_data))),
-						("RobotTreeShowReplacementToolTip", ListenerType::RightDown, Rc::new(|_, absolute_position, _, element_data| show_joint_replacement_menu(absolute_position, element_data)))
-					]);
-					joint_entry.data_set_mut().set_settings_values::<usize>(vec![("leg_index", leg_index), ("joint_index", joint_index)]);
-					tree_row_entries.push(joint_entry);
-				}
-
-				// Add joint button.
-				let mut add_joint_button:Id = self.create_ui_robot_tree_row(1 + leg.len(), "+ joint", vec![
-					("RobotTreeAddJoint", ListenerType::LeftDown, Rc::new(|_, _, _, element_data:&mut DrawableData| add_joint(element_data)))
-				]);
-				add_joint_button.data_set_mut().set_setting_value::<usize>("leg_index", leg_index);
-				tree_row_entries.push(add_joint_button);
+		// Part entries in tree order.
+		for (part_index, depth) in robot_config.tree_order() {
+			let mut part_entry:Id = self.create_ui_robot_tree_row(&format!("RobotTreePart{part_index}"), depth, robot_config.parts()[part_index].name(), vec![
+				("RobotTreeSelectPart", ListenerType::LeftDown, Rc::new(|_, _, _, element_data| select_clicked_part(element_data))),
+				("RobotTreeShowReplacementToolTip", ListenerType::RightDown, Rc::new(|_, absolute_position, _, element_data| show_part_replacement_menu(absolute_position, element_data)))
+			]);
+			part_entry.data_set_mut().set_setting_value::<usize>("part_index", part_index);
+			tree_row_entries.push(part_entry);
+
+			// Diagnostics of the part.
+			for diagnostic in diagnostics.iter().filter(|diagnostic| diagnostic.part_index() == &Some(part_index)) {
+				tree_row_entries.push(self.create_ui_robot_tree_diagnostic_row(depth + 1, diagnostic));
 			}
-
-			// Add leg button.
-			tree_row_entries.push(
-				self.create_ui_robot_tree_row(1, "+ leg", vec![
-					("RobotTreeAddLeg", ListenerType::LeftDown, Rc::new(|_,_,_,_| add_leg()))
-				])
-			);
 		}
 
+		// Add part button, adds a child to the selected part or a new root part.
+		tree_row_entries.push(
+			self.create_ui_robot_tree_row("RobotTreeAddPart", 0, if self.selected_part().is_some() { "+ child part" } else { "+ part" }, vec![
+				("RobotTreeAddPart", ListenerType::LeftDown, Rc::new(|_,_,_,_| add_part()))
+			])
+		);
+
 		// Create the column list.
 		let scroll_view_width:usize = self.setting("menu_content_width");
 		let scroll_view_height:usize = self.setting("menu_robot_config_tree_height");
@@ -96,13 +95,13 @@ impl Window {
 	}
 
 	/// Create a singular rot in the robot components tree.
-	fn create_ui_robot_tree_row(&self, depth:usize, text:&str, listeners:Vec<(&str, ListenerType, ListenerCallback)>) -> Id {
+	fn create_ui_robot_tree_row(&self, id:&str, depth:usize, text:&str, listeners:Vec<(&str, ListenerType, ListenerCallback)>) -> Id {
 
 		// Settings.
 		let margin:usize = self.setting::<usize>("menu_content_margin") * 3;
 
 		// Create element.
-		let mut row:Id = Id::new(&text.replace(' ', ""), vec![
+		let mut row:Id = Id::new(id, vec![
 			&Rectangle::new(self.setting::<usize>("menu_content_width") - 10, 20, self.setting("color_foreground"), vec![
 				&Positioned::new(3 + depth * margin, 0, vec![
 					&self.default_text(text)
@@ -120,4 +119,21 @@ impl Window {
 		// Return.
 		row
 	}
+
+	/// Create a row in the robot components tree that describes a diagnostic of the config.
+	fn create_ui_robot_tree_diagnostic_row(&self, depth:usize, diagnostic:&ConfigDiagnostic) -> Id {
+
+		// Settings.
+		let margin:usize = self.setting::<usize>("menu_content_margin") * 3;
+		let color:u32 = self.setting(match diagnostic.severity() { DiagnosticSeverity::Warning => "color_warning", DiagnosticSeverity::Error => "color_error" });
+
+		// Create element.
+		Id::new("RobotTreeDiagnostic", vec![
+			&Rectangle::new(self.setting::<usize>("menu_content_width") - 10, 16, self.setting("color_foreground"), vec![
+				&Positioned::new(3 + depth * margin, 0, vec![
+					&Text::new(self.font(), &format!("! {}", diagnostic.message()), (self.setting::<usize>("default_font_height") as f32 * 0.75) as usize, color)
+				])
+			])
+		])
+	}
 }
\ No newline at end of file
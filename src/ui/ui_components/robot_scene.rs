@@ -1,5 +1,5 @@
 use glass_panel::{ ListenerType, elements::Id, tridimensional::{ model::{ Mesh, VertexMath, materials::SimpleColorMaterial }, Entity, Scene }, Drawable };
-use crate::robot_configuration::{ KinematicsConfig, LegConfig, MotorConfig, RobotConfig };
+use crate::robot_configuration::{ KinematicsConfig, ManipulatorConfig, MotorConfig, PartConfig, RobotConfig };
 use std::{ error::Error, rc::Rc };
 use super::super::Window;
 
@@ -47,113 +47,135 @@ impl Window {
 
 	/// Update the robot components in the 3D scene after all listeners have finished.
 	pub(crate) fn update_robot_config_in_scene_synchronized(&self) {
-		Window::get().execute_post_listener(&||
+		Window::get().execute_post_listener(&|| {
 			if let Err(error) = Window::get().update_robot_config_in_scene_now() {
 				eprintln!("{error}");
 			}
-		);
+			Window::get().update_robot_config_diagnostics_delayed();
+		});
 	}
 
 	/// Update the robot components in the 3D scene.
 	pub(crate) fn update_robot_config_in_scene_now(&mut self) -> Result<(), Box<dyn Error>> {
-
-		// Create local copies of the robot config to work around not borrowing self twice.
-		let robot_config:&mut RobotConfig = RobotConfig::get_mut();
 		let scene:&mut Scene = self.get_scene_mut();
+		Self::build_robot_in_scene(scene)?;
+		scene.add_entity(Entity::new("DEBUG", Mesh::raw(vec![[0.0, 0.0, 0.0], [10.0, 0.0, 0.0], [-10.0, 0.0, 0.0], [0.0, -10.0, 0.0], [0.0, 10.0, 0.0]], vec![[1,0,2],[3,0,4]])));
+		Ok(())
+	}
 
-		// Create / update body.
-		if let Some(path) = robot_config.body() {
-			match scene.entity_by_name_mut("RobotBody") {
-				Some(body) => body.set_obj(path)?,
-				None => scene.add_entity(Entity::from_obj("RobotBody", path)?)
-			}
-		} else {
-			scene.remove_entity_by_name("RobotBody");
-		}
-
-		// Create / update legs.
-		if let Some(body) = scene.entity_by_name_mut("RobotBody") {
-			body.remove_children();
-
-			// Build legs.
-			for leg_index in 0..robot_config.legs().len() {
-				let leg_config:&Vec<LegConfig> = &robot_config.legs()[leg_index];
-
-				// Build joints for leg.
-				for joint_index in 0..leg_config.len() {
+	/// Build the robot components of the current robot config in a scene. Does not require a window to exist.
+	pub(crate) fn build_robot_in_scene(scene:&mut Scene) -> Result<(), Box<dyn Error>> {
+		let robot_config:&RobotConfig = RobotConfig::get();
 
-					// Create the joint's entity.
-					let joint:Entity = Self::create_joint_entity(body, leg_index, joint_index).unwrap();
+		// Remove the previously built parts.
+		let previous_parts:Vec<String> = scene.entities().iter().map(|entity| entity.get_name()).filter(|name| name.starts_with("RobotPart")).collect::<Vec<String>>();
+		for name in previous_parts {
+			scene.remove_entity_by_name(&name);
+		}
 
-					// Find parent to add the entity to.
-					let mut parent:&mut Entity = body;
-					if joint_index > 0 {
-						let parent_joint_index:usize = joint_index - 1;
-						parent = body.child_by_name_mut(&format!("RobotLeg{leg_index}Joint{parent_joint_index}")).unwrap();
-					}
+		// Build each root part including all parts attached below it.
+		for root_index in robot_config.roots() {
+			scene.add_entity(Self::create_part_entity_tree(robot_config, root_index));
+		}
 
-					// Add the entity to its parent.
-					parent.add_child(joint);
+		// Add the step display of each leg to the pivot of the first motor of the leg.
+		for (leg_index, chain) in robot_config.legs().iter().enumerate() {
+			if let Some(kinematics_config) = chain.last().and_then(|part_index| robot_config.parts()[*part_index].kinematics_config().as_ref()) {
+				let step_origin:usize = *chain.iter().find(|part_index| robot_config.parts()[**part_index].motor().is_some()).unwrap_or(&chain[0]);
+				if let Some(pivot) = scene.entity_by_name_mut(&format!("RobotPart{step_origin}Pivot")) {
+					pivot.add_child(Self::create_kinematics_step_display(leg_index, kinematics_config));
 				}
 			}
 		}
 
-		scene.add_entity(Entity::new("DEBUG", Mesh::raw(vec![[0.0, 0.0, 0.0], [10.0, 0.0, 0.0], [-10.0, 0.0, 0.0], [0.0, -10.0, 0.0], [0.0, 10.0, 0.0]], vec![[1,0,2],[3,0,4]])));
+		// Add the target display of each manipulator to the pivot of the first motor of the manipulator.
+		for (manipulator_index, chain) in robot_config.manipulators().iter().enumerate() {
+			if let Some(manipulator_config) = chain.last().and_then(|part_index| robot_config.parts()[*part_index].kinematics_config().as_ref()).and_then(|kinematics| kinematics.manipulator().as_ref()) {
+				let target_origin:usize = *chain.iter().find(|part_index| robot_config.parts()[**part_index].motor().is_some()).unwrap_or(&chain[0]);
+				if let Some(pivot) = scene.entity_by_name_mut(&format!("RobotPart{target_origin}Pivot")) {
+					pivot.add_child(Self::create_manipulator_target_display(manipulator_index, manipulator_config));
+				}
+			}
+		}
 
 		Ok(())
 	}
 
+	/// Render the current robot config to a PNG file from the default camera angle. Does not require a window to exist.
+	pub fn render_robot_png(path:&str, size:[usize; 2]) -> Result<(), Box<dyn Error>> {
+		use glass_panel::tridimensional::lenses::OrthographicLens;
+		use crate::ui::{ DEFAULT_SCENE_CAMERA_POSITION, DEFAULT_SCENE_CAMERA_ROTATION };
 
+		// Build the robot in an offscreen scene.
+		let mut scene:Scene = Scene::new(size[0], size[1], &OrthographicLens::new(), vec![]);
+		Self::build_robot_in_scene(&mut scene)?;
 
-	/* ENTITY CREATION METHODS */
+		// Move camera to the default location.
+		scene.camera().set_position(&DEFAULT_SCENE_CAMERA_POSITION);
+		scene.camera().set_rotation(&DEFAULT_SCENE_CAMERA_ROTATION);
+
+		// Draw the scene to the file.
+		scene.draw_png(path)
+	}
 
-	/// Create an entity for a robot joint.
-	fn create_joint_entity(body:&mut Entity, leg_index:usize, joint_index:usize) -> Option<Entity> {
-		if let Some(joint_config) = RobotConfig::get().get_joint(leg_index, joint_index) {
-			let joint_name:String = format!("RobotLeg{leg_index}Joint{joint_index}");
-
-			// Create the pivot point for the leg.
-			let mut pivot:Entity = Entity::new(&(joint_name.to_string() + "Pivot"), Mesh::raw(Vec::new(), Vec::new()));
-			pivot.set_position(joint_config.position());
-			pivot.set_rotation(joint_config.rotation());
-			let mut motor:Entity = Entity::new(&(joint_name.to_string() + "Motor"), Mesh::raw(Vec::new(), Vec::new()));
-
-			// Create an entity of the joint's obj and add it as a child to the pivot.
-			if let Ok(mut joint) = Entity::from_obj(&joint_name, joint_config.obj()) {
-
-				// If the joint has a motor, modify the pivot.
-				if let Some(motor_config) = joint_config.motor() {
-					Self::add_motor_display_to_pivot(&mut pivot, motor_config);
-					
-					// Keep mesh in place despite displacement of motor.
-					let mut joint_offset:[f32; 3] = *motor_config.position();
-					joint_offset = [-joint_offset[0], -joint_offset[1], -joint_offset[2]];
-					joint.displace(&joint_offset);
-
-					// Set rotation of motor based on the robot config.
-					let mut rotation:[f32; 3] = [0.0; 3];
-					rotation[*motor_config.rotation_axis() as usize] = *motor_config.current_rotation();
-					motor.set_euler_rotation(&rotation);
-				}
-				motor.add_child(joint);
 
-				// If the joint has kinematics configuration, add visuals for it.
-				if let Some(kinematics_config) = joint_config.kinematics_config() {
-					let endpoint_display:Entity = Self::create_kinematics_endpoint_display(leg_index, joint_index, kinematics_config);
-					motor.add_child(endpoint_display);
 
-					if let Some(child) = body.child_by_name_mut(&format!("RobotLeg{leg_index}Joint0Pivot")) {
-						let step_display:Entity = Self::create_kinematics_step_display(leg_index, kinematics_config);
-						child.add_child(step_display);
-					}
+	/* ENTITY CREATION METHODS */
+
+	/// Create the entity of a part with the entities of all parts attached below it.
+	fn create_part_entity_tree(robot_config:&RobotConfig, part_index:usize) -> Entity {
+		let mut pivot:Entity = Self::create_part_entity(part_index, &robot_config.parts()[part_index]);
+		let children:Vec<Entity> = robot_config.children(part_index).into_iter().map(|child_index| Self::create_part_entity_tree(robot_config, child_index)).collect::<Vec<Entity>>();
+		if let Some(part_entity) = pivot.child_by_name_mut(&format!("RobotPart{part_index}")) {
+			part_entity.add_children(children);
+		}
+		pivot
+	}
+
+	/// Create an entity for a robot part. A part whose mesh could not be loaded is shown as an empty entity so the parts attached to it stay visible.
+	fn create_part_entity(part_index:usize, part_config:&PartConfig) -> Entity {
+		let part_name:String = format!("RobotPart{part_index}");
+
+		// Create the pivot point for the part.
+		let mut pivot:Entity = Entity::new(&(part_name.to_string() + "Pivot"), Mesh::raw(Vec::new(), Vec::new()));
+		pivot.set_position(part_config.position());
+		pivot.set_rotation(part_config.rotation());
+		let mut motor:Entity = Entity::new(&(part_name.to_string() + "Motor"), Mesh::raw(Vec::new(), Vec::new()));
+
+		// Create an entity of the part's primitive or obj.
+		let mut part:Entity = match part_config.primitive() {
+			Some(primitive) => Entity::new(&part_name, primitive.mesh()),
+			None => match Entity::from_obj(&part_name, &crate::storage::asset_path(part_config.obj())) {
+				Ok(part) => part,
+				Err(error) => {
+					eprintln!("Could not load mesh of part '{}': {error}", part_config.name());
+					Entity::new(&part_name, Mesh::raw(Vec::new(), Vec::new()))
 				}
-				
-				// Return the entity.
-				pivot.add_child(motor);
-				return Some(pivot);
 			}
+		};
+
+		// If the part has a motor, modify the pivot.
+		if let Some(motor_config) = part_config.motor() {
+			Self::add_motor_display_to_pivot(&mut pivot, motor_config);
+			
+			// Keep mesh in place despite displacement of motor.
+			part.displace(&motor_config.position().negative());
+
+			// Set rotation of motor based on the robot config.
+			let mut rotation:[f32; 3] = [0.0; 3];
+			rotation[*motor_config.rotation_axis() as usize] = *motor_config.current_rotation();
+			motor.set_euler_rotation(&rotation);
+		}
+		motor.add_child(part);
+
+		// If the part has kinematics configuration, add visuals for it.
+		if let Some(kinematics_config) = part_config.kinematics_config() {
+			motor.add_child(Self::create_kinematics_endpoint_display(part_index, kinematics_config));
 		}
-		None
+		
+		// Return the entity.
+		pivot.add_child(motor);
+		pivot
 	}
 
 	/// Create an entity for a robot motor.
@@ -244,8 +266,8 @@ impl Window {
 		}
 	}
 
-	/// Create an entity for a kinematics endpoint for a joint.
-	fn create_kinematics_endpoint_display(leg_index:usize, joint_index:usize, kinematics_config:&KinematicsConfig) -> Entity {
+	/// Create an entity for a kinematics endpoint for a part.
+	fn create_kinematics_endpoint_display(part_index:usize, kinematics_config:&KinematicsConfig) -> Entity {
 
 		// Size settings.
 		let mesh_size:f32 = 10.0;
@@ -269,12 +291,12 @@ impl Window {
 		mesh.set_material(&SimpleColorMaterial::new(0, 0xFF00FF00, 0));
 
 		// Create the entity.
-		let mut entity:Entity = Entity::new(&format!("RobotLeg{leg_index}Joint{joint_index}KinematicsEndpoint"), mesh);
+		let mut entity:Entity = Entity::new(&format!("RobotPart{part_index}KinematicsEndpoint"), mesh);
 		entity.set_position(kinematics_config.leg_endpoint());
 		entity
 	}
 
-	/// Create an entity for a kinematics endpoint for a joint.
+	/// Create an entity for the step of a leg.
 	fn create_kinematics_step_display(leg_index:usize, kinematics_config:&KinematicsConfig) -> Entity {
 		use crate::kinematics::LegSynchronizer;
 		
@@ -303,31 +325,70 @@ impl Window {
 
 
 
+	/// Create an entity for the target and path of a manipulator.
+	fn create_manipulator_target_display(manipulator_index:usize, manipulator_config:&ManipulatorConfig) -> Entity {
+
+		// Size settings.
+		let marker_size:f32 = 6.0;
+
+		// Create a cross at the target.
+		let target:[f32; 3] = *manipulator_config.target();
+		let mut vertices:Vec<[f32; 3]> = vec![target];
+		let mut faces:Vec<[usize; 3]> = Vec::new();
+		for axis in 0..3 {
+			let mut start:[f32; 3] = target;
+			let mut end:[f32; 3] = target;
+			start[axis] -= marker_size;
+			end[axis] += marker_size;
+			faces.push([0, vertices.len(), vertices.len() + 1]);
+			vertices.push(start);
+			vertices.push(end);
+		}
+
+		// Add a closed line through all waypoints of the path.
+		let path:&Vec<[f32; 3]> = manipulator_config.path();
+		let path_start:usize = vertices.len();
+		vertices.extend_from_slice(path);
+		if path.len() > 1 {
+			for index in 0..path.len() {
+				faces.push([path_start + index, path_start + index, path_start + (index + 1) % path.len()]);
+			}
+		}
+		let mut mesh:Mesh = Mesh::raw(vertices, faces);
+
+		// Set a specific material to the mesh.
+		// TODO: Use material manager later.
+		mesh.set_material(&SimpleColorMaterial::new(0, 0xFFFFAA00, 0));
+
+		// Create the entity.
+		Entity::new(&format!("RobotManipulator{manipulator_index}Target"), mesh)
+	}
+
+
+
 	/* ENTITY UPDATE METHODS */
 	
-	/// Rotate all joints to their current rotation in the scene.
+	/// Rotate all parts to their current rotation in the scene.
 	pub fn update_scene_motors(&self) {
 
-		// Loop through joints with motors.
-		for (leg_index, leg) in RobotConfig::get().legs().iter().enumerate() {
-			for (joint_index, joint) in leg.iter().enumerate() {
-				if joint.motor().is_some() {
-					self.update_scene_motor(leg_index, joint_index)
-				}
+		// Loop through parts with motors.
+		for (part_index, part) in RobotConfig::get().parts().iter().enumerate() {
+			if part.motor().is_some() {
+				self.update_scene_motor(part_index)
 			}
 		}
 
 	}
 
-	/// Rotate a joint to its current rotation in the scene.
-	pub fn update_scene_motor(&self, leg_index:usize, joint_index:usize) {
+	/// Rotate a part to its current rotation in the scene.
+	pub fn update_scene_motor(&self, part_index:usize) {
 
 		// Get motor config.
-		if let Some(joint_config) = RobotConfig::get_mut().get_joint_mut(leg_index, joint_index) {
-			if let Some(motor) = joint_config.motor() {
+		if let Some(part_config) = RobotConfig::get().part(part_index) {
+			if let Some(motor) = part_config.motor() {
 
 				// Get motor entity.
-				if let Some(motor_entity) = Window::get().get_scene_mut().entity_by_name_mut(&format!("RobotLeg{leg_index}Joint{joint_index}Motor")) {
+				if let Some(motor_entity) = Window::get().get_scene_mut().entity_by_name_mut(&format!("RobotPart{part_index}Motor")) {
 
 					// Set rotation.
 					let mut rotation:[f32; 3] = [0.0; 3];
@@ -1,5 +1,5 @@
-use glass_panel::{ Drawable, ListenerType, elements::{ Id, Class, Row, Col, Rectangle, Border, Text, Centered } };
-use crate::ui::Window;
+use glass_panel::{ Drawable, Key, ListenerType, elements::{ Id, Class, Row, Col, Rectangle, Border, Text, Centered } };
+use crate::{ robot_configuration::{ ConfigHistory, RobotConfig }, ui::Window };
 use std::rc::Rc;
 
 impl Window {
@@ -8,8 +8,8 @@ impl Window {
 
 	/// Create a new input group.
 	pub(crate) fn create_toolbar(&self) -> Id {
-		use crate::{ storage, robot_configuration::RobotConfig };
-		use super::file_selector::save_file;
+		use crate::{ storage, exporting::{ UrdfExport, URDF_DEFAULT_SCALE }, importing::import_urdf, project_archive::{ project_archive_for_file, PROJECT_ARCHIVE_EXTENSION } };
+		use super::file_selector::{ save_file, select_dir, select_file };
 		use std::thread;
 
 		// Size calculations.
@@ -29,8 +29,88 @@ impl Window {
 							&self.create_toolbar_button("Save as", &|| {
 								thread::spawn(||{
 									if let Some(new_dir) = save_file(Some(&storage::projects_dir()), Vec::new()) {
-										if let Err(error) = storage::store_project_at(&new_dir) {
-											eprint!("COULD NOT SAVE ROBOT CONFIG TO FILE. {error}");
+										Window::execute_on_ui_thread(move || {
+											if let Err(error) = storage::store_project_at(&new_dir) {
+												eprint!("COULD NOT SAVE ROBOT CONFIG TO FILE. {error}");
+											}
+										});
+									}
+								});
+							}),
+							&spacer,
+							&self.create_toolbar_button("Undo", &|| Window::get().undo_config_edit()),
+							&spacer,
+							&self.create_toolbar_button("Redo", &|| Window::get().redo_config_edit()),
+							&spacer,
+							&self.create_toolbar_button("Pack project", &|| {
+								match storage::pack_project() {
+									Ok(missing_meshes) if missing_meshes.is_empty() => {},
+									Ok(_) => Window::get().execute_post_listener(&|| Window::get().show_mesh_relink_tooltip()),
+									Err(error) => eprintln!("Could not pack project. {error}")
+								}
+							}),
+							&spacer,
+							&self.create_toolbar_button("Export archive", &|| {
+								thread::spawn(||{
+									if let Some(file) = save_file(None, vec![("Project archive", &[PROJECT_ARCHIVE_EXTENSION])]) {
+										let file:String = if file.ends_with(&format!(".{PROJECT_ARCHIVE_EXTENSION}")) { file } else { format!("{file}.{PROJECT_ARCHIVE_EXTENSION}") };
+
+										// Saving the project and rendering its thumbnail happens on the UI thread, only writing the archive happens in the background.
+										Window::execute_on_ui_thread(move || {
+											match project_archive_for_file(&file) {
+												Ok(archive) => {
+													thread::spawn(move || {
+														if let Err(error) = archive.write(&file) {
+															eprintln!("Could not export project archive. {error}");
+														}
+													});
+												},
+												Err(error) => eprintln!("Could not export project archive. {error}")
+											}
+										});
+									}
+								});
+							}),
+							&spacer,
+							&self.create_toolbar_button("Export URDF", &|| {
+								thread::spawn(||{
+									if let Some(dir) = select_dir(None) {
+
+										// The description is created from the robot config on the UI thread, only writing it and copying meshes happens in the background.
+										Window::execute_on_ui_thread(move || {
+											let export:UrdfExport = UrdfExport::from_current_project(URDF_DEFAULT_SCALE);
+											for mesh in export.missing_meshes() {
+												eprintln!("Could not find mesh '{mesh}', its part is exported without geometry.");
+											}
+											thread::spawn(move || {
+												if let Err(error) = export.write(&dir) {
+													eprintln!("Could not export URDF. {error}");
+												}
+											});
+										});
+									}
+								});
+							}),
+							&spacer,
+							&self.create_toolbar_button("Import URDF", &|| {
+								thread::spawn(||{
+									if let Some(file) = select_file(None, vec![("URDF", &["urdf"])]) {
+										match import_urdf(&file, 1.0 / URDF_DEFAULT_SCALE) {
+											Ok((robot_config, warnings)) => {
+												for warning in warnings {
+													eprintln!("{warning}");
+												}
+
+												// Replace the config on the UI thread, which owns the config, its history and the UI.
+												Window::execute_on_ui_thread(move || {
+													ConfigHistory::edit(None, || RobotConfig::replace(robot_config));
+													Window::get().deselect_removed_part();
+													if !RobotConfig::get().missing_meshes().is_empty() {
+														Window::get().execute_post_listener(&|| Window::get().show_mesh_relink_tooltip());
+													}
+												});
+											},
+											Err(error) => eprintln!("Could not import URDF. {error}")
 										}
 									}
 								});
@@ -69,4 +149,42 @@ impl Window {
 		// Return button element.
 		button
 	}
+
+
+	/* HISTORY METHODS */
+
+	/// Undo the last edit of the robot config.
+	pub(crate) fn undo_config_edit(&mut self) {
+		if ConfigHistory::undo() {
+			self.deselect_removed_part();
+		}
+	}
+
+	/// Redo the last undone edit of the robot config.
+	pub(crate) fn redo_config_edit(&mut self) {
+		if ConfigHistory::redo() {
+			self.deselect_removed_part();
+		}
+	}
+
+	/// Undo or redo edits using the keyboard. Ctrl+Z undoes, Ctrl+Y and Ctrl+Shift+Z redo.
+	pub(crate) fn handle_history_shortcuts(&mut self) {
+		let control:bool = [Key::LeftCtrl, Key::RightCtrl, Key::LeftSuper, Key::RightSuper].iter().any(|key| self.window().key_down(*key));
+		let shift:bool = [Key::LeftShift, Key::RightShift].iter().any(|key| self.window().key_down(*key));
+		if !control {
+			return;
+		}
+		if self.window().key_pressed(Key::Y) || (shift && self.window().key_pressed(Key::Z)) {
+			self.redo_config_edit();
+		} else if self.window().key_pressed(Key::Z) {
+			self.undo_config_edit();
+		}
+	}
+
+	/// Deselect the selected part when it no longer exists after the config was replaced, like when undoing or redoing an edit.
+	fn deselect_removed_part(&mut self) {
+		if self.selected_part().is_some_and(|part_index| part_index >= RobotConfig::get().parts().len()) {
+			*self.selected_part_mut() = None;
+		}
+	}
 }
\ No newline at end of file
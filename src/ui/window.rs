@@ -1,17 +1,30 @@
 use glass_panel::{ elements::*, Drawable, DrawableDataSettingDataType, DrawableDataSettings, GlassPanel };
+use crate::robot_configuration::{ ConfigDiagnostic, RobotTemplate, TemplateParameters };
+use std::{ sync::{ Mutex, OnceLock, mpsc::{ channel, Receiver, Sender } }, time::Instant };
 
 
 
+pub(crate) static DEFAULT_SCENE_CAMERA_POSITION:[f32; 3] = [200.0, -200.0, 100.0];
+pub(crate) static DEFAULT_SCENE_CAMERA_ROTATION:[f32; 3] = [22.5, 0.0, -45.0];
+
 static mut STATIC_UI_INSTANCE:Option<Window> = None; // Allows modifications in listeners by providing static access.
+type UiThreadAction = Box<dyn FnOnce() + Send>;
+static UI_THREAD_ACTIONS:OnceLock<(Sender<UiThreadAction>, Mutex<Receiver<UiThreadAction>>)> = OnceLock::new(); // Lets background threads, like the ones waiting for file dialogs, hand their results to the UI thread.
 pub struct Window {
 	window:GlassPanel,
 	settings:DrawableDataSettings,
 	font:Font,
 
-	selected_joint:Option<[usize; 2]>,
+	selected_part:Option<usize>,
+	config_diagnostics:Vec<ConfigDiagnostic>,
+	config_diagnostics_due:Option<Instant>, // When the config should be validated again after it was modified.
 	menu_tab_names:Vec<String>,
 	active_tab:usize,
 
+	// The template the new project tooltip generates a robot from, none creates an empty project.
+	new_project_template:Option<RobotTemplate>,
+	new_project_parameters:TemplateParameters,
+
 	// Used to temporarily store camera modifications to apply after a render rather than during.
 	scene_camera_distance:f32,
 	scene_camera_position:[f32; 3],
@@ -80,17 +93,24 @@ impl Window {
 				("color_scrollbar_outer", 0xFF222222_u32.to_bytes()),
 				("color_scrollbar_inner", 0xFF404040_u32.to_bytes()),
 				("color_tooltip_shadow", 0x88000000_u32.to_bytes()),
+				("color_warning", 0xFFE0C040_u32.to_bytes()),
+				("color_error", 0xFFE05050_u32.to_bytes()),
 
 				("default_font_height", 16_usize.to_bytes())
 			]),
 			font: Font::new("resource/Roboto.ttf", 0.1).unwrap(),
-			selected_joint: None,
+			selected_part: None,
+			config_diagnostics: Vec::new(),
+			config_diagnostics_due: None,
 			menu_tab_names: ["Rendering", "Displacement", "Motor", "Kinematics", "Controller", "Programming"].iter().map(|name| name.to_string()).collect::<Vec<String>>(),
 			active_tab: 0,
+
+			new_project_template: None,
+			new_project_parameters: RobotTemplate::Hexapod.default_parameters(),
 			
 			scene_camera_distance: 300.0,
-			scene_camera_position: [200.0, -200.0, 100.0],
-			scene_camera_rotation: [22.5, 0.0, -45.0],
+			scene_camera_position: DEFAULT_SCENE_CAMERA_POSITION,
+			scene_camera_rotation: DEFAULT_SCENE_CAMERA_ROTATION,
 
 			realtime_kinematics: false
 		};
@@ -131,6 +151,9 @@ impl Window {
 	/// Update the window.
 	pub fn display(&mut self) {
 		self.window.display();
+		self.handle_history_shortcuts();
+		Self::execute_ui_thread_actions();
+		self.update_robot_config_diagnostics_if_due();
 	}
 
 	/// Schedule an action to happen after all listeners are finished.
@@ -138,6 +161,27 @@ impl Window {
 		self.window.execute_post_listener(action);
 	}
 
+	/// Schedule an action to run on the UI thread during the next update. Can be called from any thread, the config, storage and UI should only be modified on the UI thread.
+	pub fn execute_on_ui_thread<T:FnOnce() + Send + 'static>(action:T) {
+		let _ = Self::ui_thread_actions().0.send(Box::new(action));
+	}
+
+	/// Run all actions scheduled by other threads.
+	fn execute_ui_thread_actions() {
+		let actions:Vec<UiThreadAction> = Self::ui_thread_actions().1.lock().unwrap().try_iter().collect::<Vec<UiThreadAction>>();
+		for action in actions {
+			action();
+		}
+	}
+
+	/// Get the channel actions for the UI thread are sent over.
+	fn ui_thread_actions() -> &'static (Sender<UiThreadAction>, Mutex<Receiver<UiThreadAction>>) {
+		UI_THREAD_ACTIONS.get_or_init(|| {
+			let (sender, receiver) = channel::<UiThreadAction>();
+			(sender, Mutex::new(receiver))
+		})
+	}
+
 	/// Get the name of the active tab.
 	pub fn active_tab_name(&self) -> &str {
 		&self.menu_tab_names[self.active_tab]
@@ -177,14 +221,34 @@ impl Window {
 		&mut self.font
 	}
 
-	/// Return a reference to the selected_joint.
-	pub fn selected_joint(&self) -> &Option<[usize; 2]> {
-		&self.selected_joint
+	/// Return a reference to the index of the selected_part.
+	pub fn selected_part(&self) -> &Option<usize> {
+		&self.selected_part
+	}
+
+	/// Return a mutable reference to the index of the selected_part.
+	pub fn selected_part_mut(&mut self) -> &mut Option<usize> {
+		&mut self.selected_part
+	}
+
+	/// Return a reference to the config_diagnostics.
+	pub fn config_diagnostics(&self) -> &Vec<ConfigDiagnostic> {
+		&self.config_diagnostics
+	}
+
+	/// Return a mutable reference to the config_diagnostics.
+	pub fn config_diagnostics_mut(&mut self) -> &mut Vec<ConfigDiagnostic> {
+		&mut self.config_diagnostics
 	}
 
-	/// Return a mutable reference to the selected_joint.
-	pub fn selected_joint_mut(&mut self) -> &mut Option<[usize; 2]> {
-		&mut self.selected_joint
+	/// Return a reference to the config_diagnostics_due.
+	pub fn config_diagnostics_due(&self) -> &Option<Instant> {
+		&self.config_diagnostics_due
+	}
+
+	/// Return a mutable reference to the config_diagnostics_due.
+	pub fn config_diagnostics_due_mut(&mut self) -> &mut Option<Instant> {
+		&mut self.config_diagnostics_due
 	}
 
 	/// Return a reference to the menu_tab_names.
@@ -207,6 +271,26 @@ impl Window {
 		&mut self.active_tab
 	}
 
+	/// Return a reference to the new_project_template.
+	pub fn new_project_template(&self) -> &Option<RobotTemplate> {
+		&self.new_project_template
+	}
+
+	/// Return a mutable reference to the new_project_template.
+	pub fn new_project_template_mut(&mut self) -> &mut Option<RobotTemplate> {
+		&mut self.new_project_template
+	}
+
+	/// Return a reference to the new_project_parameters.
+	pub fn new_project_parameters(&self) -> &TemplateParameters {
+		&self.new_project_parameters
+	}
+
+	/// Return a mutable reference to the new_project_parameters.
+	pub fn new_project_parameters_mut(&mut self) -> &mut TemplateParameters {
+		&mut self.new_project_parameters
+	}
+
 
 	
 	/* MAIN SCENE PROPERTY GETTER METHODS */
@@ -303,7 +387,9 @@ impl Window {
 
 			// Tooltips.
 			&self.create_mesh_replacement_tooltip(),
-			&self.create_project_loading_tooltip()
+			&self.create_project_loading_tooltip(),
+			&self.create_new_project_tooltip(),
+			&self.create_mesh_relink_tooltip()
 		])
 	}
 